@@ -0,0 +1,34 @@
+//! Conditioning throughput on a large pool.
+//!
+//! Run with `--features parallel` to compare BLAKE3 hashing across
+//! threads against the serial run.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use optical_entropy::conditioning::{Conditioner, HashAlgorithm};
+use optical_entropy::extraction::RawBits;
+
+/// One 4K grayscale frame's worth of raw bits.
+const POOL_BYTES: usize = 3840 * 2160;
+
+fn conditioning(c: &mut Criterion) {
+    let data = (0..POOL_BYTES as u64)
+        .map(|i| (i.wrapping_mul(0x9E37_79B9_7F4A_7C15) >> 56) as u8)
+        .collect();
+    let raw = RawBits::from_bytes(data, 1);
+
+    let mut group = c.benchmark_group("condition_4k");
+    group.throughput(Throughput::Bytes(POOL_BYTES as u64));
+    for (name, algorithm) in [
+        ("blake3", HashAlgorithm::Blake3),
+        ("sha256", HashAlgorithm::Sha256),
+    ] {
+        let conditioner = Conditioner::new(algorithm);
+        group.bench_function(name, |b| {
+            b.iter(|| black_box(conditioner.condition(&raw)));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, conditioning);
+criterion_main!(benches);
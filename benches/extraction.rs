@@ -0,0 +1,62 @@
+//! Extraction throughput on 4K frames.
+//!
+//! Compares the allocating and buffered differencing paths; the
+//! buffered path performs no per-frame allocations.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use optical_entropy::capture::Frame;
+use optical_entropy::extraction::{Extractor, TemporalDifferencer};
+
+const WIDTH: u32 = 3840;
+const HEIGHT: u32 = 2160;
+
+fn frames() -> [Frame; 2] {
+    let len = (WIDTH * HEIGHT) as usize;
+    [0u64, 1].map(|s| {
+        let pixels = (0..len as u64)
+            .map(|i| ((i ^ (s << 32)).wrapping_mul(0x9E37_79B9_7F4A_7C15) >> 56) as u8)
+            .collect();
+        Frame::new(pixels, WIDTH, HEIGHT, s)
+    })
+}
+
+fn differencing(c: &mut Criterion) {
+    let frames = frames();
+    let mut group = c.benchmark_group("difference_4k");
+
+    group.bench_function("allocating", |b| {
+        let mut diff = TemporalDifferencer::new();
+        let mut i = 0;
+        b.iter(|| {
+            i ^= 1;
+            black_box(diff.difference(&frames[i]));
+        });
+    });
+
+    group.bench_function("buffered", |b| {
+        let mut diff = TemporalDifferencer::new();
+        let mut i = 0;
+        b.iter(|| {
+            i ^= 1;
+            black_box(diff.difference_buffered(&frames[i]));
+        });
+    });
+
+    group.finish();
+}
+
+fn extractor(c: &mut Criterion) {
+    let frames = frames();
+    let mut extractor = Extractor::new();
+    let mut i = 0;
+
+    c.bench_function("extractor_4k", |b| {
+        b.iter(|| {
+            i ^= 1;
+            black_box(extractor.process(&frames[i]));
+        });
+    });
+}
+
+criterion_group!(benches, differencing, extractor);
+criterion_main!(benches);
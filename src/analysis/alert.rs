@@ -0,0 +1,75 @@
+//! In-process alert hooks for health events.
+//!
+//! Metrics need an external scraper to notice anything. An
+//! [`AlertSink`] registered with a
+//! [`HealthMonitor`](super::HealthMonitor) is called directly when
+//! the source degrades, recovers, is quarantined or latches a
+//! failure, e.g. to page an
+//! operator through a webhook.
+
+use super::threshold::ThresholdViolation;
+
+/// A notable change in source health.
+#[derive(Debug, Clone)]
+pub enum HealthEvent {
+    /// The source was healthy and failed a check.
+    QualityDegraded {
+        /// The check that failed.
+        violation: ThresholdViolation,
+        /// Sample number at which it failed.
+        sample: u64,
+    },
+    /// The source completed a healthy streak after being unhealthy,
+    /// including the first time after start.
+    Recovered {
+        /// Sample number at which it recovered.
+        sample: u64,
+    },
+    /// Repeated failures put the source into quarantine.
+    QuarantineEntered {
+        /// Sample number at which quarantine began.
+        sample: u64,
+        /// Samples the quarantine will last.
+        cooldown: u64,
+    },
+    /// A failure was latched and needs an operator to clear it.
+    FailureLatched {
+        /// The check whose failure was latched.
+        violation: ThresholdViolation,
+        /// Sample number at which it latched.
+        sample: u64,
+    },
+}
+
+/// Receiver of [`HealthEvent`]s.
+///
+/// Called synchronously from
+/// [`HealthMonitor::analyze`](super::HealthMonitor::analyze), so
+/// slow sinks (network calls) should hand events off to another thread.
+pub trait AlertSink: Send {
+    /// Handles one event.
+    fn alert(&self, event: HealthEvent);
+}
+
+/// Sink that writes events to the `tracing` log.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogAlertSink;
+
+impl AlertSink for LogAlertSink {
+    fn alert(&self, event: HealthEvent) {
+        match event {
+            HealthEvent::QualityDegraded { violation, sample } => {
+                tracing::warn!(sample, violation = %violation, "Alert: entropy quality degraded");
+            }
+            HealthEvent::Recovered { sample } => {
+                tracing::info!(sample, "Alert: entropy source recovered");
+            }
+            HealthEvent::QuarantineEntered { sample, cooldown } => {
+                tracing::error!(sample, cooldown, "Alert: entropy source quarantined");
+            }
+            HealthEvent::FailureLatched { violation, sample } => {
+                tracing::error!(sample, violation = %violation, "Alert: entropy source failure latched");
+            }
+        }
+    }
+}
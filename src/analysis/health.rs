@@ -4,17 +4,43 @@
 //! behavior when quality degrades.
 
 use super::{
+    alert::{AlertSink, HealthEvent},
+    quantiles::{HealthQuantiles, RollingQuantiles},
     statistics::StatisticalTests,
     threshold::{QualityThresholds, ThresholdViolation},
 };
+use crate::clock::{Clock, SystemClock};
 use crate::extraction::RawBits;
+use serde::Serialize;
+use std::collections::{BTreeMap, VecDeque};
+use std::time::{Duration, Instant};
+
+/// Number of health transitions retained by [`HealthMonitor::recent_transitions`].
+pub const TRANSITION_HISTORY_LEN: usize = 32;
+
+/// Default consecutive unhealthy samples before entering quarantine.
+const DEFAULT_QUARANTINE_THRESHOLD: u64 = 100;
+
+/// Default quarantine cooldown, in samples.
+const DEFAULT_QUARANTINE_COOLDOWN: u64 = 300;
+
+/// Default minimum sample size, in bytes, for a conclusive health test.
+pub const DEFAULT_MIN_SAMPLE_SIZE: usize = 64;
+
+/// Default weight of the newest sample in the smoothed bias.
+pub const DEFAULT_DRIFT_ALPHA: f64 = 0.05;
+
+/// Default minimum time between logs of the same kind of violation.
+pub const DEFAULT_VIOLATION_LOG_INTERVAL: Duration = Duration::from_secs(10);
 
 /// Current health status of the entropy source.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct HealthMetrics {
     /// Most recent statistical test results.
     pub latest_stats: Option<StatisticalTests>,
     /// Whether the source is currently healthy.
+    ///
+    /// Defaults to `false`: fail-closed, unhealthy until proven otherwise.
     pub is_healthy: bool,
     /// Most recent violation, if any.
     pub last_violation: Option<ThresholdViolation>,
@@ -24,21 +50,69 @@ pub struct HealthMetrics {
     pub consecutive_unhealthy: u64,
     /// Total samples analyzed.
     pub total_samples: u64,
+    /// Samples too small to test, counted in `total_samples` only.
+    pub indeterminate_samples: u64,
+    /// Whether the source is still within its startup grace window.
+    ///
+    /// See [`HealthMonitor::with_startup_grace`].
+    pub is_warming: bool,
 }
 
-impl Default for HealthMetrics {
-    fn default() -> Self {
-        Self {
-            latest_stats: None,
-            is_healthy: false, // Fail-closed: unhealthy until proven otherwise
-            last_violation: None,
-            consecutive_healthy: 0,
-            consecutive_unhealthy: 0,
-            total_samples: 0,
-        }
-    }
+/// Overall state of the entropy source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthState {
+    /// Passing checks; reseeding allowed.
+    Healthy,
+    /// Failing checks, or not proven healthy within the startup grace window.
+    Unhealthy,
+    /// Starting up: not yet proven healthy, but within the startup
+    /// grace window. Reseeding is still refused.
+    Warming,
+    /// Suspended after repeated failures.
+    Quarantined,
+    /// Failed with the failure latch enabled; awaiting operator reset.
+    Latched,
+}
+
+/// Human- and machine-readable summary of source health.
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthSummary {
+    /// Total samples seen since the monitor was created or reset.
+    pub uptime_samples: u64,
+    /// Fraction of samples that passed all checks (0.0 to 1.0).
+    pub healthy_fraction: f64,
+    /// Current state.
+    pub current_state: HealthState,
+    /// Kind of the most recent violation, if the last sample failed.
+    pub last_violation_kind: Option<&'static str>,
+    /// Most recent statistical test results.
+    pub latest_stats: Option<StatisticalTests>,
+}
+
+/// A change in the healthy/unhealthy state of the source.
+#[derive(Debug, Clone)]
+pub struct HealthTransition {
+    /// The new health state.
+    pub is_healthy: bool,
+    /// The violation that caused the source to become unhealthy, if any.
+    pub violation: Option<ThresholdViolation>,
+    /// Value of `total_samples` when the transition occurred.
+    pub sample: u64,
+}
+
+/// Rate-limit state of one kind of violation within its log interval.
+#[derive(Debug, Clone, Copy)]
+struct ViolationLog {
+    /// When a violation of this kind was last logged.
+    logged_at: Instant,
+    /// Violations of this kind suppressed since then.
+    suppressed: u64,
 }
 
+/// Callback invoked on every health transition.
+pub type TransitionCallback = Box<dyn FnMut(&HealthTransition) + Send>;
+
 /// Monitors entropy health over time.
 ///
 /// Implements fail-closed behavior: reseeding is only allowed
@@ -50,6 +124,61 @@ pub struct HealthMonitor {
     metrics: HealthMetrics,
     /// Required consecutive healthy samples to become healthy.
     required_healthy_streak: u64,
+    /// Bounded history of recent transitions (oldest first).
+    transitions: VecDeque<HealthTransition>,
+    /// Optional transition callback.
+    on_transition: Option<TransitionCallback>,
+    /// Optional alert sink.
+    alert_sink: Option<Box<dyn AlertSink>>,
+    /// Consecutive unhealthy samples that trigger quarantine (0 disables).
+    quarantine_threshold: u64,
+    /// Samples to skip once quarantined.
+    quarantine_cooldown: u64,
+    /// Samples remaining in the current quarantine.
+    quarantine_remaining: u64,
+    /// Samples smaller than this many bytes are indeterminate.
+    min_sample_size: usize,
+    /// Fixed analysis window in bytes, if input is re-chunked.
+    analysis_window: Option<usize>,
+    /// Bytes buffered towards the next analysis window.
+    window_buffer: Vec<u8>,
+    /// Run the statistical tests on every `cadence`-th sample.
+    cadence: u64,
+    /// Samples to skip before the next test.
+    until_next_test: u64,
+    /// Latch the first failure until cleared by the operator.
+    latch_failures: bool,
+    /// A failure has been latched.
+    latched: bool,
+    /// Total samples that passed all checks.
+    total_passed: u64,
+    /// Samples after start or reset reported as warming up.
+    startup_grace_samples: u64,
+    /// A healthy streak has completed since start or reset.
+    warmed_up: bool,
+    /// Exponentially weighted moving average of the signed bit bias.
+    smoothed_bias: Option<f64>,
+    /// Weight of the newest sample in `smoothed_bias`.
+    drift_alpha: f64,
+    /// Largest accepted magnitude of `smoothed_bias`, if drift is checked.
+    max_drift_bias: Option<f64>,
+    /// Accepted range of the fraction of set bits, if the fast popcount
+    /// check is enabled.
+    popcount_band: Option<(f64, f64)>,
+    /// Recent absolute bias values.
+    bias_quantiles: RollingQuantiles,
+    /// Recent variance values.
+    variance_quantiles: RollingQuantiles,
+    /// Recent absolute autocorrelation values.
+    autocorrelation_quantiles: RollingQuantiles,
+    /// Time source for rate-limiting violation logs.
+    clock: Box<dyn Clock>,
+    /// Minimum time between logs of the same kind of violation.
+    violation_log_interval: Duration,
+    /// Violation kinds logged within the last log interval.
+    violation_logs: BTreeMap<&'static str, ViolationLog>,
+    /// Violations suppressed in total.
+    suppressed_violations: u64,
 }
 
 impl HealthMonitor {
@@ -59,25 +188,360 @@ impl HealthMonitor {
             thresholds,
             metrics: HealthMetrics::default(),
             required_healthy_streak: 3, // Require 3 good samples
+            transitions: VecDeque::with_capacity(TRANSITION_HISTORY_LEN),
+            on_transition: None,
+            alert_sink: None,
+            quarantine_threshold: DEFAULT_QUARANTINE_THRESHOLD,
+            quarantine_cooldown: DEFAULT_QUARANTINE_COOLDOWN,
+            quarantine_remaining: 0,
+            min_sample_size: DEFAULT_MIN_SAMPLE_SIZE,
+            analysis_window: None,
+            window_buffer: Vec::new(),
+            cadence: 1,
+            until_next_test: 0,
+            latch_failures: false,
+            latched: false,
+            total_passed: 0,
+            startup_grace_samples: 0,
+            warmed_up: false,
+            smoothed_bias: None,
+            drift_alpha: DEFAULT_DRIFT_ALPHA,
+            max_drift_bias: None,
+            popcount_band: None,
+            bias_quantiles: RollingQuantiles::default(),
+            variance_quantiles: RollingQuantiles::default(),
+            autocorrelation_quantiles: RollingQuantiles::default(),
+            clock: Box::new(SystemClock),
+            violation_log_interval: DEFAULT_VIOLATION_LOG_INTERVAL,
+            violation_logs: BTreeMap::new(),
+            suppressed_violations: 0,
         }
     }
 
     /// Creates a monitor with a custom healthy streak requirement.
     pub fn with_streak_requirement(thresholds: QualityThresholds, streak: u64) -> Self {
         Self {
-            thresholds,
-            metrics: HealthMetrics::default(),
             required_healthy_streak: streak.max(1),
+            ..Self::new(thresholds)
         }
     }
 
+    /// Configures quarantine behavior.
+    ///
+    /// After `threshold` consecutive unhealthy samples the monitor is
+    /// quarantined for `cooldown` samples: statistical tests are skipped
+    /// and reseeding is refused. A `threshold` of 0 disables quarantine.
+    pub fn with_quarantine(mut self, threshold: u64, cooldown: u64) -> Self {
+        self.quarantine_threshold = threshold;
+        self.quarantine_cooldown = cooldown;
+        self
+    }
+
+    /// Sets the minimum sample size, in bytes, for a conclusive test.
+    ///
+    /// See [`set_min_sample_size`](Self::set_min_sample_size).
+    pub fn with_min_sample_size(mut self, bytes: usize) -> Self {
+        self.min_sample_size = bytes;
+        self
+    }
+
+    /// Replaces the minimum sample size, in bytes, for a conclusive test.
+    ///
+    /// Smaller samples are indeterminate: they neither pass nor fail,
+    /// and leave streaks and health state unchanged. 0 tests everything.
+    pub fn set_min_sample_size(&mut self, bytes: usize) {
+        self.min_sample_size = bytes;
+    }
+
+    /// Reports the first `samples` samples as warming up.
+    ///
+    /// See [`set_startup_grace`](Self::set_startup_grace).
+    pub fn with_startup_grace(mut self, samples: u64) -> Self {
+        self.set_startup_grace(samples);
+        self
+    }
+
+    /// Replaces the startup grace window, in samples.
+    ///
+    /// A fresh monitor is unhealthy until it completes a healthy
+    /// streak, which on its own is indistinguishable from a failing
+    /// source. Until the first streak completes or `samples` samples
+    /// have been seen, the monitor reports [`HealthState::Warming`]
+    /// instead. This changes reporting only: reseeding stays refused
+    /// until the source is healthy. 0 disables the grace window.
+    pub fn set_startup_grace(&mut self, samples: u64) {
+        self.startup_grace_samples = samples;
+        self.update_warming();
+    }
+
+    /// Returns true while the source is within its startup grace window.
+    pub fn is_warming(&self) -> bool {
+        self.metrics.is_warming
+    }
+
+    /// Recomputes whether the source is still warming up.
+    fn update_warming(&mut self) {
+        self.metrics.is_warming =
+            !self.warmed_up && self.metrics.total_samples < self.startup_grace_samples;
+    }
+
+    /// Analyzes fixed windows of `bytes` bytes (at least 1).
+    ///
+    /// See [`set_analysis_window`](Self::set_analysis_window).
+    pub fn with_analysis_window(mut self, bytes: usize) -> Self {
+        self.set_analysis_window(Some(bytes));
+        self
+    }
+
+    /// Sets or clears the fixed analysis window, in bytes.
+    ///
+    /// Extraction output varies in size with the frame dimensions and
+    /// extraction options, and so do the statistics computed over it.
+    /// With a window set, [`analyze`](Self::analyze) buffers its input
+    /// and tests exactly `bytes` bytes at a time, carrying leftovers to
+    /// the next window, so results are comparable across setups.
+    /// Samples, streaks and quarantine cooldowns then count windows.
+    /// Metrics change only when a window completes, so until then they
+    /// reflect the previous window.
+    pub fn set_analysis_window(&mut self, bytes: Option<usize>) {
+        self.analysis_window = bytes.map(|b| b.max(1));
+    }
+
+    /// Returns the fixed analysis window in bytes, if set.
+    pub fn analysis_window(&self) -> Option<usize> {
+        self.analysis_window
+    }
+
+    /// Runs the statistical tests only on every `n`-th sample (at least 1).
+    ///
+    /// Testing every frame is costly at high frame rates. In between,
+    /// samples are counted and credited with the last tested verdict in
+    /// the healthy fraction, but do not advance streaks, statistics or
+    /// percentiles. This trades detection latency for CPU: a failure
+    /// can go unnoticed for up to `n - 1` samples, and reaching a
+    /// healthy streak takes `n` times as many samples.
+    pub fn with_cadence(mut self, n: u64) -> Self {
+        self.cadence = n.max(1);
+        self.until_next_test = 0;
+        self
+    }
+
+    /// Returns the test cadence; 1 tests every sample.
+    pub fn cadence(&self) -> u64 {
+        self.cadence
+    }
+
+    /// Fails samples once the smoothed bias exceeds `max_bias`.
+    ///
+    /// A source can drift into a bias that stays below
+    /// [`max_bit_bias`](QualityThresholds::max_bit_bias) in every
+    /// sample yet persists. The signed bias of each tested sample feeds
+    /// an exponentially weighted moving average, starting from zero,
+    /// with the newest sample weighted by `alpha` (clamped to 0.0-1.0).
+    /// Random fluctuations cancel out while a steady lean accumulates,
+    /// so `max_bias` can be much tighter than the per-sample threshold.
+    /// Once exceeded, every tested sample fails with
+    /// [`ThresholdViolation::BiasDrift`] until the average recovers.
+    pub fn with_drift_detection(mut self, max_bias: f64, alpha: f64) -> Self {
+        self.max_drift_bias = Some(max_bias);
+        self.drift_alpha = alpha.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Enables a fast popcount check on every sample.
+    ///
+    /// Before the full statistics, and regardless of the
+    /// [cadence](Self::with_cadence), each sample's fraction of set
+    /// bits must lie within `min..=max` (e.g. 0.4-0.6). A sample
+    /// outside the band fails at once with
+    /// [`ThresholdViolation::PopcountOutOfBand`] and the full tests are
+    /// skipped. The check costs one pass of `count_ones`, so it catches
+    /// gross bias (a stuck sensor, a saturated frame) even when the
+    /// full battery only runs on a fraction of samples at high frame
+    /// rates. Samples below the minimum sample size are not checked.
+    pub fn with_popcount_check(mut self, min: f64, max: f64) -> Self {
+        self.popcount_band = Some((min, max));
+        self
+    }
+
+    /// Logs each kind of violation at most once per `interval`.
+    ///
+    /// A failing source fails every sample, usually for the same
+    /// reason. The first violation of each kind is logged; repeats of
+    /// that kind within `interval` are counted instead. Each kind's
+    /// count is logged once its interval has passed, or as soon as the
+    /// source becomes healthy again. A zero interval logs every
+    /// violation. Defaults to [`DEFAULT_VIOLATION_LOG_INTERVAL`].
+    pub fn with_violation_log_interval(mut self, interval: Duration) -> Self {
+        self.violation_log_interval = interval;
+        self
+    }
+
+    /// Replaces the clock used to rate-limit violation logs.
+    pub fn with_clock(mut self, clock: Box<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Returns the number of violations not logged because of the
+    /// [log interval](Self::with_violation_log_interval).
+    pub fn suppressed_violations(&self) -> u64 {
+        self.suppressed_violations
+    }
+
+    /// Returns the number of suppressed violations whose count has not
+    /// been logged yet.
+    pub fn pending_suppressed_violations(&self) -> u64 {
+        self.violation_logs.values().map(|log| log.suppressed).sum()
+    }
+
+    /// Returns the smoothed bit bias, or `None` until a sample is tested.
+    ///
+    /// Measured from the thresholds'
+    /// [`expected_bias_center`](QualityThresholds::expected_bias_center).
+    /// Tracked whether or not drift detection is enabled.
+    pub fn smoothed_bias(&self) -> Option<f64> {
+        self.smoothed_bias
+    }
+
+    /// Enables or disables the failure latch.
+    ///
+    /// SP 800-90B treats a continuous health test failure as a source
+    /// error needing operator intervention. With the latch enabled, the
+    /// first failed check refuses reseeding permanently, whatever later
+    /// samples show, until [`clear_latch`](Self::clear_latch) is called.
+    pub fn with_failure_latch(mut self, enabled: bool) -> Self {
+        self.latch_failures = enabled;
+        self
+    }
+
+    /// Replaces the quality thresholds, e.g. after a config reload.
+    ///
+    /// Takes effect from the next [`analyze`](Self::analyze); the
+    /// current streak and counters are kept.
+    pub fn set_thresholds(&mut self, thresholds: QualityThresholds) {
+        tracing::info!(?thresholds, "Quality thresholds updated");
+        self.thresholds = thresholds;
+    }
+
+    /// Returns the active quality thresholds.
+    pub fn thresholds(&self) -> &QualityThresholds {
+        &self.thresholds
+    }
+
+    /// Replaces the healthy streak requirement (at least 1).
+    pub fn set_streak_requirement(&mut self, streak: u64) {
+        self.required_healthy_streak = streak.max(1);
+    }
+
+    /// Registers a callback invoked whenever `is_healthy` flips.
+    ///
+    /// Replaces any previously registered callback.
+    pub fn on_transition<F>(&mut self, callback: F)
+    where
+        F: FnMut(&HealthTransition) + Send + 'static,
+    {
+        self.on_transition = Some(Box::new(callback));
+    }
+
+    /// Registers a sink notified of degradation, recovery and quarantine.
+    ///
+    /// Replaces any previously registered sink.
+    pub fn set_alert_sink<S: AlertSink + 'static>(&mut self, sink: S) {
+        self.alert_sink = Some(Box::new(sink));
+    }
+
+    /// Returns the most recent health transitions, oldest first.
+    ///
+    /// At most [`TRANSITION_HISTORY_LEN`] entries are retained.
+    pub fn recent_transitions(&self) -> impl Iterator<Item = &HealthTransition> {
+        self.transitions.iter()
+    }
+
     /// Analyzes a sample and updates health status.
+    ///
+    /// While quarantined the sample only advances the cooldown and
+    /// is not tested. With an
+    /// [analysis window](Self::set_analysis_window), the input is
+    /// buffered and each completed window is analyzed as a sample.
     pub fn analyze(&mut self, raw: &RawBits) -> &HealthMetrics {
-        let stats = StatisticalTests::analyze(raw);
+        let Some(window) = self.analysis_window else {
+            self.analyze_sample(raw);
+            return &self.metrics;
+        };
+
+        self.window_buffer.extend_from_slice(raw.data());
+        while self.window_buffer.len() >= window {
+            let sample: Vec<u8> = self.window_buffer.drain(..window).collect();
+            self.analyze_sample(&RawBits::from_bytes(sample, raw.source_frames()));
+        }
+        &self.metrics
+    }
+
+    /// Tests one sample and updates health status.
+    fn analyze_sample(&mut self, raw: &RawBits) {
+        self.flush_suppressed_violations(false);
         self.metrics.total_samples += 1;
+        self.test_sample(raw);
+        self.update_warming();
+    }
 
-        match self.thresholds.check(&stats) {
+    /// Runs the statistical tests on one sample, if due.
+    fn test_sample(&mut self, raw: &RawBits) {
+        if self.quarantine_remaining > 0 {
+            self.quarantine_remaining -= 1;
+            if self.quarantine_remaining == 0 {
+                self.metrics.consecutive_unhealthy = 0;
+                tracing::info!("Entropy source quarantine lifted");
+            }
+            return;
+        }
+
+        if let Err(violation) = self.check_popcount(raw) {
+            self.record_failure(violation);
+            return;
+        }
+
+        if self.until_next_test > 0 {
+            self.until_next_test -= 1;
+            let last_passed =
+                self.metrics.latest_stats.is_some() && self.metrics.last_violation.is_none();
+            if last_passed {
+                self.total_passed += 1;
+            }
+            return;
+        }
+        self.until_next_test = self.cadence - 1;
+
+        let stats = StatisticalTests::analyze(raw);
+        if !stats.is_significant(self.min_sample_size) {
+            self.metrics.indeterminate_samples += 1;
+            tracing::debug!(
+                sample_size = stats.sample_size,
+                min_sample_size = self.min_sample_size,
+                "Sample too small to test, health unchanged"
+            );
+            return;
+        }
+
+        let bias = self.thresholds.bias_deviation(stats.bit_bias);
+        self.bias_quantiles.push(bias.abs());
+        self.variance_quantiles.push(stats.variance);
+        self.autocorrelation_quantiles
+            .push(stats.autocorrelation.abs());
+
+        let smoothed = self.smoothed_bias.unwrap_or(0.0);
+        let smoothed = smoothed + self.drift_alpha * (bias - smoothed);
+        self.smoothed_bias = Some(smoothed);
+
+        let checked = self
+            .thresholds
+            .check(&stats)
+            .and_then(|()| self.check_drift(stats.sample_size));
+
+        match checked {
             Ok(()) => {
+                self.total_passed += 1;
                 self.metrics.consecutive_healthy += 1;
                 self.metrics.consecutive_unhealthy = 0;
                 self.metrics.last_violation = None;
@@ -89,8 +553,11 @@ impl HealthMonitor {
                             streak = self.metrics.consecutive_healthy,
                             "Entropy source became healthy"
                         );
+                        self.flush_suppressed_violations(true);
+                        self.record_transition(true, None);
                     }
                     self.metrics.is_healthy = true;
+                    self.warmed_up = true;
                 }
 
                 tracing::trace!(
@@ -100,24 +567,161 @@ impl HealthMonitor {
                     "Health check passed"
                 );
             }
-            Err(violation) => {
-                self.metrics.consecutive_unhealthy += 1;
-                self.metrics.consecutive_healthy = 0;
-                self.metrics.last_violation = Some(violation.clone());
-
-                // Immediately become unhealthy (fail-closed)
-                if self.metrics.is_healthy {
-                    tracing::warn!(
-                        violation = %violation,
-                        "Entropy source became unhealthy"
-                    );
-                }
-                self.metrics.is_healthy = false;
-            }
+            Err(violation) => self.record_failure(violation),
         }
 
         self.metrics.latest_stats = Some(stats);
-        &self.metrics
+    }
+
+    /// Checks the fraction of set bits against the fast-path band, if set.
+    fn check_popcount(&self, raw: &RawBits) -> Result<(), ThresholdViolation> {
+        let Some((min, max)) = self.popcount_band else {
+            return Ok(());
+        };
+        if raw.is_empty() || raw.len() < self.min_sample_size {
+            return Ok(());
+        }
+
+        let observed = raw.popcount() as f64 / raw.bit_count() as f64;
+        if (min..=max).contains(&observed) {
+            return Ok(());
+        }
+        Err(ThresholdViolation::PopcountOutOfBand {
+            observed,
+            min,
+            max,
+            sample_size: raw.len(),
+        })
+    }
+
+    /// Marks the source unhealthy after a failed check (fail-closed).
+    fn record_failure(&mut self, violation: ThresholdViolation) {
+        self.metrics.consecutive_unhealthy += 1;
+        self.metrics.consecutive_healthy = 0;
+        self.metrics.last_violation = Some(violation.clone());
+        self.log_violation(&violation);
+
+        // Immediately become unhealthy (fail-closed)
+        if self.metrics.is_healthy {
+            tracing::warn!(
+                violation = %violation,
+                "Entropy source became unhealthy"
+            );
+            self.record_transition(false, Some(violation.clone()));
+        }
+        self.metrics.is_healthy = false;
+
+        if self.latch_failures && !self.latched {
+            self.latched = true;
+            tracing::error!(
+                violation = %violation,
+                "Entropy source failure latched; operator reset required"
+            );
+            self.emit(HealthEvent::FailureLatched {
+                violation,
+                sample: self.metrics.total_samples,
+            });
+        }
+
+        if self.quarantine_threshold > 0
+            && self.metrics.consecutive_unhealthy >= self.quarantine_threshold
+            && self.quarantine_cooldown > 0
+        {
+            self.quarantine_remaining = self.quarantine_cooldown;
+            tracing::warn!(
+                consecutive_unhealthy = self.metrics.consecutive_unhealthy,
+                cooldown = self.quarantine_cooldown,
+                "Entropy source quarantined"
+            );
+            self.emit(HealthEvent::QuarantineEntered {
+                sample: self.metrics.total_samples,
+                cooldown: self.quarantine_cooldown,
+            });
+        }
+    }
+
+    /// Logs a violation unless one of the same kind was logged within
+    /// the log interval.
+    fn log_violation(&mut self, violation: &ThresholdViolation) {
+        if let Some(log) = self.violation_logs.get_mut(violation.kind()) {
+            log.suppressed += 1;
+            self.suppressed_violations += 1;
+            return;
+        }
+        tracing::warn!(violation = %violation, "Quality violation");
+        self.violation_logs.insert(
+            violation.kind(),
+            ViolationLog {
+                logged_at: self.clock.now(),
+                suppressed: 0,
+            },
+        );
+    }
+
+    /// Logs the suppressed count of each violation kind whose log
+    /// interval has passed, or of every kind if `all`, and forgets them.
+    fn flush_suppressed_violations(&mut self, all: bool) {
+        let now = self.clock.now();
+        let interval = self.violation_log_interval;
+        self.violation_logs.retain(|&kind, log| {
+            let expired = all || now.saturating_duration_since(log.logged_at) >= interval;
+            if expired && log.suppressed > 0 {
+                tracing::warn!(
+                    kind,
+                    suppressed = log.suppressed,
+                    "Repeated quality violations suppressed"
+                );
+            }
+            !expired
+        });
+    }
+
+    /// Checks the smoothed bias against the drift threshold, if set.
+    fn check_drift(&self, sample_size: usize) -> Result<(), ThresholdViolation> {
+        match (self.max_drift_bias, self.smoothed_bias) {
+            (Some(threshold), Some(observed)) if observed.abs() > threshold => {
+                Err(ThresholdViolation::BiasDrift {
+                    observed,
+                    threshold,
+                    sample_size,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Records a transition and notifies the callback and alert sink.
+    fn record_transition(&mut self, is_healthy: bool, violation: Option<ThresholdViolation>) {
+        let sample = self.metrics.total_samples;
+        let transition = HealthTransition {
+            is_healthy,
+            violation,
+            sample,
+        };
+
+        if let Some(callback) = self.on_transition.as_mut() {
+            callback(&transition);
+        }
+
+        match &transition.violation {
+            Some(violation) if !is_healthy => self.emit(HealthEvent::QualityDegraded {
+                violation: violation.clone(),
+                sample,
+            }),
+            _ => self.emit(HealthEvent::Recovered { sample }),
+        }
+
+        if self.transitions.len() == TRANSITION_HISTORY_LEN {
+            self.transitions.pop_front();
+        }
+        self.transitions.push_back(transition);
+    }
+
+    /// Sends an event to the alert sink, if any.
+    fn emit(&self, event: HealthEvent) {
+        if let Some(sink) = &self.alert_sink {
+            sink.alert(event);
+        }
     }
 
     /// Returns current health metrics.
@@ -127,12 +731,100 @@ impl HealthMonitor {
 
     /// Returns true if reseeding should be allowed.
     pub fn allow_reseed(&self) -> bool {
-        self.metrics.is_healthy
+        self.metrics.is_healthy && !self.is_quarantined() && !self.latched
+    }
+
+    /// Returns true if a failure has been latched.
+    pub fn is_latched(&self) -> bool {
+        self.latched
+    }
+
+    /// Clears a latched failure after operator review.
+    ///
+    /// Reseeding resumes once the source completes a fresh healthy streak.
+    pub fn clear_latch(&mut self) {
+        if self.latched {
+            self.latched = false;
+            self.reset_streak();
+            tracing::warn!("Entropy source failure latch cleared by operator");
+        }
+    }
+
+    /// Returns true if the source is quarantined after repeated failures.
+    pub fn is_quarantined(&self) -> bool {
+        self.quarantine_remaining > 0
+    }
+
+    /// Returns the number of samples left in the current quarantine.
+    pub fn quarantine_remaining(&self) -> u64 {
+        self.quarantine_remaining
+    }
+
+    /// Returns a summary of source health.
+    pub fn summary(&self) -> HealthSummary {
+        let current_state = if self.latched {
+            HealthState::Latched
+        } else if self.is_quarantined() {
+            HealthState::Quarantined
+        } else if self.metrics.is_healthy {
+            HealthState::Healthy
+        } else if self.metrics.is_warming {
+            HealthState::Warming
+        } else {
+            HealthState::Unhealthy
+        };
+
+        let healthy_fraction = if self.metrics.total_samples == 0 {
+            0.0
+        } else {
+            self.total_passed as f64 / self.metrics.total_samples as f64
+        };
+
+        HealthSummary {
+            uptime_samples: self.metrics.total_samples,
+            healthy_fraction,
+            current_state,
+            last_violation_kind: self.metrics.last_violation.as_ref().map(|v| v.kind()),
+            latest_stats: self.metrics.latest_stats.clone(),
+        }
+    }
+
+    /// Returns percentiles of the statistics over recent samples.
+    ///
+    /// Covers the last [`DEFAULT_QUANTILE_WINDOW`](super::DEFAULT_QUANTILE_WINDOW)
+    /// tested samples; `None` until a sample has been tested.
+    pub fn quantiles(&self) -> Option<HealthQuantiles> {
+        Some(HealthQuantiles {
+            bias: self.bias_quantiles.quantiles()?,
+            variance: self.variance_quantiles.quantiles()?,
+            autocorrelation: self.autocorrelation_quantiles.quantiles()?,
+        })
+    }
+
+    /// Clears the healthy streak, requiring a fresh streak before reseeding.
+    ///
+    /// Unlike [`reset`](Self::reset), sample and failure counters are kept.
+    pub fn reset_streak(&mut self) {
+        self.metrics.consecutive_healthy = 0;
+        self.metrics.is_healthy = false;
     }
 
     /// Resets the monitor to initial state.
+    ///
+    /// A latched failure survives; only [`clear_latch`](Self::clear_latch)
+    /// releases it.
     pub fn reset(&mut self) {
         self.metrics = HealthMetrics::default();
+        self.quarantine_remaining = 0;
+        self.total_passed = 0;
+        self.warmed_up = false;
+        self.update_warming();
+        self.until_next_test = 0;
+        self.smoothed_bias = None;
+        self.window_buffer.clear();
+        self.bias_quantiles.clear();
+        self.variance_quantiles.clear();
+        self.autocorrelation_quantiles.clear();
         tracing::info!("Health monitor reset");
     }
 }
@@ -146,9 +838,10 @@ impl Default for HealthMonitor {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clock::ManualClock;
 
     fn make_good_data() -> RawBits {
-        let data: Vec<u8> = (0..1000).map(|i| (i * 17 + 31) as u8).collect();
+        let data: Vec<u8> = (0..1000).map(|i| (i * 53 + 31) as u8).collect();
         RawBits::from_bytes(data, 1)
     }
 
@@ -190,4 +883,469 @@ mod tests {
         monitor.analyze(&make_bad_data());
         assert!(!monitor.allow_reseed());
     }
+
+    #[test]
+    fn test_transition_callback_fires_on_failure() {
+        use std::sync::{Arc, Mutex};
+
+        let mut monitor =
+            HealthMonitor::with_streak_requirement(QualityThresholds::permissive(), 2);
+
+        let fired = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&fired);
+        monitor.on_transition(move |t| sink.lock().unwrap().push(t.clone()));
+
+        // Become healthy, then fail
+        monitor.analyze(&make_good_data());
+        monitor.analyze(&make_good_data());
+        fired.lock().unwrap().clear();
+
+        monitor.analyze(&make_bad_data());
+        monitor.analyze(&make_bad_data());
+
+        // Only the healthy -> unhealthy flip fires
+        let fired = fired.lock().unwrap();
+        assert_eq!(fired.len(), 1);
+        assert!(!fired[0].is_healthy);
+        assert!(matches!(
+            fired[0].violation,
+            Some(ThresholdViolation::BitBias { .. })
+        ));
+
+        let history: Vec<_> = monitor.recent_transitions().collect();
+        assert_eq!(history.len(), 2);
+        assert!(history[0].is_healthy);
+        assert!(!history[1].is_healthy);
+    }
+
+    /// Sink that keeps every event it receives; clones share the list.
+    #[derive(Clone, Default)]
+    struct RecordingSink(std::sync::Arc<std::sync::Mutex<Vec<HealthEvent>>>);
+
+    impl AlertSink for RecordingSink {
+        fn alert(&self, event: HealthEvent) {
+            self.0.lock().unwrap().push(event);
+        }
+    }
+
+    #[test]
+    fn test_alert_sink_sees_degrade_recover_cycle() {
+        let sink = RecordingSink::default();
+        let mut monitor =
+            HealthMonitor::with_streak_requirement(QualityThresholds::permissive(), 2)
+                .with_quarantine(3, 2);
+        monitor.set_alert_sink(sink.clone());
+
+        let good = make_good_data();
+        let bad = make_bad_data();
+        for bits in [&good, &good, &bad, &good, &good, &bad, &bad, &bad] {
+            monitor.analyze(bits);
+        }
+
+        let events = sink.0.lock().unwrap();
+        assert_eq!(events.len(), 5, "{:?}", events);
+        assert!(matches!(events[0], HealthEvent::Recovered { sample: 2 }));
+        assert!(matches!(
+            events[1],
+            HealthEvent::QualityDegraded {
+                violation: ThresholdViolation::BitBias { .. },
+                sample: 3
+            }
+        ));
+        assert!(matches!(events[2], HealthEvent::Recovered { sample: 5 }));
+        assert!(matches!(
+            events[3],
+            HealthEvent::QualityDegraded { sample: 6, .. }
+        ));
+        assert!(matches!(
+            events[4],
+            HealthEvent::QuarantineEntered {
+                sample: 8,
+                cooldown: 2
+            }
+        ));
+    }
+
+    #[test]
+    fn test_quarantine_engages_and_lifts() {
+        let mut monitor =
+            HealthMonitor::with_streak_requirement(QualityThresholds::permissive(), 1)
+                .with_quarantine(5, 10);
+
+        for _ in 0..4 {
+            monitor.analyze(&make_bad_data());
+        }
+        assert!(!monitor.is_quarantined());
+
+        // Fifth consecutive failure triggers quarantine
+        monitor.analyze(&make_bad_data());
+        assert!(monitor.is_quarantined());
+        assert_eq!(monitor.quarantine_remaining(), 10);
+
+        // Good samples during cooldown are not tested
+        for _ in 0..10 {
+            monitor.analyze(&make_good_data());
+            assert!(!monitor.allow_reseed());
+        }
+        assert!(!monitor.is_quarantined());
+        assert_eq!(monitor.metrics().consecutive_unhealthy, 0);
+
+        // Normal testing resumes after cooldown
+        monitor.analyze(&make_good_data());
+        assert!(monitor.allow_reseed());
+    }
+
+    #[test]
+    fn test_set_thresholds_applies_to_next_sample() {
+        let mut monitor =
+            HealthMonitor::with_streak_requirement(QualityThresholds::permissive(), 1);
+
+        monitor.analyze(&make_good_data());
+        assert!(monitor.allow_reseed());
+
+        monitor.set_thresholds(QualityThresholds {
+            min_variance: 1e6,
+            ..QualityThresholds::permissive()
+        });
+        assert_eq!(monitor.thresholds().min_variance, 1e6);
+
+        let metrics = monitor.analyze(&make_good_data());
+        assert!(!metrics.is_healthy);
+        assert!(matches!(
+            metrics.last_violation,
+            Some(ThresholdViolation::LowVariance { .. })
+        ));
+    }
+
+    #[test]
+    fn test_failure_latch_persists_until_cleared() {
+        let mut monitor =
+            HealthMonitor::with_streak_requirement(QualityThresholds::permissive(), 1)
+                .with_failure_latch(true);
+
+        monitor.analyze(&make_good_data());
+        assert!(monitor.allow_reseed());
+
+        monitor.analyze(&make_bad_data());
+        assert!(monitor.is_latched());
+
+        for _ in 0..10 {
+            monitor.analyze(&make_good_data());
+        }
+        monitor.reset();
+        monitor.analyze(&make_good_data());
+        assert!(monitor.metrics().is_healthy);
+        assert!(monitor.is_latched());
+        assert!(!monitor.allow_reseed());
+        assert_eq!(monitor.summary().current_state, HealthState::Latched);
+
+        monitor.clear_latch();
+        assert!(!monitor.is_latched());
+        assert!(!monitor.allow_reseed());
+        monitor.analyze(&make_good_data());
+        assert!(monitor.allow_reseed());
+    }
+
+    #[test]
+    fn test_failure_latch_alerts_once() {
+        let sink = RecordingSink::default();
+        let mut monitor =
+            HealthMonitor::with_streak_requirement(QualityThresholds::permissive(), 1)
+                .with_failure_latch(true);
+        monitor.set_alert_sink(sink.clone());
+
+        monitor.analyze(&make_good_data());
+        monitor.analyze(&make_bad_data());
+        monitor.analyze(&make_bad_data());
+
+        let events = sink.0.lock().unwrap();
+        assert_eq!(events.len(), 3, "{:?}", events);
+        assert!(matches!(
+            events[1],
+            HealthEvent::QualityDegraded { sample: 2, .. }
+        ));
+        assert!(matches!(
+            events[2],
+            HealthEvent::FailureLatched {
+                violation: ThresholdViolation::BitBias { .. },
+                sample: 2
+            }
+        ));
+    }
+
+    #[test]
+    fn test_tiny_sample_is_indeterminate() {
+        let mut monitor =
+            HealthMonitor::with_streak_requirement(QualityThresholds::permissive(), 2);
+
+        monitor.analyze(&make_good_data());
+        assert_eq!(monitor.metrics().consecutive_healthy, 1);
+
+        // Four bytes say nothing about the source either way
+        monitor.analyze(&RawBits::from_bytes(vec![0x0F, 0xF0, 0x33, 0xCC], 1));
+        assert_eq!(monitor.metrics().consecutive_healthy, 1);
+        assert!(!monitor.metrics().is_healthy);
+        assert_eq!(monitor.metrics().indeterminate_samples, 1);
+
+        monitor.set_min_sample_size(0);
+        monitor.analyze(&RawBits::from_bytes(vec![0x0F, 0xF0, 0x33, 0xCC], 1));
+        assert_eq!(monitor.metrics().indeterminate_samples, 1);
+        assert_eq!(monitor.metrics().total_samples, 3);
+    }
+
+    #[test]
+    fn test_analysis_window_rechunks_input() {
+        let mut monitor =
+            HealthMonitor::with_streak_requirement(QualityThresholds::permissive(), 1)
+                .with_analysis_window(1000);
+        let data = make_good_data().data().repeat(3);
+
+        // 300 + 500 bytes: no window complete yet
+        monitor.analyze(&RawBits::from_bytes(data[..300].to_vec(), 1));
+        monitor.analyze(&RawBits::from_bytes(data[300..800].to_vec(), 1));
+        assert_eq!(monitor.metrics().total_samples, 0);
+        assert!(monitor.metrics().latest_stats.is_none());
+
+        // 800 + 400 bytes: one window, 200 bytes carried over
+        monitor.analyze(&RawBits::from_bytes(data[800..1200].to_vec(), 1));
+        assert_eq!(monitor.metrics().total_samples, 1);
+        let stats = monitor.metrics().latest_stats.as_ref().unwrap();
+        assert_eq!(stats.sample_size, 1000);
+        assert!(monitor.metrics().is_healthy);
+
+        // 200 + 1800 bytes: exactly two more windows
+        monitor.analyze(&RawBits::from_bytes(data[1200..].to_vec(), 1));
+        assert_eq!(monitor.metrics().total_samples, 3);
+    }
+
+    #[test]
+    fn test_cadence_tests_every_nth_sample() {
+        let mut monitor =
+            HealthMonitor::with_streak_requirement(QualityThresholds::permissive(), 2)
+                .with_cadence(5);
+
+        // Each sample has a distinct length, identifying the tested ones
+        let data = make_good_data();
+        let mut tested = Vec::new();
+        for i in 0..11 {
+            monitor.analyze(&RawBits::from_bytes(data.data()[..900 + i].to_vec(), 1));
+            let stats = monitor.metrics().latest_stats.as_ref().unwrap();
+            if tested.last() != Some(&stats.sample_size) {
+                tested.push(stats.sample_size);
+            }
+        }
+
+        assert_eq!(tested, vec![900, 905, 910]);
+        assert_eq!(monitor.metrics().total_samples, 11);
+        // Streaks count tested samples only
+        assert_eq!(monitor.metrics().consecutive_healthy, 3);
+        assert!((monitor.summary().healthy_fraction - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_summary_healthy_fraction() {
+        let mut monitor =
+            HealthMonitor::with_streak_requirement(QualityThresholds::permissive(), 1);
+
+        for _ in 0..3 {
+            monitor.analyze(&make_good_data());
+        }
+        monitor.analyze(&make_bad_data());
+
+        let summary = monitor.summary();
+        assert_eq!(summary.uptime_samples, 4);
+        assert!((summary.healthy_fraction - 0.75).abs() < 1e-9);
+        assert_eq!(summary.current_state, HealthState::Unhealthy);
+        assert_eq!(summary.last_violation_kind, Some("bit_bias"));
+        assert!(summary.latest_stats.is_some());
+    }
+
+    #[test]
+    fn test_popcount_check_fails_fast() {
+        let mut monitor =
+            HealthMonitor::with_streak_requirement(QualityThresholds::permissive(), 1)
+                .with_cadence(100)
+                .with_popcount_check(0.4, 0.6);
+
+        monitor.analyze(&make_good_data());
+        assert!(monitor.metrics().is_healthy);
+
+        // The full tests are not due for 99 samples; the fast path trips now
+        let metrics = monitor.analyze(&make_bad_data());
+        assert!(!metrics.is_healthy);
+        assert!(matches!(
+            metrics.last_violation,
+            Some(ThresholdViolation::PopcountOutOfBand { observed, .. }) if observed == 1.0
+        ));
+        assert_eq!(metrics.last_violation.as_ref().unwrap().kind(), "popcount");
+        assert_eq!(metrics.consecutive_unhealthy, 1);
+    }
+
+    #[test]
+    fn test_warming_until_first_streak() {
+        let mut monitor =
+            HealthMonitor::with_streak_requirement(QualityThresholds::permissive(), 3)
+                .with_startup_grace(10);
+        assert_eq!(monitor.summary().current_state, HealthState::Warming);
+
+        // A failure inside the grace window is still warming up
+        monitor.analyze(&make_bad_data());
+        monitor.analyze(&make_good_data());
+        monitor.analyze(&make_good_data());
+        assert!(monitor.is_warming());
+        assert_eq!(monitor.summary().current_state, HealthState::Warming);
+        assert!(!monitor.allow_reseed());
+
+        monitor.analyze(&make_good_data());
+        assert!(!monitor.is_warming());
+        assert_eq!(monitor.summary().current_state, HealthState::Healthy);
+
+        // Once warmed up, failures are failures
+        monitor.analyze(&make_bad_data());
+        assert_eq!(monitor.summary().current_state, HealthState::Unhealthy);
+    }
+
+    #[test]
+    fn test_grace_window_expires() {
+        let mut monitor = HealthMonitor::default().with_startup_grace(2);
+        monitor.analyze(&make_bad_data());
+        assert_eq!(monitor.summary().current_state, HealthState::Warming);
+        monitor.analyze(&make_bad_data());
+        assert_eq!(monitor.summary().current_state, HealthState::Unhealthy);
+
+        monitor.reset();
+        assert_eq!(monitor.summary().current_state, HealthState::Warming);
+    }
+
+    #[test]
+    fn test_drift_detection_trips_on_steady_bias() {
+        use rand_chacha::ChaCha8Rng;
+        use rand_core::{RngCore, SeedableRng};
+
+        // Each bit is one with probability 17/32: a bias of about 0.03
+        let mut rng = ChaCha8Rng::seed_from_u64(5);
+        let mut biased_sample = || {
+            let data: Vec<u8> = (0..1000)
+                .map(|_| {
+                    let bytes = rng.next_u64().to_le_bytes();
+                    bytes[0] | (bytes[1] & bytes[2] & bytes[3] & bytes[4])
+                })
+                .collect();
+            RawBits::from_bytes(data, 1)
+        };
+
+        let mut monitor = HealthMonitor::with_streak_requirement(QualityThresholds::default(), 1)
+            .with_drift_detection(0.02, 0.1);
+
+        let mut tripped_at = None;
+        for i in 0..50 {
+            let sample = biased_sample();
+            let stats = StatisticalTests::analyze(&sample);
+            assert!(monitor.thresholds().check(&stats).is_ok());
+
+            let metrics = monitor.analyze(&sample);
+            if let Some(ThresholdViolation::BiasDrift { observed, .. }) = metrics.last_violation {
+                assert!(observed > 0.02);
+                tripped_at = Some(i);
+                break;
+            }
+            assert!(metrics.is_healthy);
+        }
+
+        // The average starts at zero, so one sample alone does not trip it
+        let tripped_at = tripped_at.expect("drift never detected");
+        assert!(tripped_at > 1);
+        assert!(!monitor.allow_reseed());
+
+        monitor.reset();
+        assert!(monitor.smoothed_bias().is_none());
+    }
+
+    #[test]
+    fn test_quantiles_expose_intermittent_failures() {
+        let mut monitor = HealthMonitor::new(QualityThresholds::permissive());
+        assert!(monitor.quantiles().is_none());
+
+        for i in 0..100 {
+            if i % 20 == 0 {
+                monitor.analyze(&make_bad_data());
+            } else {
+                monitor.analyze(&make_good_data());
+            }
+        }
+
+        let quantiles = monitor.quantiles().unwrap();
+        assert!(quantiles.bias.p50 < 0.01);
+        assert!((quantiles.bias.p99 - 0.5).abs() < 0.001);
+
+        monitor.reset();
+        assert!(monitor.quantiles().is_none());
+    }
+
+    #[test]
+    fn test_repeated_violations_rate_limited() {
+        let clock = ManualClock::new();
+        let mut monitor = HealthMonitor::new(QualityThresholds::permissive())
+            .with_clock(Box::new(clock.clone()))
+            .with_violation_log_interval(Duration::from_secs(10));
+
+        // Only the first of a burst of identical violations is logged
+        for _ in 0..50 {
+            monitor.analyze(&make_bad_data());
+        }
+        assert_eq!(monitor.suppressed_violations(), 49);
+
+        assert_eq!(monitor.pending_suppressed_violations(), 49);
+
+        // Once the interval has passed, the suppressed count is logged
+        // and the next violation is logged again
+        clock.advance(Duration::from_secs(11));
+        monitor.analyze(&make_bad_data());
+        assert_eq!(monitor.suppressed_violations(), 49);
+        assert_eq!(monitor.pending_suppressed_violations(), 0);
+        monitor.analyze(&make_bad_data());
+        assert_eq!(monitor.suppressed_violations(), 50);
+
+        // A different kind of violation is logged at once
+        monitor.analyze(&RawBits::from_bytes(vec![0x55; 1000], 1));
+        assert_eq!(
+            monitor.metrics().last_violation.as_ref().unwrap().kind(),
+            "low_variance"
+        );
+        assert_eq!(monitor.suppressed_violations(), 50);
+    }
+
+    #[test]
+    fn test_suppressed_violations_flushed() {
+        let clock = ManualClock::new();
+        let mut monitor =
+            HealthMonitor::with_streak_requirement(QualityThresholds::permissive(), 1)
+                .with_clock(Box::new(clock.clone()))
+                .with_violation_log_interval(Duration::from_secs(10));
+        let low_variance = RawBits::from_bytes(vec![0x55; 1000], 1);
+
+        // Alternating kinds are rate-limited separately
+        for _ in 0..10 {
+            monitor.analyze(&make_bad_data());
+            monitor.analyze(&low_variance);
+        }
+        assert_eq!(monitor.suppressed_violations(), 18);
+        assert_eq!(monitor.pending_suppressed_violations(), 18);
+
+        // Recovery reports the counts without waiting for another violation
+        monitor.analyze(&make_good_data());
+        assert!(monitor.metrics().is_healthy);
+        assert_eq!(monitor.pending_suppressed_violations(), 0);
+
+        // So does the interval passing, even if that kind never recurs
+        for _ in 0..5 {
+            monitor.analyze(&make_bad_data());
+        }
+        assert_eq!(monitor.pending_suppressed_violations(), 4);
+        clock.advance(Duration::from_secs(11));
+        monitor.analyze(&low_variance);
+        assert!(!monitor.metrics().is_healthy);
+        assert_eq!(monitor.pending_suppressed_violations(), 0);
+        assert_eq!(monitor.suppressed_violations(), 22);
+    }
 }
@@ -0,0 +1,75 @@
+//! Per-region noise analysis.
+//!
+//! Helps aim the camera at the noisiest part of the scene by
+//! measuring local pixel variance across a grid of cells.
+
+use super::statistics::StatisticalTests;
+use crate::capture::Frame;
+
+/// Computes per-cell pixel variance over a `(columns, rows)` grid.
+///
+/// Returns one value per cell in row-major order. Variance is a
+/// proxy for local noise; higher cells are better candidates for a
+/// region of interest. Assumes one byte per pixel (grayscale).
+/// Cells beyond the frame bounds, or a zero-sized grid, yield no data.
+pub fn spatial_heatmap(frame: &Frame, grid: (usize, usize)) -> Vec<f64> {
+    let (cols, rows) = grid;
+    let width = frame.width() as usize;
+    let height = frame.height() as usize;
+    let pixels = frame.pixels();
+
+    let mut heatmap = Vec::with_capacity(cols * rows);
+    for row in 0..rows {
+        let y0 = row * height / rows;
+        let y1 = (row + 1) * height / rows;
+        for col in 0..cols {
+            let x0 = col * width / cols;
+            let x1 = (col + 1) * width / cols;
+
+            let cell: Vec<u8> = (y0..y1)
+                .flat_map(|y| {
+                    let start = (y * width + x0).min(pixels.len());
+                    let end = (y * width + x1).min(pixels.len());
+                    pixels[start..end].iter().copied()
+                })
+                .collect();
+
+            heatmap.push(StatisticalTests::compute_variance(&cell));
+        }
+    }
+
+    heatmap
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_noisy_quadrant_is_hottest() {
+        let (width, height) = (64usize, 64usize);
+        let pixels: Vec<u8> = (0..width * height)
+            .map(|i| {
+                let (x, y) = (i % width, i / width);
+                if x >= width / 2 && y < height / 2 {
+                    // Top-right quadrant: noise
+                    ((i as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15) >> 56) as u8
+                } else {
+                    128
+                }
+            })
+            .collect();
+        let frame = Frame::new(pixels, width as u32, height as u32, 1);
+
+        let heatmap = spatial_heatmap(&frame, (2, 2));
+        assert_eq!(heatmap.len(), 4);
+
+        let hottest = heatmap
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(i, _)| i);
+        assert_eq!(hottest, Some(1));
+        assert_eq!(heatmap[0], 0.0);
+    }
+}
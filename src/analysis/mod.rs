@@ -4,10 +4,22 @@
 //! for monitoring entropy quality. These are sanity checks,
 //! not cryptographic proofs of entropy.
 
+mod alert;
 mod health;
+mod heatmap;
+mod quantiles;
 mod statistics;
 mod threshold;
 
-pub use health::{HealthMetrics, HealthMonitor};
+pub use alert::{AlertSink, HealthEvent, LogAlertSink};
+pub use health::{
+    HealthMetrics, HealthMonitor, HealthState, HealthSummary, HealthTransition, TransitionCallback,
+    DEFAULT_DRIFT_ALPHA, DEFAULT_MIN_SAMPLE_SIZE, DEFAULT_VIOLATION_LOG_INTERVAL,
+    TRANSITION_HISTORY_LEN,
+};
+pub use heatmap::spatial_heatmap;
+pub use quantiles::{HealthQuantiles, Quantiles, RollingQuantiles, DEFAULT_QUANTILE_WINDOW};
 pub use statistics::StatisticalTests;
-pub use threshold::{QualityThresholds, ThresholdViolation};
+pub use threshold::{
+    QualityThresholds, ThresholdError, ThresholdViolation, VarianceMode, DEFAULT_BIAS_CENTER,
+};
@@ -0,0 +1,162 @@
+//! Rolling percentiles of health statistics.
+//!
+//! The latest test result hides intermittent degradation: a source
+//! that fails one sample in twenty looks fine most of the time. Tail
+//! percentiles over a sliding window make such patterns visible.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Default number of samples retained per statistic.
+pub const DEFAULT_QUANTILE_WINDOW: usize = 1024;
+
+/// Median and tail percentiles of one statistic.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Quantiles {
+    /// 50th percentile.
+    pub p50: f64,
+    /// 95th percentile.
+    pub p95: f64,
+    /// 99th percentile.
+    pub p99: f64,
+}
+
+/// Percentiles of each health statistic over the recent window.
+///
+/// Bias and autocorrelation are tracked as magnitudes, matching how
+/// [`QualityThresholds`](super::QualityThresholds) checks them.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HealthQuantiles {
+    /// Absolute bit bias.
+    pub bias: Quantiles,
+    /// Byte-level variance.
+    pub variance: Quantiles,
+    /// Absolute lag-1 autocorrelation.
+    pub autocorrelation: Quantiles,
+}
+
+/// Percentiles over a sliding window of the most recent values.
+///
+/// Quantiles are computed exactly (nearest rank) from the window on
+/// each query, so queries cost `O(n log n)` in the window size.
+#[derive(Debug, Clone)]
+pub struct RollingQuantiles {
+    values: VecDeque<f64>,
+    window: usize,
+}
+
+impl RollingQuantiles {
+    /// Creates a tracker retaining the last `window` values (at least 1).
+    pub fn new(window: usize) -> Self {
+        let window = window.max(1);
+        Self {
+            values: VecDeque::with_capacity(window),
+            window,
+        }
+    }
+
+    /// Records a value, evicting the oldest once the window is full.
+    ///
+    /// NaN values are ignored.
+    pub fn push(&mut self, value: f64) {
+        if value.is_nan() {
+            return;
+        }
+        if self.values.len() == self.window {
+            self.values.pop_front();
+        }
+        self.values.push_back(value);
+    }
+
+    /// Returns the number of values in the window.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns true if no values have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the window size.
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Returns the `q` quantile (0.0 to 1.0), or `None` if empty.
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        let sorted = self.sorted();
+        Self::nearest_rank(&sorted, q)
+    }
+
+    /// Returns p50, p95 and p99, or `None` if empty.
+    pub fn quantiles(&self) -> Option<Quantiles> {
+        let sorted = self.sorted();
+        Some(Quantiles {
+            p50: Self::nearest_rank(&sorted, 0.50)?,
+            p95: Self::nearest_rank(&sorted, 0.95)?,
+            p99: Self::nearest_rank(&sorted, 0.99)?,
+        })
+    }
+
+    /// Discards all recorded values.
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+
+    fn sorted(&self) -> Vec<f64> {
+        let mut sorted: Vec<f64> = self.values.iter().copied().collect();
+        sorted.sort_by(f64::total_cmp);
+        sorted
+    }
+
+    fn nearest_rank(sorted: &[f64], q: f64) -> Option<f64> {
+        if sorted.is_empty() {
+            return None;
+        }
+        let rank = (q.clamp(0.0, 1.0) * sorted.len() as f64).ceil() as usize;
+        Some(sorted[rank.saturating_sub(1).min(sorted.len() - 1)])
+    }
+}
+
+impl Default for RollingQuantiles {
+    fn default() -> Self {
+        Self::new(DEFAULT_QUANTILE_WINDOW)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_chacha::ChaCha8Rng;
+    use rand_core::{RngCore, SeedableRng};
+
+    #[test]
+    fn test_median_of_uniform_distribution() {
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let mut quantiles = RollingQuantiles::new(10_000);
+        for _ in 0..10_000 {
+            quantiles.push(rng.next_u32() as f64 / u32::MAX as f64);
+        }
+
+        let q = quantiles.quantiles().unwrap();
+        assert!((q.p50 - 0.50).abs() < 0.02, "p50 = {}", q.p50);
+        assert!((q.p95 - 0.95).abs() < 0.02, "p95 = {}", q.p95);
+        assert!((q.p99 - 0.99).abs() < 0.02, "p99 = {}", q.p99);
+    }
+
+    #[test]
+    fn test_window_evicts_oldest() {
+        let mut quantiles = RollingQuantiles::new(100);
+        assert!(quantiles.quantiles().is_none());
+
+        for i in 0..1000 {
+            quantiles.push(i as f64);
+        }
+
+        assert_eq!(quantiles.len(), 100);
+        assert_eq!(quantiles.quantile(0.0), Some(900.0));
+        assert_eq!(quantiles.quantile(0.5), Some(949.0));
+        assert_eq!(quantiles.quantile(1.0), Some(999.0));
+    }
+}
@@ -5,9 +5,32 @@
 //! but not sufficient for good entropy.
 
 use crate::extraction::RawBits;
+use serde::{Deserialize, Serialize};
+
+/// Variance of uniformly distributed bytes, `(256² - 1) / 12`.
+const UNIFORM_BYTE_VARIANCE: f64 = 5461.25;
+
+/// Weight of the bias component in [`StatisticalTests::quality_score`].
+const BIAS_WEIGHT: f64 = 0.4;
+/// Weight of the variance component in [`StatisticalTests::quality_score`].
+const VARIANCE_WEIGHT: f64 = 0.3;
+/// Weight of the autocorrelation component in [`StatisticalTests::quality_score`].
+const AUTOCORRELATION_WEIGHT: f64 = 0.3;
+
+/// Statistics normalized to `[0, 1]`, where 1 is what uniform random
+/// bytes produce; see [`StatisticalTests::quality_score`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct QualityComponents {
+    /// `1 - 2|bit_bias|`.
+    pub bias: f64,
+    /// Variance relative to uniform bytes.
+    pub variance: f64,
+    /// `1 - |autocorrelation|`.
+    pub autocorrelation: f64,
+}
 
 /// Statistical test results.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StatisticalTests {
     /// Bit bias (deviation from 0.5).
     pub bit_bias: f64,
@@ -32,8 +55,16 @@ impl StatisticalTests {
         }
     }
 
+    /// Returns true if at least `min_samples` bytes were analyzed.
+    ///
+    /// Statistics over a handful of bytes are noise: four bytes can
+    /// easily look perfectly unbiased or hopelessly correlated.
+    pub fn is_significant(&self, min_samples: usize) -> bool {
+        self.sample_size >= min_samples
+    }
+
     /// Computes the variance of byte values.
-    fn compute_variance(data: &[u8]) -> f64 {
+    pub(crate) fn compute_variance(data: &[u8]) -> f64 {
         if data.is_empty() {
             return 0.0;
         }
@@ -71,6 +102,74 @@ impl StatisticalTests {
         covariance / variance
     }
 
+    /// Counts occurrences of each byte value.
+    pub fn byte_histogram(data: &[u8]) -> [u64; 256] {
+        let mut counts = [0u64; 256];
+        for &b in data {
+            counts[b as usize] += 1;
+        }
+        counts
+    }
+
+    /// Estimates min-entropy per byte from the most common byte value.
+    ///
+    /// Returns `-log2(p_max)` where `p_max` is the observed frequency
+    /// of the most common value (NIST SP 800-90B most-common-value
+    /// style, without the confidence bound). Ranges from 0.0 to 8.0.
+    pub fn min_entropy_per_byte(data: &[u8]) -> f64 {
+        if data.is_empty() {
+            return 0.0;
+        }
+
+        let max_count = Self::byte_histogram(data).into_iter().max().unwrap_or(0);
+        let p_max = max_count as f64 / data.len() as f64;
+
+        -p_max.log2()
+    }
+
+    /// Summarizes the tests as one advisory score in `[0, 1]`.
+    ///
+    /// Each statistic is normalized to `[0, 1]`, where 1 is what
+    /// uniform random bytes produce:
+    ///
+    /// - bias: `1 - 2|bit_bias|`
+    /// - variance: `variance / 5461.25` (the uniform byte variance), capped at 1
+    /// - autocorrelation: `1 - |autocorrelation|`
+    ///
+    /// The score is their weighted geometric mean, with weights 0.4 for
+    /// bias and 0.3 each for variance and autocorrelation, so any one
+    /// statistic at its worst pulls the score to 0. Like the tests it
+    /// summarizes, a high score is a sanity check, not evidence of
+    /// entropy.
+    pub fn quality_score(&self) -> f64 {
+        let QualityComponents {
+            bias,
+            variance,
+            autocorrelation,
+        } = self.quality_components();
+
+        bias.powf(BIAS_WEIGHT)
+            * variance.powf(VARIANCE_WEIGHT)
+            * autocorrelation.powf(AUTOCORRELATION_WEIGHT)
+    }
+
+    /// Returns the normalized statistics that
+    /// [`quality_score`](Self::quality_score) combines, each in `[0, 1]`.
+    pub(crate) fn quality_components(&self) -> QualityComponents {
+        let normalize = |component: f64| {
+            if component.is_nan() {
+                0.0
+            } else {
+                component.clamp(0.0, 1.0)
+            }
+        };
+        QualityComponents {
+            bias: normalize(1.0 - 2.0 * self.bit_bias.abs()),
+            variance: normalize(self.variance / UNIFORM_BYTE_VARIANCE),
+            autocorrelation: normalize(1.0 - self.autocorrelation.abs()),
+        }
+    }
+
     /// Returns true if results look reasonable (not proof of quality).
     pub fn looks_reasonable(&self) -> bool {
         // These are loose sanity checks, not security guarantees
@@ -86,6 +185,17 @@ impl StatisticalTests {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_byte_histogram_bins() {
+        let mut data = vec![0x00u8; 10];
+        data.extend(vec![0xFFu8; 10]);
+
+        let histogram = StatisticalTests::byte_histogram(&data);
+        assert_eq!(histogram.iter().filter(|&&count| count > 0).count(), 2);
+        assert_eq!(histogram[0x00], 10);
+        assert_eq!(histogram[0xFF], 10);
+    }
+
     #[test]
     fn test_uniform_random_passes() {
         // Simulated "random" data (alternating pattern for predictability)
@@ -110,6 +220,29 @@ mod tests {
         assert!(!stats.looks_reasonable());
     }
 
+    #[test]
+    fn test_quality_score_ranks_data() {
+        use rand_chacha::ChaCha8Rng;
+        use rand_core::{RngCore, SeedableRng};
+
+        let constant = StatisticalTests::analyze(&RawBits::from_bytes(vec![0x80; 1000], 1));
+        assert!(constant.quality_score() < 0.01);
+
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let mut random = vec![0u8; 4096];
+        rng.fill_bytes(&mut random);
+        let random = StatisticalTests::analyze(&RawBits::from_bytes(random, 1));
+        assert!(random.quality_score() > 0.95);
+
+        // Each bit set with probability 0.75: usable but clearly skewed
+        let skewed: Vec<u8> = (0..4096)
+            .map(|_| (rng.next_u32() as u8) | (rng.next_u32() as u8))
+            .collect();
+        let skewed = StatisticalTests::analyze(&RawBits::from_bytes(skewed, 1));
+        let score = skewed.quality_score();
+        assert!(score > 0.2 && score < 0.8, "score {}", score);
+    }
+
     #[test]
     fn test_all_ones_biased() {
         let data = vec![0xFFu8; 1000];
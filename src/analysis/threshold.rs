@@ -6,15 +6,58 @@
 use super::statistics::StatisticalTests;
 use serde::{Deserialize, Serialize};
 
+/// Default expected one-probability: an unbiased source.
+pub const DEFAULT_BIAS_CENTER: f64 = 0.5;
+
+fn default_bias_center() -> f64 {
+    DEFAULT_BIAS_CENTER
+}
+
+/// How [`QualityThresholds::min_variance`] is interpreted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "mode")]
+pub enum VarianceMode {
+    /// `min_variance` is an absolute variance of sample values.
+    #[default]
+    Absolute,
+    /// `min_variance` is a fraction (0.0 to 1.0) of the maximum
+    /// possible variance for samples of the given bit depth.
+    Fraction {
+        /// Bits per sample (8 for standard byte data).
+        bit_depth: u32,
+    },
+}
+
+impl VarianceMode {
+    /// Returns the maximum possible variance for samples of `bit_depth` bits.
+    ///
+    /// Attained when half the samples sit at each extreme of the range.
+    pub fn max_variance(bit_depth: u32) -> f64 {
+        let max_value = 2f64.powi(bit_depth as i32) - 1.0;
+        max_value * max_value / 4.0
+    }
+}
+
 /// Quality thresholds for entropy monitoring.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QualityThresholds {
-    /// Maximum acceptable bit bias (absolute value).
+    /// Maximum acceptable bit bias: the absolute deviation of the
+    /// fraction of set bits from `expected_bias_center`.
     pub max_bit_bias: f64,
     /// Minimum acceptable variance.
     pub min_variance: f64,
     /// Maximum acceptable autocorrelation (absolute value).
     pub max_autocorrelation: f64,
+    /// Interpretation of `min_variance`.
+    #[serde(default)]
+    pub variance_mode: VarianceMode,
+    /// Expected fraction of set bits (0.0 to 1.0).
+    ///
+    /// 0.5 for an unbiased source. Some extraction strategies have a
+    /// different natural one-probability; conditioning removes that
+    /// bias, so the health tests can be centered on it instead.
+    #[serde(default = "default_bias_center")]
+    pub expected_bias_center: f64,
 }
 
 impl Default for QualityThresholds {
@@ -23,6 +66,8 @@ impl Default for QualityThresholds {
             max_bit_bias: 0.05,       // 5% bias tolerance
             min_variance: 500.0,      // Require meaningful variation
             max_autocorrelation: 0.3, // Low correlation tolerance
+            variance_mode: VarianceMode::Absolute,
+            expected_bias_center: DEFAULT_BIAS_CENTER,
         }
     }
 }
@@ -34,6 +79,8 @@ impl QualityThresholds {
             max_bit_bias: 0.02,
             min_variance: 1000.0,
             max_autocorrelation: 0.1,
+            variance_mode: VarianceMode::Absolute,
+            expected_bias_center: DEFAULT_BIAS_CENTER,
         }
     }
 
@@ -43,22 +90,62 @@ impl QualityThresholds {
             max_bit_bias: 0.2,
             min_variance: 100.0,
             max_autocorrelation: 0.5,
+            variance_mode: VarianceMode::Absolute,
+            expected_bias_center: DEFAULT_BIAS_CENTER,
         }
     }
 
+    /// Returns the absolute variance floor after applying `variance_mode`.
+    pub fn effective_min_variance(&self) -> f64 {
+        match self.variance_mode {
+            VarianceMode::Absolute => self.min_variance,
+            VarianceMode::Fraction { bit_depth } => {
+                self.min_variance * VarianceMode::max_variance(bit_depth)
+            }
+        }
+    }
+
+    /// Checks that each threshold lies within its meaningful range.
+    pub fn validate(&self) -> Result<(), ThresholdError> {
+        let invalid = |field, requirement| Err(ThresholdError::OutOfRange { field, requirement });
+        if !(0.0..=0.5).contains(&self.max_bit_bias) {
+            return invalid("max_bit_bias", "within 0.0-0.5");
+        }
+        if !(0.0..=1.0).contains(&self.expected_bias_center) {
+            return invalid("expected_bias_center", "within 0.0-1.0");
+        }
+        if !self.min_variance.is_finite() || self.min_variance < 0.0 {
+            return invalid("min_variance", "non-negative");
+        }
+        if !(0.0..=1.0).contains(&self.max_autocorrelation) {
+            return invalid("max_autocorrelation", "within 0.0-1.0");
+        }
+        Ok(())
+    }
+
+    /// Returns the signed deviation of a sample's one-probability from
+    /// `expected_bias_center`, given its [`bit_bias`](StatisticalTests::bit_bias).
+    pub fn bias_deviation(&self, bit_bias: f64) -> f64 {
+        bit_bias + 0.5 - self.expected_bias_center
+    }
+
     /// Checks statistics against thresholds.
     pub fn check(&self, stats: &StatisticalTests) -> Result<(), ThresholdViolation> {
-        if stats.bit_bias.abs() > self.max_bit_bias {
+        let bias = self.bias_deviation(stats.bit_bias);
+        if bias.abs() > self.max_bit_bias {
             return Err(ThresholdViolation::BitBias {
-                observed: stats.bit_bias,
+                observed: bias,
                 threshold: self.max_bit_bias,
+                sample_size: stats.sample_size,
             });
         }
 
-        if stats.variance < self.min_variance {
+        let min_variance = self.effective_min_variance();
+        if stats.variance < min_variance {
             return Err(ThresholdViolation::LowVariance {
                 observed: stats.variance,
-                threshold: self.min_variance,
+                threshold: min_variance,
+                sample_size: stats.sample_size,
             });
         }
 
@@ -66,6 +153,7 @@ impl QualityThresholds {
             return Err(ThresholdViolation::HighAutocorrelation {
                 observed: stats.autocorrelation,
                 threshold: self.max_autocorrelation,
+                sample_size: stats.sample_size,
             });
         }
 
@@ -73,17 +161,113 @@ impl QualityThresholds {
     }
 }
 
+/// Invalid [`QualityThresholds`] settings.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ThresholdError {
+    /// A threshold lies outside its meaningful range.
+    #[error("{field} must be {requirement}")]
+    OutOfRange {
+        /// Name of the [`QualityThresholds`] field.
+        field: &'static str,
+        /// The range the value must lie in.
+        requirement: &'static str,
+    },
+}
+
 /// Threshold violation types.
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum ThresholdViolation {
-    #[error("bit bias {observed:.4} exceeds threshold {threshold:.4}")]
-    BitBias { observed: f64, threshold: f64 },
+    /// Bit bias is too far from the expected center.
+    #[error("bit bias {observed:.4} exceeds threshold {threshold:.4} over {sample_size} bytes")]
+    BitBias {
+        /// Observed deviation from the expected center.
+        observed: f64,
+        /// Configured maximum.
+        threshold: f64,
+        /// Number of bytes analyzed.
+        sample_size: usize,
+    },
 
-    #[error("variance {observed:.2} below threshold {threshold:.2}")]
-    LowVariance { observed: f64, threshold: f64 },
+    /// Byte variance is too low.
+    #[error("variance {observed:.2} below threshold {threshold:.2} over {sample_size} bytes")]
+    LowVariance {
+        /// Observed variance.
+        observed: f64,
+        /// Configured minimum.
+        threshold: f64,
+        /// Number of bytes analyzed.
+        sample_size: usize,
+    },
+
+    /// Lag-1 autocorrelation is too high.
+    #[error(
+        "autocorrelation {observed:.4} exceeds threshold {threshold:.4} over {sample_size} bytes"
+    )]
+    HighAutocorrelation {
+        /// Observed autocorrelation.
+        observed: f64,
+        /// Configured maximum.
+        threshold: f64,
+        /// Number of bytes analyzed.
+        sample_size: usize,
+    },
+
+    /// Smoothed bit bias has drifted too far from the expected center, even if recent
+    /// samples passed individually.
+    #[error(
+        "smoothed bit bias {observed:.4} exceeds drift threshold {threshold:.4} \
+         (latest sample {sample_size} bytes)"
+    )]
+    BiasDrift {
+        /// Exponentially weighted moving average of the bias.
+        observed: f64,
+        /// Configured maximum.
+        threshold: f64,
+        /// Number of bytes in the latest sample.
+        sample_size: usize,
+    },
+
+    /// The fraction of set bits is outside the fast-path band.
+    #[error(
+        "fraction of set bits {observed:.4} outside {min:.2}-{max:.2} over {sample_size} bytes"
+    )]
+    PopcountOutOfBand {
+        /// Observed fraction of set bits.
+        observed: f64,
+        /// Configured lower bound.
+        min: f64,
+        /// Configured upper bound.
+        max: f64,
+        /// Number of bytes checked.
+        sample_size: usize,
+    },
+}
+
+impl ThresholdViolation {
+    /// Returns a short, stable identifier for the violated test.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ThresholdViolation::BitBias { .. } => "bit_bias",
+            ThresholdViolation::LowVariance { .. } => "low_variance",
+            ThresholdViolation::HighAutocorrelation { .. } => "high_autocorrelation",
+            ThresholdViolation::BiasDrift { .. } => "bias_drift",
+            ThresholdViolation::PopcountOutOfBand { .. } => "popcount",
+        }
+    }
 
-    #[error("autocorrelation {observed:.4} exceeds threshold {threshold:.4}")]
-    HighAutocorrelation { observed: f64, threshold: f64 },
+    /// Returns the number of bytes in the failing sample.
+    ///
+    /// A violation on a few bytes is far weaker evidence than one on
+    /// a full frame; consumers can weight or ignore violations by size.
+    pub fn sample_size(&self) -> usize {
+        match self {
+            ThresholdViolation::BitBias { sample_size, .. }
+            | ThresholdViolation::LowVariance { sample_size, .. }
+            | ThresholdViolation::HighAutocorrelation { sample_size, .. }
+            | ThresholdViolation::BiasDrift { sample_size, .. }
+            | ThresholdViolation::PopcountOutOfBand { sample_size, .. } => *sample_size,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -96,7 +280,7 @@ mod tests {
         let thresholds = QualityThresholds::permissive();
 
         // Simulated reasonable data
-        let data: Vec<u8> = (0..1000).map(|i| (i * 17 + 31) as u8).collect();
+        let data: Vec<u8> = (0..1000).map(|i| (i * 53 + 31) as u8).collect();
         let raw = RawBits::from_bytes(data, 1);
         let stats = StatisticalTests::analyze(&raw);
 
@@ -111,17 +295,17 @@ mod tests {
         let raw = RawBits::from_bytes(data, 1);
         let stats = StatisticalTests::analyze(&raw);
 
-        assert!(matches!(
-            thresholds.check(&stats),
-            Err(ThresholdViolation::BitBias { .. })
-        ));
+        let violation = thresholds.check(&stats).unwrap_err();
+        assert!(matches!(violation, ThresholdViolation::BitBias { .. }));
+        assert_eq!(violation.sample_size(), 1000);
+        assert!(violation.to_string().ends_with("over 1000 bytes"));
     }
 
     #[test]
     fn test_constant_data_fails_variance() {
         let thresholds = QualityThresholds::default();
 
-        let data = vec![0x80u8; 1000]; // Constant = zero variance
+        let data = vec![0xAAu8; 1000]; // Constant, unbiased = zero variance
         let raw = RawBits::from_bytes(data, 1);
         let stats = StatisticalTests::analyze(&raw);
 
@@ -130,4 +314,88 @@ mod tests {
             Err(ThresholdViolation::LowVariance { .. })
         ));
     }
+
+    #[test]
+    fn test_fractional_variance_floor() {
+        // Uniform-ish bytes: variance around 5400
+        let data: Vec<u8> = (0..1000).map(|i| (i * 53 + 31) as u8).collect();
+        let raw = RawBits::from_bytes(data, 1);
+        let stats = StatisticalTests::analyze(&raw);
+
+        let absolute = QualityThresholds {
+            min_variance: 500.0,
+            ..QualityThresholds::permissive()
+        };
+        assert!(absolute.check(&stats).is_ok());
+
+        // 0.2 of the 8-bit maximum (~16256) is ~3251: passes
+        let loose = QualityThresholds {
+            min_variance: 0.2,
+            variance_mode: VarianceMode::Fraction { bit_depth: 8 },
+            ..QualityThresholds::permissive()
+        };
+        assert!(loose.check(&stats).is_ok());
+
+        // 0.5 of the maximum is ~8128: fails
+        let strict = QualityThresholds {
+            min_variance: 0.5,
+            variance_mode: VarianceMode::Fraction { bit_depth: 8 },
+            ..QualityThresholds::permissive()
+        };
+        assert!(matches!(
+            strict.check(&stats),
+            Err(ThresholdViolation::LowVariance { threshold, .. }) if (threshold - 8128.125).abs() < 1e-6
+        ));
+    }
+
+    #[test]
+    fn test_validate_rejects_out_of_range() {
+        assert!(QualityThresholds::default().validate().is_ok());
+
+        let off_center = QualityThresholds {
+            expected_bias_center: 1.5,
+            ..Default::default()
+        };
+        assert!(matches!(
+            off_center.validate(),
+            Err(ThresholdError::OutOfRange {
+                field: "expected_bias_center",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_bias_center_shifts_check() {
+        use rand_chacha::ChaCha8Rng;
+        use rand_core::{RngCore, SeedableRng};
+
+        // Each bit set with probability 0.6
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let data: Vec<u8> = (0..4096)
+            .map(|_| {
+                (0..8).fold(0u8, |byte, bit| {
+                    byte | u8::from(rng.next_u32() % 10 < 6) << bit
+                })
+            })
+            .collect();
+        let stats = StatisticalTests::analyze(&RawBits::from_bytes(data, 1));
+        assert!((stats.bit_bias - 0.1).abs() < 0.01);
+
+        let centered = QualityThresholds {
+            max_bit_bias: 0.05,
+            expected_bias_center: 0.6,
+            ..QualityThresholds::permissive()
+        };
+        assert!(centered.check(&stats).is_ok());
+
+        let unbiased = QualityThresholds {
+            max_bit_bias: 0.05,
+            ..QualityThresholds::permissive()
+        };
+        assert!(matches!(
+            unbiased.check(&stats),
+            Err(ThresholdViolation::BitBias { observed, .. }) if (observed - 0.1).abs() < 0.01
+        ));
+    }
 }
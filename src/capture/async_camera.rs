@@ -0,0 +1,364 @@
+//! Background capture with a bounded frame queue.
+//!
+//! Capturing on the processing thread stalls the sensor whenever
+//! conditioning or reseeding takes longer than a frame interval.
+//! [`AsyncCamera`] captures on its own thread into a bounded queue; when
+//! the consumer falls behind, the oldest queued frames are dropped so
+//! the consumer always sees recent data.
+//!
+//! The thread paces itself on the configured frame rate, so a camera
+//! that returns immediately (such as a mock) is not polled in a busy
+//! loop.
+
+use super::{Camera, CameraCapabilities, CameraError, CameraState, CaptureConfig, Frame};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Default number of frames buffered between capture and consumer.
+pub const DEFAULT_QUEUE_DEPTH: usize = 4;
+
+/// Frames and counters shared with the capture thread.
+#[derive(Default)]
+struct Queue {
+    frames: VecDeque<Frame>,
+    /// Frames evicted because the queue was full.
+    dropped: u64,
+    /// Largest queue length observed.
+    high_water: usize,
+    /// Error that stopped the capture thread, if not yet reported.
+    error: Option<CameraError>,
+    /// The capture thread has exited.
+    stopped: bool,
+}
+
+#[derive(Default)]
+struct Shared {
+    queue: Mutex<Queue>,
+    ready: Condvar,
+    stop: AtomicBool,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, Queue> {
+        self.queue.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Wraps a [`Camera`], capturing on a background thread.
+///
+/// `open` opens the wrapped camera and starts the thread; `capture`
+/// returns the oldest queued frame, blocking until one is available.
+/// If the wrapped camera fails, the thread stops and the error is
+/// returned by the next `capture`.
+///
+/// Delivered frames are renumbered consecutively from 1 after each
+/// `open`, so frames evicted from the queue do not look like sequence
+/// gaps that reset the extractor's differencer. Evictions are counted
+/// by [`dropped_frames`](Self::dropped_frames) instead.
+pub struct AsyncCamera<C: Camera + Send + 'static> {
+    /// The wrapped camera, while no capture thread owns it.
+    camera: Option<C>,
+    worker: Option<JoinHandle<C>>,
+    shared: Arc<Shared>,
+    depth: usize,
+    state: CameraState,
+    /// Capabilities of the wrapped camera, recorded before the capture
+    /// thread takes ownership of it.
+    capabilities: CameraCapabilities,
+    /// Format mismatches of the wrapped camera, recorded likewise.
+    format_mismatches: u64,
+    /// Frames delivered since `open`; numbers the next frame.
+    delivered: u64,
+}
+
+impl<C: Camera + Send + 'static> AsyncCamera<C> {
+    /// Wraps a camera with a queue of [`DEFAULT_QUEUE_DEPTH`] frames.
+    pub fn new(camera: C) -> Self {
+        Self {
+            camera: Some(camera),
+            worker: None,
+            shared: Arc::new(Shared::default()),
+            depth: DEFAULT_QUEUE_DEPTH,
+            state: CameraState::Unopened,
+            capabilities: CameraCapabilities::default(),
+            format_mismatches: 0,
+            delivered: 0,
+        }
+    }
+
+    /// Sets the queue capacity (at least 1), applied on the next `open`.
+    ///
+    /// A depth of 1 keeps only the latest frame.
+    pub fn with_queue_depth(mut self, depth: usize) -> Self {
+        self.depth = depth.max(1);
+        self
+    }
+
+    /// Returns the queue capacity.
+    pub fn queue_depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the number of frames waiting to be consumed.
+    pub fn queue_len(&self) -> usize {
+        self.shared.lock().frames.len()
+    }
+
+    /// Returns the largest queue length seen since `open`.
+    ///
+    /// A high-water mark at the depth means the consumer has fallen
+    /// behind and frames were dropped.
+    pub fn queue_high_water(&self) -> usize {
+        self.shared.lock().high_water
+    }
+
+    /// Returns the number of frames dropped because the queue was full.
+    pub fn dropped_frames(&self) -> u64 {
+        self.shared.lock().dropped
+    }
+
+    /// Starts a capture thread owning `camera` with a fresh queue,
+    /// capturing at most `fps` frames per second.
+    fn start(&mut self, camera: C, fps: u32) {
+        self.shared = Arc::new(Shared::default());
+        let shared = Arc::clone(&self.shared);
+        let depth = self.depth;
+        let interval = Duration::from_secs(1) / fps.max(1);
+        self.worker = Some(std::thread::spawn(move || {
+            capture_loop(camera, &shared, depth, interval)
+        }));
+        self.state = CameraState::Open;
+    }
+
+    /// Stops the capture thread and returns the wrapped camera to `self`.
+    fn stop(&mut self) {
+        self.shared.stop.store(true, Ordering::Relaxed);
+        if let Some(worker) = self.worker.take() {
+            match worker.join() {
+                Ok(camera) => self.camera = Some(camera),
+                Err(_) => tracing::error!("Capture thread panicked"),
+            }
+        }
+    }
+}
+
+/// Captures until stopped or the camera fails, evicting the oldest
+/// frame whenever the queue is full.
+///
+/// Captures start at most once per `interval`; a camera that blocks
+/// for its own frame period is not slowed further.
+fn capture_loop<C: Camera>(mut camera: C, shared: &Shared, depth: usize, interval: Duration) -> C {
+    let mut next_capture = Instant::now();
+    while !shared.stop.load(Ordering::Relaxed) {
+        let result = camera.capture();
+
+        let mut queue = shared.lock();
+        match result {
+            Ok(frame) => {
+                if queue.frames.len() >= depth {
+                    queue.frames.pop_front();
+                    queue.dropped += 1;
+                }
+                queue.frames.push_back(frame);
+                queue.high_water = queue.high_water.max(queue.frames.len());
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Capture thread stopped");
+                queue.error = Some(e);
+                break;
+            }
+        }
+        drop(queue);
+        shared.ready.notify_one();
+
+        next_capture += interval;
+        let now = Instant::now();
+        if next_capture > now {
+            std::thread::sleep(next_capture - now);
+        } else {
+            // Behind schedule: don't burst to catch up
+            next_capture = now;
+        }
+    }
+
+    shared.lock().stopped = true;
+    shared.ready.notify_all();
+    camera
+}
+
+impl<C: Camera + Send + 'static> Camera for AsyncCamera<C> {
+    fn open(&mut self, config: &CaptureConfig) -> Result<(), CameraError> {
+        self.stop();
+        let mut camera = self
+            .camera
+            .take()
+            .ok_or_else(|| CameraError::OpenFailed("camera lost by capture thread".into()))?;
+        if let Err(e) = camera.open(config) {
+            self.camera = Some(camera);
+            return Err(e);
+        }
+
+        self.capabilities = camera.capabilities();
+        self.format_mismatches = camera.format_mismatches();
+        self.delivered = 0;
+        self.start(camera, config.fps);
+        Ok(())
+    }
+
+    fn capture(&mut self) -> Result<Frame, CameraError> {
+        if self.state != CameraState::Open {
+            return Err(self.state.unavailable_error());
+        }
+
+        let mut queue = self.shared.lock();
+        loop {
+            if let Some(frame) = queue.frames.pop_front() {
+                self.delivered += 1;
+                return Ok(frame.with_sequence(self.delivered));
+            }
+            if let Some(e) = queue.error.take() {
+                return Err(e);
+            }
+            if queue.stopped {
+                return Err(CameraError::CaptureFailed("capture thread stopped".into()));
+            }
+            queue = self
+                .shared
+                .ready
+                .wait(queue)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+    }
+
+    fn is_open(&self) -> bool {
+        self.state == CameraState::Open
+    }
+
+    fn state(&self) -> CameraState {
+        self.state
+    }
+
+    fn close(&mut self) {
+        self.stop();
+        if let Some(camera) = self.camera.as_mut() {
+            camera.close();
+        }
+        self.shared.lock().frames.clear();
+        if matches!(self.state, CameraState::Open | CameraState::Paused) {
+            self.state = CameraState::Closed;
+        }
+    }
+
+    /// Stops the capture thread, discards queued frames and pauses the
+    /// wrapped camera.
+    fn pause(&mut self) -> Result<(), CameraError> {
+        match self.state {
+            CameraState::Open => {}
+            CameraState::Paused => return Ok(()),
+            state => return Err(state.unavailable_error()),
+        }
+        self.stop();
+        self.shared.lock().frames.clear();
+        self.state = CameraState::Paused;
+        match self.camera.as_mut() {
+            Some(camera) => camera.pause(),
+            None => Err(CameraError::CaptureFailed(
+                "camera lost by capture thread".into(),
+            )),
+        }
+    }
+
+    /// Resumes the wrapped camera and restarts the capture thread.
+    fn resume(&mut self, config: &CaptureConfig) -> Result<(), CameraError> {
+        match self.state {
+            CameraState::Paused => {}
+            CameraState::Open => return Ok(()),
+            state => return Err(state.unavailable_error()),
+        }
+        let mut camera = self
+            .camera
+            .take()
+            .ok_or_else(|| CameraError::OpenFailed("camera lost by capture thread".into()))?;
+        if let Err(e) = camera.resume(config) {
+            self.camera = Some(camera);
+            return Err(e);
+        }
+        self.start(camera, config.fps);
+        Ok(())
+    }
+
+    fn capabilities(&self) -> CameraCapabilities {
+        match &self.camera {
+            Some(camera) => camera.capabilities(),
+            None => self.capabilities.clone(),
+        }
+    }
+
+    fn format_mismatches(&self) -> u64 {
+        match &self.camera {
+            Some(camera) => camera.format_mismatches(),
+            None => self.format_mismatches,
+        }
+    }
+}
+
+impl<C: Camera + Send + 'static> Drop for AsyncCamera<C> {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capture::MockCamera;
+    use std::time::Duration;
+
+    #[test]
+    fn test_depth_one_delivers_latest_frame() {
+        let config = CaptureConfig {
+            fps: 120,
+            ..CaptureConfig::with_dimensions(8, 8)
+        };
+        let mut camera = AsyncCamera::new(MockCamera::new()).with_queue_depth(1);
+        camera.open(&config).unwrap();
+
+        // A slow consumer: let the capture thread overwrite the queue
+        while camera.dropped_frames() < 10 {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        // Evictions are counted, not exposed as sequence gaps
+        let frame = camera.capture().unwrap();
+        assert_eq!(frame.sequence(), 1);
+        assert!(camera.dropped_frames() >= 10);
+        assert_eq!(camera.queue_high_water(), 1);
+        assert!(camera.queue_len() <= 1);
+
+        let next = camera.capture().unwrap();
+        assert_eq!(next.sequence(), 2);
+
+        camera.close();
+        assert_eq!(camera.state(), CameraState::Closed);
+        assert!(matches!(camera.capture(), Err(CameraError::Closed)));
+    }
+
+    #[test]
+    fn test_capture_paced_by_fps() {
+        let config = CaptureConfig {
+            fps: 20,
+            ..CaptureConfig::with_dimensions(8, 8)
+        };
+        let mut camera = AsyncCamera::new(MockCamera::new()).with_queue_depth(1);
+        camera.open(&config).unwrap();
+
+        std::thread::sleep(Duration::from_millis(200));
+        camera.close();
+
+        // About four frames in 200 ms at 20 fps, not thousands
+        assert!(camera.dropped_frames() < 10);
+    }
+}
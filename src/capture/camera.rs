@@ -4,21 +4,127 @@
 //! allowing for both real camera input and mock implementations for testing.
 
 use super::{CaptureConfig, Frame};
+use rand_chacha::ChaCha8Rng;
+use rand_core::{RngCore, SeedableRng};
 use thiserror::Error;
 
 /// Errors that can occur during camera operations.
 #[derive(Debug, Error)]
 pub enum CameraError {
+    /// The requested device does not exist or could not be enumerated.
     #[error("camera device not found: {0}")]
     DeviceNotFound(String),
+    /// The device exists but could not be opened.
     #[error("failed to open camera: {0}")]
     OpenFailed(String),
+    /// The capture configuration was rejected.
     #[error("failed to configure camera: {0}")]
     ConfigFailed(String),
+    /// A frame could not be captured or decoded.
     #[error("failed to capture frame: {0}")]
     CaptureFailed(String),
+    /// The camera was used before being opened.
     #[error("camera not initialized")]
     NotInitialized,
+    /// The camera was used after being closed.
+    #[error("camera closed")]
+    Closed,
+    /// The camera was used while paused.
+    #[error("camera paused")]
+    Paused,
+}
+
+/// Lifecycle state of a camera.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CameraState {
+    /// Never opened.
+    #[default]
+    Unopened,
+    /// Opened and ready to capture.
+    Open,
+    /// Opened, but capture is suspended until resumed.
+    Paused,
+    /// Closed after use.
+    Closed,
+}
+
+impl CameraState {
+    /// Returns the error for using a camera in this state, if not open.
+    pub(crate) fn unavailable_error(self) -> CameraError {
+        match self {
+            CameraState::Closed => CameraError::Closed,
+            CameraState::Paused => CameraError::Paused,
+            CameraState::Unopened | CameraState::Open => CameraError::NotInitialized,
+        }
+    }
+}
+
+/// A capture mode a camera supports.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaptureMode {
+    /// Frame width in pixels.
+    pub width: u32,
+    /// Frame height in pixels.
+    pub height: u32,
+    /// Backend-specific frame format name (e.g. `"MJPEG"`, `"YUYV"`).
+    pub format: String,
+    /// Lowest supported frame rate.
+    pub min_fps: u32,
+    /// Highest supported frame rate.
+    pub max_fps: u32,
+}
+
+/// Capture modes a camera reports as supported.
+///
+/// An empty set means the camera cannot report its modes, not that
+/// it supports none.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CameraCapabilities {
+    /// Supported modes, in the order the backend reports them.
+    pub modes: Vec<CaptureMode>,
+}
+
+impl CameraCapabilities {
+    /// Returns the distinct supported resolutions as `(width, height)`.
+    pub fn resolutions(&self) -> Vec<(u32, u32)> {
+        let mut resolutions: Vec<(u32, u32)> = Vec::new();
+        for mode in &self.modes {
+            if !resolutions.contains(&(mode.width, mode.height)) {
+                resolutions.push((mode.width, mode.height));
+            }
+        }
+        resolutions
+    }
+
+    /// Returns the distinct supported frame formats.
+    pub fn formats(&self) -> Vec<&str> {
+        let mut formats: Vec<&str> = Vec::new();
+        for mode in &self.modes {
+            if !formats.contains(&mode.format.as_str()) {
+                formats.push(&mode.format);
+            }
+        }
+        formats
+    }
+
+    /// Returns the frame rate range over all modes at a resolution, or
+    /// `None` if the resolution is not supported.
+    pub fn fps_range(&self, width: u32, height: u32) -> Option<(u32, u32)> {
+        self.modes
+            .iter()
+            .filter(|m| m.width == width && m.height == height)
+            .fold(None, |range, m| match range {
+                None => Some((m.min_fps, m.max_fps)),
+                Some((min, max)) => Some((min.min(m.min_fps), max.max(m.max_fps))),
+            })
+    }
+
+    /// Returns true if some mode supports this resolution and frame rate.
+    pub fn supports(&self, width: u32, height: u32, fps: u32) -> bool {
+        self.modes.iter().any(|m| {
+            m.width == width && m.height == height && (m.min_fps..=m.max_fps).contains(&fps)
+        })
+    }
 }
 
 /// Trait for camera implementations.
@@ -35,21 +141,114 @@ pub trait Camera {
     /// Checks if the camera is currently open.
     fn is_open(&self) -> bool;
 
+    /// Returns the lifecycle state.
+    ///
+    /// The default cannot tell a closed camera from one never opened
+    /// and reports both as [`CameraState::Unopened`].
+    fn state(&self) -> CameraState {
+        if self.is_open() {
+            CameraState::Open
+        } else {
+            CameraState::Unopened
+        }
+    }
+
     /// Closes the camera and releases resources.
     fn close(&mut self);
+
+    /// Suspends capture without giving up the configuration.
+    ///
+    /// While paused, `capture` fails with [`CameraError::Paused`]. The
+    /// default closes the camera, which releases the device but loses
+    /// the paused state; implementations that can stop their stream in
+    /// place should override this and [`resume`](Self::resume).
+    fn pause(&mut self) -> Result<(), CameraError> {
+        self.close();
+        Ok(())
+    }
+
+    /// Resumes capture after [`pause`](Self::pause).
+    ///
+    /// The default reopens the camera with `config`.
+    fn resume(&mut self, config: &CaptureConfig) -> Result<(), CameraError> {
+        self.open(config)
+    }
+
+    /// Returns the capture modes the camera supports.
+    ///
+    /// Lets callers offer only valid configurations. The default
+    /// reports nothing, meaning the modes are unknown.
+    fn capabilities(&self) -> CameraCapabilities {
+        CameraCapabilities::default()
+    }
+
+    /// Returns how many opens negotiated a format other than the one
+    /// configured, including opens refused by `strict_format`.
+    ///
+    /// The default reports none, for cameras that never negotiate.
+    fn format_mismatches(&self) -> u64 {
+        0
+    }
 }
 
+/// Resolutions the mock camera advertises, in addition to any
+/// configured one.
+const MOCK_RESOLUTIONS: [(u32, u32); 4] = [(320, 240), (640, 480), (1280, 720), (1920, 1080)];
+
+/// Frame rates the mock camera advertises.
+const MOCK_FPS_RANGE: (u32, u32) = (1, 60);
+
 /// Mock camera for testing that generates synthetic frames.
+///
+/// By default frames follow a simple deterministic pattern. A camera
+/// created with [`MockCamera::with_seed`] instead emits pseudo-random
+/// noise from a seeded generator, so property tests can replay a
+/// failing case from its seed.
 #[derive(Debug, Default)]
 pub struct MockCamera {
     config: Option<CaptureConfig>,
     sequence: u64,
+    /// Seed for the noise generator, if seeded.
+    seed: Option<u64>,
+    /// Noise generator, re-seeded on every `open`.
+    rng: Option<ChaCha8Rng>,
+    state: CameraState,
 }
 
 impl MockCamera {
+    /// Creates a new, unopened mock camera.
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Creates a mock camera whose frames are seeded noise.
+    ///
+    /// The same seed always produces the same frame sequence after `open`.
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            seed: Some(seed),
+            ..Self::default()
+        }
+    }
+
+    /// Returns the noise seed, if any.
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Positions the camera so the next capture is frame `sequence`.
+    ///
+    /// Sequences start at 1. The frame content matches what the
+    /// `sequence`-th capture after `open` would have produced, for both
+    /// pattern and seeded cameras. Opening the camera rewinds to the start.
+    pub fn seek(&mut self, sequence: u64) {
+        self.sequence = sequence.saturating_sub(1);
+        if let (Some(rng), Some(config)) = (self.rng.as_mut(), self.config.as_ref()) {
+            // fill_bytes consumes whole 32-bit words per frame
+            let words_per_frame = ((config.width * config.height) as u128).div_ceil(4);
+            rng.set_word_pos(self.sequence as u128 * words_per_frame);
+        }
+    }
 }
 
 impl Camera for MockCamera {
@@ -59,41 +258,113 @@ impl Camera for MockCamera {
             .map_err(|e| CameraError::ConfigFailed(e.to_string()))?;
         self.config = Some(config.clone());
         self.sequence = 0;
+        self.rng = self.seed.map(ChaCha8Rng::seed_from_u64);
+        self.state = CameraState::Open;
         tracing::info!("MockCamera opened with config: {:?}", config);
         Ok(())
     }
 
     fn capture(&mut self) -> Result<Frame, CameraError> {
-        let config = self.config.as_ref().ok_or(CameraError::NotInitialized)?;
+        if self.state == CameraState::Paused {
+            return Err(CameraError::Paused);
+        }
+        let config = self.config.as_ref().ok_or(self.state.unavailable_error())?;
 
         // Generate synthetic noise pattern for testing
         let pixel_count = (config.width * config.height) as usize;
-        let pixels: Vec<u8> = (0..pixel_count)
-            .map(|i| {
-                // Simple deterministic pattern mixed with sequence
-                // NOT for entropy - only for testing frame handling
-                ((i as u64 ^ self.sequence) % 256) as u8
-            })
-            .collect();
+        let pixels: Vec<u8> = match self.rng.as_mut() {
+            Some(rng) => {
+                // Seeded noise - reproducible, still NOT for entropy
+                let mut pixels = vec![0u8; pixel_count];
+                rng.fill_bytes(&mut pixels);
+                pixels
+            }
+            None => (0..pixel_count)
+                .map(|i| {
+                    // Simple deterministic pattern mixed with sequence
+                    // NOT for entropy - only for testing frame handling
+                    ((i as u64 ^ self.sequence) % 256) as u8
+                })
+                .collect(),
+        };
 
         self.sequence += 1;
-        Ok(Frame::new(pixels, config.width, config.height, self.sequence))
+        let frame = Frame::new(pixels, config.width, config.height, self.sequence);
+        Ok(match config.downsample {
+            Some((width, height)) => frame.downsample(width, height),
+            None => frame,
+        })
     }
 
     fn is_open(&self) -> bool {
         self.config.is_some()
     }
 
+    fn state(&self) -> CameraState {
+        self.state
+    }
+
     fn close(&mut self) {
         self.config = None;
+        if matches!(self.state, CameraState::Open | CameraState::Paused) {
+            self.state = CameraState::Closed;
+        }
         tracing::info!("MockCamera closed");
     }
+
+    fn pause(&mut self) -> Result<(), CameraError> {
+        match self.state {
+            CameraState::Open | CameraState::Paused => {
+                self.state = CameraState::Paused;
+                Ok(())
+            }
+            state => Err(state.unavailable_error()),
+        }
+    }
+
+    /// Resumes where the sequence left off; `config` is not reapplied.
+    fn resume(&mut self, _config: &CaptureConfig) -> Result<(), CameraError> {
+        match self.state {
+            CameraState::Open | CameraState::Paused => {
+                self.state = CameraState::Open;
+                Ok(())
+            }
+            state => Err(state.unavailable_error()),
+        }
+    }
+
+    /// Reports a synthetic set of grayscale and RGB modes, including
+    /// the configured resolution while open.
+    fn capabilities(&self) -> CameraCapabilities {
+        let mut resolutions = MOCK_RESOLUTIONS.to_vec();
+        if let Some(config) = &self.config {
+            if !resolutions.contains(&(config.width, config.height)) {
+                resolutions.push((config.width, config.height));
+            }
+        }
+
+        let (min_fps, max_fps) = MOCK_FPS_RANGE;
+        let modes = resolutions
+            .into_iter()
+            .flat_map(|(width, height)| {
+                ["GRAY", "RGB"].map(|format| CaptureMode {
+                    width,
+                    height,
+                    format: format.to_string(),
+                    min_fps,
+                    max_fps,
+                })
+            })
+            .collect();
+        CameraCapabilities { modes }
+    }
 }
 
 /// Real camera implementation using nokhwa.
 #[cfg(feature = "camera")]
 pub mod real {
     use super::*;
+    use crate::capture::ConfigError;
     use nokhwa::pixel_format::RgbFormat;
     use nokhwa::utils::{
         CameraFormat, CameraIndex, FrameFormat, RequestedFormat, RequestedFormatType, Resolution,
@@ -105,17 +376,84 @@ pub mod real {
         camera: Option<NokhwaCamera_>,
         config: Option<CaptureConfig>,
         sequence: u64,
+        state: CameraState,
+        /// Format the camera actually opened in.
+        negotiated: Option<CameraFormat>,
+        /// Bits per sample the camera delivers.
+        bit_depth: u8,
+        /// Modes the opened device reported.
+        capabilities: CameraCapabilities,
+        /// A native resolution below the output size has been reported.
+        warned_undersized: bool,
+        /// Opens that negotiated a format other than the configured one.
+        format_mismatches: u64,
+    }
+
+    /// Collects a device's compatible formats as capture modes.
+    ///
+    /// nokhwa lists one entry per discrete frame rate; each becomes a
+    /// mode whose range holds just that rate.
+    fn compatible_capabilities(
+        camera: &mut NokhwaCamera_,
+    ) -> Result<CameraCapabilities, CameraError> {
+        let formats = camera
+            .compatible_camera_formats()
+            .map_err(|e| CameraError::ConfigFailed(e.to_string()))?;
+        let modes = formats
+            .into_iter()
+            .map(|f| CaptureMode {
+                width: f.width(),
+                height: f.height(),
+                format: format!("{:?}", f.format()),
+                min_fps: f.frame_rate(),
+                max_fps: f.frame_rate(),
+            })
+            .collect();
+        Ok(CameraCapabilities { modes })
     }
 
     impl NokhwaCamera {
+        /// Creates a new, unopened camera.
         pub fn new() -> Self {
             Self {
                 camera: None,
                 config: None,
                 sequence: 0,
+                state: CameraState::Unopened,
+                negotiated: None,
+                bit_depth: 8,
+                capabilities: CameraCapabilities::default(),
+                warned_undersized: false,
+                format_mismatches: 0,
             }
         }
 
+        /// Returns the format negotiated on open, which may differ from
+        /// the configured one unless `strict_format` is set.
+        pub fn negotiated_format(&self) -> Option<CameraFormat> {
+            self.negotiated
+        }
+
+        /// Returns the bits per sample of captured frames.
+        ///
+        /// Always 8: every nokhwa frame format carries 8-bit samples
+        /// and frames are decoded as 8-bit RGB, so a 16-bit request
+        /// falls back with a warning on open.
+        pub fn bit_depth(&self) -> u8 {
+            self.bit_depth
+        }
+
+        /// Queries the modes a device supports without keeping it open.
+        ///
+        /// Use this before `open`; once open, `capabilities` reports
+        /// the modes of the opened device.
+        pub fn query_capabilities(device_id: u32) -> Result<CameraCapabilities, CameraError> {
+            let format = RequestedFormat::new::<RgbFormat>(RequestedFormatType::None);
+            let mut camera = NokhwaCamera_::new(CameraIndex::Index(device_id), format)
+                .map_err(|e| CameraError::OpenFailed(e.to_string()))?;
+            compatible_capabilities(&mut camera)
+        }
+
         /// Lists all available camera devices.
         pub fn list_devices() -> Result<Vec<CameraInfo>, CameraError> {
             let devices = nokhwa::query(nokhwa::utils::ApiBackend::Auto)
@@ -163,32 +501,81 @@ pub mod real {
                 .open_stream()
                 .map_err(|e| CameraError::OpenFailed(e.to_string()))?;
 
+            // Closest-format negotiation may have picked another mode
+            let negotiated = camera.camera_format();
+            if !config.matches_format(
+                negotiated.width(),
+                negotiated.height(),
+                negotiated.frame_rate(),
+            ) {
+                self.format_mismatches += 1;
+            }
+            if let Err(e) = config.check_negotiated(
+                negotiated.width(),
+                negotiated.height(),
+                negotiated.frame_rate(),
+            ) {
+                let _ = camera.stop_stream();
+                return Err(CameraError::ConfigFailed(e.to_string()));
+            }
+
+            let capabilities = compatible_capabilities(&mut camera).unwrap_or_else(|e| {
+                tracing::warn!("Could not query camera formats: {}", e);
+                CameraCapabilities::default()
+            });
+
+            // Frames are decoded as 8-bit RGB whatever the native format
+            let bit_depth = config.check_bit_depth(8);
+
             tracing::info!(
                 "Opened camera {} at {}x{} @ {} fps",
                 config.device_id,
-                config.width,
-                config.height,
-                config.fps
+                negotiated.width(),
+                negotiated.height(),
+                negotiated.frame_rate()
             );
 
             self.camera = Some(camera);
+            self.negotiated = Some(negotiated);
+            self.bit_depth = bit_depth;
+            self.capabilities = capabilities;
+            self.warned_undersized = false;
             self.config = Some(config.clone());
             self.sequence = 0;
+            self.state = CameraState::Open;
 
             Ok(())
         }
 
         fn capture(&mut self) -> Result<Frame, CameraError> {
-            let camera = self.camera.as_mut().ok_or(CameraError::NotInitialized)?;
-            let config = self.config.as_ref().ok_or(CameraError::NotInitialized)?;
+            if self.state == CameraState::Paused {
+                return Err(CameraError::Paused);
+            }
+            let unavailable = self.state.unavailable_error();
+            let camera = self.camera.as_mut().ok_or(unavailable)?;
+            let config = self.config.as_ref().ok_or(self.state.unavailable_error())?;
 
             let frame = camera
                 .frame()
                 .map_err(|e| CameraError::CaptureFailed(e.to_string()))?;
 
+            // Check the native size before decoding allocates for it
+            let native = frame.resolution();
+            let bytes = config.frame_bytes_at(native.width(), native.height());
+            if bytes > config.max_frame_bytes {
+                let e = ConfigError::FrameTooLarge {
+                    bytes,
+                    max: config.max_frame_bytes,
+                };
+                return Err(CameraError::CaptureFailed(e.to_string()));
+            }
+
             let rgb_data = frame.decode_image::<RgbFormat>()
                 .map_err(|e| CameraError::CaptureFailed(e.to_string()))?;
 
+            // The closest native format may not match the request
+            let (native_width, native_height) = (rgb_data.width(), rgb_data.height());
+
             // Convert to grayscale if configured
             let pixels: Vec<u8> = if config.grayscale {
                 rgb_data
@@ -207,25 +594,90 @@ pub mod real {
 
             self.sequence += 1;
 
-            Ok(Frame::new(
+            let frame = Frame::new_checked(
                 pixels,
-                config.width,
-                config.height,
+                native_width,
+                native_height,
+                config.channels(),
                 self.sequence,
-            ))
+                config.max_frame_bytes,
+            )
+            .map_err(|e| CameraError::CaptureFailed(e.to_string()))?;
+            let (width, height) = config.output_dimensions();
+            // Downsampling cannot upscale, so such frames pass through as-is
+            if (native_width < width || native_height < height) && !self.warned_undersized {
+                tracing::warn!(
+                    "Camera delivers {}x{}, below the {}x{} output; using native frames",
+                    native_width,
+                    native_height,
+                    width,
+                    height
+                );
+                self.warned_undersized = true;
+            }
+            Ok(frame.downsample(width, height))
         }
 
         fn is_open(&self) -> bool {
             self.camera.is_some()
         }
 
+        fn state(&self) -> CameraState {
+            self.state
+        }
+
         fn close(&mut self) {
             if let Some(mut camera) = self.camera.take() {
                 let _ = camera.stop_stream();
             }
             self.config = None;
+            if matches!(self.state, CameraState::Open | CameraState::Paused) {
+                self.state = CameraState::Closed;
+            }
             tracing::info!("Camera closed");
         }
+
+        /// Stops the stream but keeps the device and its negotiated format.
+        fn pause(&mut self) -> Result<(), CameraError> {
+            if self.state != CameraState::Open {
+                return match self.state {
+                    CameraState::Paused => Ok(()),
+                    state => Err(state.unavailable_error()),
+                };
+            }
+            let camera = self.camera.as_mut().ok_or(CameraError::NotInitialized)?;
+            camera
+                .stop_stream()
+                .map_err(|e| CameraError::CaptureFailed(e.to_string()))?;
+            self.state = CameraState::Paused;
+            tracing::info!("Camera paused");
+            Ok(())
+        }
+
+        /// Restarts the stream in the format negotiated on open.
+        fn resume(&mut self, _config: &CaptureConfig) -> Result<(), CameraError> {
+            if self.state != CameraState::Paused {
+                return match self.state {
+                    CameraState::Open => Ok(()),
+                    state => Err(state.unavailable_error()),
+                };
+            }
+            let camera = self.camera.as_mut().ok_or(CameraError::NotInitialized)?;
+            camera
+                .open_stream()
+                .map_err(|e| CameraError::OpenFailed(e.to_string()))?;
+            self.state = CameraState::Open;
+            tracing::info!("Camera resumed");
+            Ok(())
+        }
+
+        fn capabilities(&self) -> CameraCapabilities {
+            self.capabilities.clone()
+        }
+
+        fn format_mismatches(&self) -> u64 {
+            self.format_mismatches
+        }
     }
 
     impl Drop for NokhwaCamera {
@@ -238,8 +690,11 @@ pub mod real {
 /// Information about an available camera device.
 #[derive(Debug, Clone)]
 pub struct CameraInfo {
+    /// Device index, as accepted by `CaptureConfig::device_id`.
     pub index: u32,
+    /// Human-readable device name.
     pub name: String,
+    /// Backend-specific device description.
     pub description: String,
 }
 
@@ -250,6 +705,20 @@ pub use real::NokhwaCamera;
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_mock_camera_downsamples() {
+        let config = CaptureConfig {
+            downsample: Some((4, 4)),
+            ..CaptureConfig::with_dimensions(8, 8)
+        };
+        let mut camera = MockCamera::new();
+        camera.open(&config).unwrap();
+
+        let frame = camera.capture().unwrap();
+        assert_eq!((frame.width(), frame.height()), (4, 4));
+        assert!(frame.is_valid());
+    }
+
     #[test]
     fn test_mock_camera_lifecycle() {
         let mut camera = MockCamera::new();
@@ -271,6 +740,42 @@ mod tests {
         assert!(!camera.is_open());
     }
 
+    #[test]
+    fn test_mock_capabilities_include_configured_resolution() {
+        let mut camera = MockCamera::new();
+        assert!(!camera.capabilities().resolutions().contains(&(100, 60)));
+
+        camera
+            .open(&CaptureConfig::with_dimensions(100, 60))
+            .unwrap();
+        let capabilities = camera.capabilities();
+
+        assert!(capabilities.resolutions().contains(&(100, 60)));
+        assert!(capabilities.resolutions().contains(&(640, 480)));
+        assert_eq!(capabilities.formats(), vec!["GRAY", "RGB"]);
+        assert_eq!(capabilities.fps_range(100, 60), Some(MOCK_FPS_RANGE));
+        assert!(capabilities.supports(100, 60, 30));
+        assert!(!capabilities.supports(100, 60, 120));
+    }
+
+    #[test]
+    fn test_pause_and_resume() {
+        let config = CaptureConfig::with_dimensions(8, 8);
+        let mut camera = MockCamera::new();
+        assert!(matches!(camera.pause(), Err(CameraError::NotInitialized)));
+
+        camera.open(&config).unwrap();
+        assert_eq!(camera.capture().unwrap().sequence(), 1);
+
+        camera.pause().unwrap();
+        assert_eq!(camera.state(), CameraState::Paused);
+        assert!(matches!(camera.capture(), Err(CameraError::Paused)));
+
+        camera.resume(&config).unwrap();
+        assert_eq!(camera.state(), CameraState::Open);
+        assert_eq!(camera.capture().unwrap().sequence(), 2);
+    }
+
     #[test]
     fn test_capture_without_open() {
         let mut camera = MockCamera::new();
@@ -279,4 +784,59 @@ mod tests {
             Err(CameraError::NotInitialized)
         ));
     }
+
+    #[test]
+    fn test_mock_camera_state_transitions() {
+        let mut camera = MockCamera::new();
+        assert_eq!(camera.state(), CameraState::Unopened);
+        assert!(matches!(camera.capture(), Err(CameraError::NotInitialized)));
+
+        camera.open(&CaptureConfig::with_dimensions(8, 8)).unwrap();
+        assert_eq!(camera.state(), CameraState::Open);
+        assert!(camera.capture().is_ok());
+
+        camera.close();
+        assert_eq!(camera.state(), CameraState::Closed);
+        assert!(matches!(camera.capture(), Err(CameraError::Closed)));
+
+        camera.open(&CaptureConfig::with_dimensions(8, 8)).unwrap();
+        assert_eq!(camera.state(), CameraState::Open);
+    }
+
+    #[test]
+    fn test_mock_camera_seek_replays_frame() {
+        let config = CaptureConfig::with_dimensions(15, 7);
+        for mut camera in [MockCamera::new(), MockCamera::with_seed(7)] {
+            camera.open(&config).unwrap();
+            let frames: Vec<Frame> = (0..5).map(|_| camera.capture().unwrap()).collect();
+
+            camera.seek(3);
+            let frame = camera.capture().unwrap();
+            assert_eq!(frame.sequence(), 3);
+            assert_eq!(frame.pixels(), frames[2].pixels());
+
+            let next = camera.capture().unwrap();
+            assert_eq!(next.sequence(), 4);
+            assert_eq!(next.pixels(), frames[3].pixels());
+        }
+    }
+
+    #[test]
+    fn test_seeded_mock_is_reproducible() {
+        let config = CaptureConfig::with_dimensions(16, 16);
+        let mut a = MockCamera::with_seed(7);
+        let mut b = MockCamera::with_seed(7);
+        let mut c = MockCamera::with_seed(8);
+        a.open(&config).unwrap();
+        b.open(&config).unwrap();
+        c.open(&config).unwrap();
+
+        for _ in 0..3 {
+            let fa = a.capture().unwrap();
+            let fb = b.capture().unwrap();
+            let fc = c.capture().unwrap();
+            assert_eq!(fa.pixels(), fb.pixels());
+            assert_ne!(fa.pixels(), fc.pixels());
+        }
+    }
 }
@@ -4,8 +4,15 @@
 //! entropy characteristics. Auto-exposure would introduce
 //! unpredictable correlations.
 
+use crate::analysis::{QualityThresholds, ThresholdError, DEFAULT_MIN_SAMPLE_SIZE};
+use crate::reseeding::ReseedableRng;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Default cap on the size of a single frame: 64 MiB, enough for
+/// 16-bit RGB at 4K.
+pub const DEFAULT_MAX_FRAME_BYTES: u64 = 64 * 1024 * 1024;
 
 /// Configuration for camera capture.
 ///
@@ -27,6 +34,37 @@ pub struct CaptureConfig {
     pub fps: u32,
     /// Use grayscale mode (recommended for entropy extraction).
     pub grayscale: bool,
+    /// Extracted outputs to discard after stream start while the sensor settles.
+    #[serde(default)]
+    pub warmup_frames: u32,
+    /// Output frame dimensions `(width, height)`, if frames captured at
+    /// `width` x `height` should be box-averaged down before use.
+    #[serde(default)]
+    pub downsample: Option<(u32, u32)>,
+    /// Refuse to open if the camera negotiates a different resolution
+    /// or frame rate than requested, instead of adapting to it.
+    #[serde(default)]
+    pub strict_format: bool,
+    /// Bits per sample to request from the sensor: 8 or 16.
+    ///
+    /// Cameras that do not deliver the requested depth fall back to 8,
+    /// as the nokhwa backend always does.
+    #[serde(default = "default_bit_depth")]
+    pub bit_depth: u8,
+    /// Largest frame, in bytes, that capture may produce.
+    ///
+    /// Guards against configs whose dimensions would allocate
+    /// gigabytes per frame.
+    #[serde(default = "default_max_frame_bytes")]
+    pub max_frame_bytes: u64,
+}
+
+fn default_bit_depth() -> u8 {
+    8
+}
+
+fn default_max_frame_bytes() -> u64 {
+    DEFAULT_MAX_FRAME_BYTES
 }
 
 impl Default for CaptureConfig {
@@ -39,11 +77,21 @@ impl Default for CaptureConfig {
             gain: 1,
             fps: 30,
             grayscale: true,
+            warmup_frames: 0,
+            downsample: None,
+            strict_format: false,
+            bit_depth: 8,
+            max_frame_bytes: DEFAULT_MAX_FRAME_BYTES,
         }
     }
 }
 
 impl CaptureConfig {
+    /// Starts a [`CaptureConfigBuilder`] from the defaults.
+    pub fn builder() -> CaptureConfigBuilder {
+        CaptureConfigBuilder::default()
+    }
+
     /// Creates a new configuration with the specified dimensions.
     pub fn with_dimensions(width: u32, height: u32) -> Self {
         Self {
@@ -64,34 +112,214 @@ impl CaptureConfig {
         if self.fps == 0 || self.fps > 120 {
             return Err(ConfigError::InvalidFrameRate);
         }
+        if !matches!(self.bit_depth, 8 | 16) {
+            return Err(ConfigError::InvalidBitDepth);
+        }
+        if let Some((width, height)) = self.downsample {
+            if width == 0 || height == 0 || width > self.width || height > self.height {
+                return Err(ConfigError::InvalidDimensions);
+            }
+        }
+        let bytes = self.frame_bytes();
+        if bytes > self.max_frame_bytes {
+            return Err(ConfigError::FrameTooLarge {
+                bytes,
+                max: self.max_frame_bytes,
+            });
+        }
         Ok(())
     }
+
+    /// Returns the size in bytes of a captured frame at the configured
+    /// resolution, color mode and bit depth, before any downsampling.
+    pub fn frame_bytes(&self) -> u64 {
+        self.frame_bytes_at(self.width, self.height)
+    }
+
+    /// Returns the size in bytes of a `width` x `height` frame in the
+    /// configured color mode and bit depth, e.g. for the resolution a
+    /// camera actually delivers.
+    pub fn frame_bytes_at(&self, width: u32, height: u32) -> u64 {
+        let sample_bytes = u64::from(self.bit_depth).div_ceil(8);
+        u64::from(width) * u64::from(height) * self.channels() as u64 * sample_bytes
+    }
+
+    /// Returns the samples per pixel of captured frames.
+    pub fn channels(&self) -> usize {
+        if self.grayscale {
+            1
+        } else {
+            3
+        }
+    }
+
+    /// Returns whether a negotiated format is exactly the one requested.
+    pub fn matches_format(&self, width: u32, height: u32, fps: u32) -> bool {
+        (width, height, fps) == (self.width, self.height, self.fps)
+    }
+
+    /// Checks the format a camera actually negotiated against this config.
+    ///
+    /// Cameras may open at the closest supported mode rather than the
+    /// one requested. A mismatch is an error with `strict_format`;
+    /// otherwise it is logged and frames use the negotiated resolution.
+    pub fn check_negotiated(&self, width: u32, height: u32, fps: u32) -> Result<(), ConfigError> {
+        if self.matches_format(width, height, fps) {
+            return Ok(());
+        }
+
+        let mismatch = format!(
+            "requested {}x{} @ {} fps, camera negotiated {}x{} @ {} fps",
+            self.width, self.height, self.fps, width, height, fps
+        );
+        if self.strict_format {
+            return Err(ConfigError::FormatMismatch(mismatch));
+        }
+        tracing::warn!("Camera format differs from config: {}", mismatch);
+        Ok(())
+    }
+
+    /// Returns the bit depth to capture at, given what the camera delivered.
+    ///
+    /// Some cameras advertise 16-bit modes but deliver 8-bit samples.
+    /// Rather than misreading such frames, capture falls back to 8-bit
+    /// with a warning.
+    pub fn check_bit_depth(&self, delivered: u8) -> u8 {
+        if delivered == self.bit_depth {
+            return delivered;
+        }
+        tracing::warn!(
+            "Requested {}-bit samples but camera delivered {}-bit; falling back to 8-bit",
+            self.bit_depth,
+            delivered
+        );
+        8
+    }
+
+    /// Returns the dimensions of frames delivered to the extractor.
+    pub fn output_dimensions(&self) -> (u32, u32) {
+        self.downsample.unwrap_or((self.width, self.height))
+    }
+}
+
+/// Builds a [`CaptureConfig`], validating it once complete.
+///
+/// Unset fields keep their defaults.
+///
+/// ```
+/// use optical_entropy::capture::CaptureConfig;
+///
+/// let config = CaptureConfig::builder()
+///     .device(1)
+///     .resolution(1280, 720)
+///     .fps(60)
+///     .exposure_us(5000)
+///     .build()
+///     .unwrap();
+/// assert_eq!(config.output_dimensions(), (1280, 720));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CaptureConfigBuilder {
+    config: CaptureConfig,
+}
+
+impl CaptureConfigBuilder {
+    /// Sets the camera device index.
+    pub fn device(mut self, device_id: u32) -> Self {
+        self.config.device_id = device_id;
+        self
+    }
+
+    /// Sets the capture resolution in pixels.
+    pub fn resolution(mut self, width: u32, height: u32) -> Self {
+        self.config.width = width;
+        self.config.height = height;
+        self
+    }
+
+    /// Sets the target frame rate.
+    pub fn fps(mut self, fps: u32) -> Self {
+        self.config.fps = fps;
+        self
+    }
+
+    /// Sets the fixed exposure time in microseconds.
+    pub fn exposure_us(mut self, exposure_us: u32) -> Self {
+        self.config.exposure_us = exposure_us;
+        self
+    }
+
+    /// Sets the fixed gain (camera-specific units).
+    pub fn gain(mut self, gain: u32) -> Self {
+        self.config.gain = gain;
+        self
+    }
+
+    /// Selects grayscale (`true`) or RGB capture.
+    pub fn grayscale(mut self, grayscale: bool) -> Self {
+        self.config.grayscale = grayscale;
+        self
+    }
+
+    /// Validates and returns the config.
+    pub fn build(self) -> Result<CaptureConfig, ConfigError> {
+        self.config.validate()?;
+        Ok(self.config)
+    }
 }
 
 /// Configuration validation errors.
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum ConfigError {
+    /// Width or height is zero.
     #[error("invalid frame dimensions")]
     InvalidDimensions,
+    /// Exposure time is zero.
     #[error("invalid exposure time")]
     InvalidExposure,
+    /// Frame rate is outside the supported range.
     #[error("invalid frame rate (must be 1-120 fps)")]
     InvalidFrameRate,
+    /// Bit depth is neither 8 nor 16.
+    #[error("invalid bit depth (must be 8 or 16)")]
+    InvalidBitDepth,
+    /// The config file could not be read.
     #[error("failed to read config file: {0}")]
     FileReadError(String),
+    /// The config file is not valid TOML or has the wrong shape.
     #[error("failed to parse config file: {0}")]
     ParseError(String),
+    /// A health threshold is outside its meaningful range.
+    #[error("invalid health thresholds: {0}")]
+    InvalidThresholds(String),
+    /// A frame would exceed the configured `max_frame_bytes`.
+    #[error("frame of {bytes} bytes exceeds the {max}-byte limit")]
+    FrameTooLarge {
+        /// Size of the frame, in bytes.
+        bytes: u64,
+        /// Configured limit, in bytes.
+        max: u64,
+    },
+    /// The camera opened in a different format and `strict_format` is set.
+    #[error("camera format mismatch: {0}")]
+    FormatMismatch(String),
 }
 
 /// Full configuration file format.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct FileConfig {
+    /// Camera capture settings.
     #[serde(default)]
     pub capture: CaptureConfig,
+    /// Health monitoring settings.
     #[serde(default)]
     pub health: HealthConfig,
+    /// Output and runtime settings.
     #[serde(default)]
     pub output: OutputConfig,
+    /// CSPRNG reseeding settings.
+    #[serde(default)]
+    pub reseed: ReseedConfig,
 }
 
 /// Health monitoring configuration.
@@ -105,6 +333,21 @@ pub struct HealthConfig {
     pub min_variance: f64,
     /// Maximum autocorrelation allowed.
     pub max_autocorrelation: f64,
+    /// Samples smaller than this many bytes are not tested.
+    #[serde(default = "default_min_sample_size")]
+    pub min_sample_size: usize,
+    /// Test fixed windows of this many bytes instead of each
+    /// extraction output as it arrives.
+    #[serde(default)]
+    pub analysis_window: Option<usize>,
+    /// Samples after startup reported as warming up rather than
+    /// unhealthy, until the first healthy streak completes.
+    #[serde(default)]
+    pub startup_grace_samples: u64,
+}
+
+fn default_min_sample_size() -> usize {
+    DEFAULT_MIN_SAMPLE_SIZE
 }
 
 impl Default for HealthConfig {
@@ -114,6 +357,36 @@ impl Default for HealthConfig {
             max_bias: 0.1,
             min_variance: 100.0,
             max_autocorrelation: 0.5,
+            min_sample_size: DEFAULT_MIN_SAMPLE_SIZE,
+            analysis_window: None,
+            startup_grace_samples: 0,
+        }
+    }
+}
+
+impl HealthConfig {
+    /// Validates the threshold ranges; see [`QualityThresholds::validate`].
+    ///
+    /// Errors name the keys of this section rather than the
+    /// [`QualityThresholds`] fields they map to.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        self.thresholds().validate().map_err(|e| {
+            let ThresholdError::OutOfRange { field, requirement } = e;
+            let key = match field {
+                "max_bit_bias" => "max_bias",
+                other => other,
+            };
+            ConfigError::InvalidThresholds(format!("{} must be {}", key, requirement))
+        })
+    }
+
+    /// Returns the quality thresholds this section describes.
+    pub fn thresholds(&self) -> QualityThresholds {
+        QualityThresholds {
+            max_bit_bias: self.max_bias,
+            min_variance: self.min_variance,
+            max_autocorrelation: self.max_autocorrelation,
+            ..Default::default()
         }
     }
 }
@@ -139,14 +412,58 @@ impl Default for OutputConfig {
     }
 }
 
+/// CSPRNG reseeding configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReseedConfig {
+    /// Minimum entropy estimate a seed must carry to be accepted, in bits.
+    pub min_entropy_bits: usize,
+    /// JSON-lines file to append a provenance record to on every reseed.
+    #[serde(default)]
+    pub log_path: Option<PathBuf>,
+    /// Per-device salt the entropy pool conditions seeds with (empty
+    /// disables); see [`PoolConfig::salt`](crate::conditioning::PoolConfig::salt).
+    #[serde(default)]
+    pub salt: String,
+}
+
+impl Default for ReseedConfig {
+    fn default() -> Self {
+        Self {
+            min_entropy_bits: 128,
+            log_path: None,
+            salt: String::new(),
+        }
+    }
+}
+
+impl ReseedConfig {
+    /// Creates an OS-seeded CSPRNG enforcing this section's minimum.
+    pub fn rng(&self) -> ReseedableRng {
+        ReseedableRng::with_min_entropy(self.min_entropy_bits)
+    }
+}
+
 impl FileConfig {
     /// Loads configuration from a TOML file.
     pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
-        let content = std::fs::read_to_string(path.as_ref())
+        let file = std::fs::File::open(path.as_ref())
+            .map_err(|e| ConfigError::FileReadError(e.to_string()))?;
+        Self::from_reader(file)
+    }
+
+    /// Loads configuration from TOML read from `reader`, such as stdin.
+    ///
+    /// Reads to the end, then parses and validates as
+    /// [`from_file`](Self::from_file) does.
+    pub fn from_reader(mut reader: impl Read) -> Result<Self, ConfigError> {
+        let mut content = String::new();
+        reader
+            .read_to_string(&mut content)
             .map_err(|e| ConfigError::FileReadError(e.to_string()))?;
         let config: FileConfig =
             toml::from_str(&content).map_err(|e| ConfigError::ParseError(e.to_string()))?;
         config.capture.validate()?;
+        config.health.validate()?;
         Ok(config)
     }
 }
@@ -163,11 +480,202 @@ mod tests {
 
     #[test]
     fn test_zero_dimensions_invalid() {
-        let mut config = CaptureConfig::default();
-        config.width = 0;
+        let config = CaptureConfig {
+            width: 0,
+            ..Default::default()
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidDimensions)
+        ));
+    }
+
+    #[test]
+    fn test_downsample_cannot_upscale() {
+        let config = CaptureConfig {
+            downsample: Some((1280, 960)),
+            ..Default::default()
+        };
         assert!(matches!(
             config.validate(),
             Err(ConfigError::InvalidDimensions)
         ));
+
+        let config = CaptureConfig {
+            downsample: Some((320, 240)),
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+        assert_eq!(config.output_dimensions(), (320, 240));
+    }
+
+    #[test]
+    fn test_negotiated_format_mismatch() {
+        let config = CaptureConfig::default();
+        assert!(config.matches_format(640, 480, 30));
+        assert!(!config.matches_format(320, 240, 30));
+        assert!(config.check_negotiated(640, 480, 30).is_ok());
+
+        // Closest-format negotiation may pick another mode
+        assert!(config.check_negotiated(320, 240, 30).is_ok());
+
+        let strict = CaptureConfig {
+            strict_format: true,
+            ..Default::default()
+        };
+        assert!(strict.check_negotiated(640, 480, 30).is_ok());
+        assert!(matches!(
+            strict.check_negotiated(640, 480, 15),
+            Err(ConfigError::FormatMismatch(_))
+        ));
+    }
+
+    #[test]
+    fn test_bit_depth_validated() {
+        let config = CaptureConfig {
+            bit_depth: 12,
+            ..Default::default()
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidBitDepth)
+        ));
+
+        let config = CaptureConfig {
+            bit_depth: 16,
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+        assert_eq!(config.check_bit_depth(16), 16);
+        assert_eq!(config.check_bit_depth(8), 8);
+    }
+
+    #[test]
+    fn test_builder_validates() {
+        let config = CaptureConfig::builder()
+            .device(2)
+            .resolution(320, 240)
+            .fps(15)
+            .exposure_us(20_000)
+            .gain(4)
+            .grayscale(false)
+            .build()
+            .unwrap();
+        assert_eq!(
+            (config.device_id, config.width, config.height),
+            (2, 320, 240)
+        );
+        assert_eq!(
+            (config.fps, config.exposure_us, config.gain),
+            (15, 20_000, 4)
+        );
+        assert!(!config.grayscale);
+
+        assert!(matches!(
+            CaptureConfig::builder().fps(240).build(),
+            Err(ConfigError::InvalidFrameRate)
+        ));
+    }
+
+    #[test]
+    fn test_oversized_frame_rejected() {
+        let config = CaptureConfig::with_dimensions(100_000, 100_000);
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::FrameTooLarge {
+                bytes: 10_000_000_000,
+                max: DEFAULT_MAX_FRAME_BYTES
+            })
+        ));
+
+        let config = CaptureConfig {
+            grayscale: false,
+            max_frame_bytes: 640 * 480 * 3,
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+        let config = CaptureConfig {
+            bit_depth: 16,
+            ..config
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::FrameTooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn test_health_thresholds_validated() {
+        let health = HealthConfig::default();
+        assert!(health.validate().is_ok());
+        assert_eq!(health.thresholds().max_bit_bias, health.max_bias);
+
+        let invalid = HealthConfig {
+            max_bias: 0.8,
+            ..Default::default()
+        };
+        assert!(matches!(
+            invalid.validate(),
+            Err(ConfigError::InvalidThresholds(message)) if message == "max_bias must be within 0.0-0.5"
+        ));
+    }
+
+    #[test]
+    fn test_reseed_min_entropy_from_config() {
+        use crate::conditioning::ConditionedSeed;
+        use crate::reseeding::ReseedingError;
+
+        let config: FileConfig = toml::from_str("[reseed]\nmin_entropy_bits = 256\n").unwrap();
+        assert_eq!(config.reseed.min_entropy_bits, 256);
+
+        let mut rng = config.reseed.rng();
+        let seed = ConditionedSeed::new_for_testing([0x42; 32], 128);
+        assert!(matches!(
+            rng.reseed(&seed),
+            Err(ReseedingError::InsufficientEntropy {
+                got: 128,
+                need: 256
+            })
+        ));
+
+        let defaults: FileConfig = toml::from_str("").unwrap();
+        assert!(defaults.reseed.rng().reseed(&seed).is_ok());
+    }
+
+    #[test]
+    fn test_from_reader_matches_from_file() {
+        let content = r#"
+[capture]
+device_id = 1
+width = 320
+height = 240
+exposure_us = 10000
+gain = 2
+fps = 15
+grayscale = true
+
+[reseed]
+min_entropy_bits = 192
+"#;
+        let path = std::env::temp_dir().join(format!(
+            "optical-entropy-reader-{}.toml",
+            std::process::id()
+        ));
+        std::fs::write(&path, content).unwrap();
+        let from_file = FileConfig::from_file(&path);
+        std::fs::remove_file(&path).ok();
+
+        let from_reader = FileConfig::from_reader(content.as_bytes()).unwrap();
+        assert_eq!(from_reader.capture.width, 320);
+        assert_eq!(from_reader.reseed.min_entropy_bits, 192);
+        assert_eq!(
+            toml::to_string(&from_reader).unwrap(),
+            toml::to_string(&from_file.unwrap()).unwrap()
+        );
+
+        assert!(matches!(
+            FileConfig::from_reader(&b"[capture]\nwidth = "[..]),
+            Err(ConfigError::ParseError(_))
+        ));
     }
 }
@@ -0,0 +1,286 @@
+//! Warm standby camera with automatic failover.
+//!
+//! [`FailoverCamera`] keeps a secondary camera open alongside the
+//! primary and switches to it once the primary keeps failing. Frames
+//! are renumbered into one sequence, and the switch skips a sequence
+//! number: the [`Extractor`](crate::extraction::Extractor) treats the
+//! gap as dropped frames and resets its differencer, so frames from
+//! two different sensors are never differenced against each other.
+
+use super::{Camera, CameraCapabilities, CameraError, CameraState, CaptureConfig, Frame};
+
+/// Default consecutive primary failures before failing over.
+const DEFAULT_FAILURE_THRESHOLD: u32 = 3;
+
+/// Which camera of a [`FailoverCamera`] is delivering frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActiveCamera {
+    /// The preferred camera.
+    Primary,
+    /// The standby camera, after the primary failed.
+    Secondary,
+}
+
+/// Wraps a primary and a standby [`Camera`], failing over between them.
+///
+/// Both cameras are opened together so the standby is ready at once.
+/// After `failure_threshold` consecutive capture failures from the
+/// primary, capture switches to the secondary and stays there; the
+/// next `open` starts from the primary again. Failures of the
+/// secondary are returned as they are.
+pub struct FailoverCamera {
+    primary: Box<dyn Camera>,
+    secondary: Box<dyn Camera>,
+    active: ActiveCamera,
+    failure_threshold: u32,
+    /// Consecutive failed captures from the primary.
+    consecutive_failures: u32,
+    /// Switches to the secondary since creation.
+    failovers: u64,
+    sequence: u64,
+    state: CameraState,
+}
+
+impl FailoverCamera {
+    /// Creates a failover pair with the default failure threshold.
+    pub fn new(primary: Box<dyn Camera>, secondary: Box<dyn Camera>) -> Self {
+        Self {
+            primary,
+            secondary,
+            active: ActiveCamera::Primary,
+            failure_threshold: DEFAULT_FAILURE_THRESHOLD,
+            consecutive_failures: 0,
+            failovers: 0,
+            sequence: 0,
+            state: CameraState::Unopened,
+        }
+    }
+
+    /// Sets the consecutive primary failures that trigger failover (at least 1).
+    pub fn with_failure_threshold(mut self, failures: u32) -> Self {
+        self.failure_threshold = failures.max(1);
+        self
+    }
+
+    /// Returns the camera currently delivering frames.
+    pub fn active(&self) -> ActiveCamera {
+        self.active
+    }
+
+    /// Returns the number of switches to the secondary so far.
+    pub fn failovers(&self) -> u64 {
+        self.failovers
+    }
+
+    fn active_camera(&mut self) -> &mut dyn Camera {
+        match self.active {
+            ActiveCamera::Primary => self.primary.as_mut(),
+            ActiveCamera::Secondary => self.secondary.as_mut(),
+        }
+    }
+
+    /// Switches to the secondary, leaving a gap in the sequence.
+    fn fail_over(&mut self, error: &CameraError) {
+        self.active = ActiveCamera::Secondary;
+        self.failovers += 1;
+        self.sequence += 1;
+        tracing::error!(
+            failures = self.consecutive_failures,
+            error = %error,
+            "Primary camera failed, switched to secondary"
+        );
+    }
+
+    /// Gives a frame the next number in the combined sequence.
+    fn renumber(&mut self, frame: Frame) -> Frame {
+        self.sequence += 1;
+        frame.with_sequence(self.sequence)
+    }
+}
+
+impl Camera for FailoverCamera {
+    fn open(&mut self, config: &CaptureConfig) -> Result<(), CameraError> {
+        let primary = self.primary.open(config);
+        if let Err(e) = self.secondary.open(config) {
+            tracing::warn!(error = %e, "Standby camera failed to open");
+        }
+
+        self.active = match primary {
+            Ok(()) => ActiveCamera::Primary,
+            Err(e) if self.secondary.is_open() => {
+                self.fail_over(&e);
+                ActiveCamera::Secondary
+            }
+            Err(e) => return Err(e),
+        };
+        self.consecutive_failures = 0;
+        self.sequence = 0;
+        self.state = CameraState::Open;
+        Ok(())
+    }
+
+    fn capture(&mut self) -> Result<Frame, CameraError> {
+        if self.state != CameraState::Open {
+            return Err(self.state.unavailable_error());
+        }
+
+        match self.active_camera().capture() {
+            Ok(frame) => {
+                self.consecutive_failures = 0;
+                Ok(self.renumber(frame))
+            }
+            Err(e) if self.active == ActiveCamera::Primary && self.secondary.is_open() => {
+                self.consecutive_failures += 1;
+                if self.consecutive_failures < self.failure_threshold {
+                    tracing::warn!(
+                        failures = self.consecutive_failures,
+                        error = %e,
+                        "Primary camera capture failed"
+                    );
+                    return Err(e);
+                }
+                self.fail_over(&e);
+                let frame = self.secondary.capture()?;
+                Ok(self.renumber(frame))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn is_open(&self) -> bool {
+        self.state == CameraState::Open
+    }
+
+    fn state(&self) -> CameraState {
+        self.state
+    }
+
+    fn close(&mut self) {
+        self.primary.close();
+        self.secondary.close();
+        if matches!(self.state, CameraState::Open | CameraState::Paused) {
+            self.state = CameraState::Closed;
+        }
+    }
+
+    /// Pauses both cameras; only the active one must succeed.
+    fn pause(&mut self) -> Result<(), CameraError> {
+        match self.state {
+            CameraState::Open => {}
+            CameraState::Paused => return Ok(()),
+            state => return Err(state.unavailable_error()),
+        }
+        let primary = self.primary.pause();
+        let secondary = self.secondary.pause();
+        match (self.active, primary, secondary) {
+            (ActiveCamera::Primary, Err(e), _) | (ActiveCamera::Secondary, _, Err(e)) => Err(e),
+            _ => {
+                self.state = CameraState::Paused;
+                Ok(())
+            }
+        }
+    }
+
+    /// Resumes both cameras; only the active one must succeed.
+    fn resume(&mut self, config: &CaptureConfig) -> Result<(), CameraError> {
+        match self.state {
+            CameraState::Paused => {}
+            CameraState::Open => return Ok(()),
+            state => return Err(state.unavailable_error()),
+        }
+        let primary = self.primary.resume(config);
+        let secondary = self.secondary.resume(config);
+        match (self.active, primary, secondary) {
+            (ActiveCamera::Primary, Err(e), _) | (ActiveCamera::Secondary, _, Err(e)) => Err(e),
+            _ => {
+                self.state = CameraState::Open;
+                Ok(())
+            }
+        }
+    }
+
+    fn capabilities(&self) -> CameraCapabilities {
+        match self.active {
+            ActiveCamera::Primary => self.primary.capabilities(),
+            ActiveCamera::Secondary => self.secondary.capabilities(),
+        }
+    }
+
+    fn format_mismatches(&self) -> u64 {
+        self.primary.format_mismatches() + self.secondary.format_mismatches()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capture::MockCamera;
+    use crate::extraction::Extractor;
+
+    /// Delivers `frames_left` frames, then fails every capture.
+    struct DyingCamera {
+        inner: MockCamera,
+        frames_left: u32,
+    }
+
+    impl Camera for DyingCamera {
+        fn open(&mut self, config: &CaptureConfig) -> Result<(), CameraError> {
+            self.inner.open(config)
+        }
+
+        fn capture(&mut self) -> Result<Frame, CameraError> {
+            if self.frames_left == 0 {
+                return Err(CameraError::CaptureFailed("unplugged".into()));
+            }
+            self.frames_left -= 1;
+            self.inner.capture()
+        }
+
+        fn is_open(&self) -> bool {
+            self.inner.is_open()
+        }
+
+        fn close(&mut self) {
+            self.inner.close();
+        }
+    }
+
+    #[test]
+    fn test_fails_over_to_secondary() {
+        let primary = DyingCamera {
+            inner: MockCamera::with_seed(1),
+            frames_left: 5,
+        };
+        let mut camera = FailoverCamera::new(Box::new(primary), Box::new(MockCamera::with_seed(2)))
+            .with_failure_threshold(2);
+        camera
+            .open(&CaptureConfig::with_dimensions(16, 16))
+            .unwrap();
+        let mut extractor = Extractor::new();
+
+        for sequence in 1..=5 {
+            let frame = camera.capture().unwrap();
+            assert_eq!(frame.sequence(), sequence);
+            extractor.process(&frame);
+        }
+        assert_eq!(camera.active(), ActiveCamera::Primary);
+
+        // The first failure is reported, the second fails over
+        assert!(camera.capture().is_err());
+        assert_eq!(camera.active(), ActiveCamera::Primary);
+        let frame = camera.capture().unwrap();
+        assert_eq!(camera.active(), ActiveCamera::Secondary);
+        assert_eq!(camera.failovers(), 1);
+
+        // The skipped sequence number resets the differencer
+        assert_eq!(frame.sequence(), 7);
+        assert!(extractor.process(&frame).is_none());
+        assert_eq!(extractor.sequence_gaps(), 1);
+
+        for sequence in 8..=10 {
+            let frame = camera.capture().unwrap();
+            assert_eq!(frame.sequence(), sequence);
+            assert!(extractor.process(&frame).is_some());
+        }
+    }
+}
@@ -1,6 +1,7 @@
 //! Frame type representing a captured image with metadata.
 
-use std::time::Instant;
+use super::ConfigError;
+use std::time::{Instant, SystemTime};
 
 /// A single captured frame from the camera.
 ///
@@ -16,6 +17,8 @@ pub struct Frame {
     height: u32,
     /// Capture timestamp for temporal analysis.
     timestamp: Instant,
+    /// Wall-clock capture time, for correlating with external logs.
+    wall_clock: SystemTime,
     /// Monotonic sequence number.
     sequence: u64,
 }
@@ -28,10 +31,53 @@ impl Frame {
             width,
             height,
             timestamp: Instant::now(),
+            wall_clock: SystemTime::now(),
             sequence,
         }
     }
 
+    /// Creates a frame, rejecting it if it exceeds `max_bytes`.
+    ///
+    /// Both the buffer and the claimed dimensions of `channels` bytes
+    /// per pixel are checked, so a frame cannot advertise a size that
+    /// later stages would allocate for.
+    pub fn new_checked(
+        pixels: Vec<u8>,
+        width: u32,
+        height: u32,
+        channels: usize,
+        sequence: u64,
+        max_bytes: u64,
+    ) -> Result<Self, ConfigError> {
+        let claimed = u64::from(width) * u64::from(height) * channels as u64;
+        let bytes = claimed.max(pixels.len() as u64);
+        if bytes > max_bytes {
+            return Err(ConfigError::FrameTooLarge {
+                bytes,
+                max: max_bytes,
+            });
+        }
+        Ok(Self::new(pixels, width, height, sequence))
+    }
+
+    /// Sets the capture timestamp (e.g. from driver metadata or in tests).
+    pub fn with_timestamp(mut self, timestamp: Instant) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// Sets the wall-clock capture time (e.g. when replaying a recording).
+    pub fn with_wall_clock(mut self, wall_clock: SystemTime) -> Self {
+        self.wall_clock = wall_clock;
+        self
+    }
+
+    /// Sets the sequence number (e.g. when merging camera streams).
+    pub fn with_sequence(mut self, sequence: u64) -> Self {
+        self.sequence = sequence;
+        self
+    }
+
     /// Returns a reference to the raw pixel data.
     #[inline]
     pub fn pixels(&self) -> &[u8] {
@@ -50,12 +96,25 @@ impl Frame {
         self.height
     }
 
-    /// Returns the capture timestamp.
+    /// Returns the monotonic capture timestamp.
+    ///
+    /// Use this for timing math such as inter-frame intervals; it
+    /// cannot be serialized or compared across processes.
     #[inline]
     pub fn timestamp(&self) -> Instant {
         self.timestamp
     }
 
+    /// Returns the wall-clock capture time.
+    ///
+    /// Suitable for persisting and for correlating with external logs,
+    /// but it may jump if the system clock is adjusted, so never use
+    /// it for timing math.
+    #[inline]
+    pub fn wall_clock(&self) -> SystemTime {
+        self.wall_clock
+    }
+
     /// Returns the sequence number.
     #[inline]
     pub fn sequence(&self) -> u64 {
@@ -68,9 +127,65 @@ impl Frame {
         (self.width as usize) * (self.height as usize)
     }
 
+    /// Returns the number of interleaved channels: 1 for grayscale, 3 for RGB.
+    ///
+    /// Inferred from the buffer size; buffers that are not a whole
+    /// multiple of the pixel count report 1.
+    pub fn channels(&self) -> usize {
+        let count = self.pixel_count();
+        if count > 0 && self.pixels.len() == count * 3 {
+            3
+        } else {
+            1
+        }
+    }
+
+    /// Box-averages the frame down to `width` x `height`.
+    ///
+    /// Each output pixel is the rounded mean of the source pixels it
+    /// covers, per channel. Sequence and timestamp are preserved.
+    /// Returns an unchanged copy if the target is not smaller in both
+    /// dimensions or the frame is invalid.
+    pub fn downsample(&self, width: u32, height: u32) -> Frame {
+        let (src_w, src_h) = (self.width as usize, self.height as usize);
+        let (dst_w, dst_h) = (width as usize, height as usize);
+        if dst_w == 0 || dst_h == 0 || dst_w > src_w || dst_h > src_h || !self.is_valid() {
+            return self.clone();
+        }
+        if (dst_w, dst_h) == (src_w, src_h) {
+            return self.clone();
+        }
+
+        let channels = self.channels();
+        let mut pixels = Vec::with_capacity(dst_w * dst_h * channels);
+        for ty in 0..dst_h {
+            let (y0, y1) = (ty * src_h / dst_h, (ty + 1) * src_h / dst_h);
+            for tx in 0..dst_w {
+                let (x0, x1) = (tx * src_w / dst_w, (tx + 1) * src_w / dst_w);
+                let count = ((y1 - y0) * (x1 - x0)) as u32;
+                for c in 0..channels {
+                    let sum: u32 = (y0..y1)
+                        .flat_map(|y| (x0..x1).map(move |x| (y * src_w + x) * channels + c))
+                        .map(|i| u32::from(self.pixels[i]))
+                        .sum();
+                    pixels.push(((sum + count / 2) / count) as u8);
+                }
+            }
+        }
+
+        Frame {
+            pixels,
+            width,
+            height,
+            timestamp: self.timestamp,
+            wall_clock: self.wall_clock,
+            sequence: self.sequence,
+        }
+    }
+
     /// Validates that the pixel buffer size matches dimensions.
     pub fn is_valid(&self) -> bool {
-        self.pixels.len() == self.pixel_count()
+        self.pixels.len() == self.pixel_count() * self.channels()
     }
 }
 
@@ -107,4 +222,69 @@ mod tests {
 
         assert!(!frame.is_valid());
     }
+
+    #[test]
+    fn test_downsample_box_average() {
+        // Pixel (x, y) = 3 * (8y + x)
+        let pixels: Vec<u8> = (0..64).map(|i| (3 * i) as u8).collect();
+        let frame = Frame::new(pixels, 8, 8, 7);
+
+        let small = frame.downsample(4, 4);
+        assert_eq!((small.width(), small.height()), (4, 4));
+        assert!(small.is_valid());
+        assert_eq!(small.sequence(), 7);
+
+        // Each 2x2 block averages to 48by + 6bx + 13.5, rounded up
+        for (i, &value) in small.pixels().iter().enumerate() {
+            let (bx, by) = (i % 4, i / 4);
+            assert_eq!(value as usize, 48 * by + 6 * bx + 14);
+        }
+    }
+
+    #[test]
+    fn test_both_timestamps_populated() {
+        let before = SystemTime::now();
+        let frame = Frame::new(vec![0u8; 64], 8, 8, 1);
+        let after = SystemTime::now();
+
+        assert!(frame.wall_clock() >= before && frame.wall_clock() <= after);
+        assert!(frame.timestamp() <= Instant::now());
+
+        // Derived frames keep both capture times
+        let small = frame.downsample(4, 4);
+        assert_eq!(small.timestamp(), frame.timestamp());
+        assert_eq!(small.wall_clock(), frame.wall_clock());
+    }
+
+    #[test]
+    fn test_rgb_frame_channels() {
+        let frame = Frame::new(vec![0u8; 3 * 64], 8, 8, 1);
+        assert_eq!(frame.channels(), 3);
+        assert!(frame.is_valid());
+
+        let gray = Frame::new(vec![0u8; 64], 8, 8, 1);
+        assert_eq!(gray.channels(), 1);
+    }
+
+    #[test]
+    fn test_new_checked_rejects_oversize() {
+        assert!(Frame::new_checked(vec![0u8; 64], 8, 8, 1, 1, 64).is_ok());
+        assert!(matches!(
+            Frame::new_checked(vec![0u8; 3 * 64], 8, 8, 1, 1, 64),
+            Err(ConfigError::FrameTooLarge {
+                bytes: 192,
+                max: 64
+            })
+        ));
+        // Claimed dimensions count even when the buffer is small
+        assert!(Frame::new_checked(vec![0u8; 4], 100_000, 100_000, 1, 1, 1 << 20).is_err());
+        // Claimed channels count too, as in CaptureConfig::frame_bytes
+        assert!(matches!(
+            Frame::new_checked(vec![0u8; 4], 8, 8, 3, 1, 64),
+            Err(ConfigError::FrameTooLarge {
+                bytes: 192,
+                max: 64
+            })
+        ));
+    }
 }
@@ -4,12 +4,25 @@
 //! and managing camera configuration. The camera is treated as a source
 //! of raw optical data, not as a source of entropy directly.
 
+mod async_camera;
 mod camera;
 mod config;
+mod failover;
 mod frame;
+mod multi;
+mod retry;
 
-pub use camera::{Camera, CameraError, CameraInfo, MockCamera};
+pub use async_camera::{AsyncCamera, DEFAULT_QUEUE_DEPTH};
+pub use camera::{
+    Camera, CameraCapabilities, CameraError, CameraInfo, CameraState, CaptureMode, MockCamera,
+};
 #[cfg(feature = "camera")]
 pub use camera::NokhwaCamera;
-pub use config::{CaptureConfig, ConfigError, FileConfig, HealthConfig, OutputConfig};
+pub use config::{
+    CaptureConfig, CaptureConfigBuilder, ConfigError, FileConfig, HealthConfig, OutputConfig,
+    ReseedConfig, DEFAULT_MAX_FRAME_BYTES,
+};
+pub use failover::{ActiveCamera, FailoverCamera};
 pub use frame::Frame;
+pub use multi::MultiCamera;
+pub use retry::RetryingCamera;
@@ -0,0 +1,309 @@
+//! Multiple cameras combined into a single entropy source.
+//!
+//! Frames from each camera are stacked vertically into one combined
+//! frame, so downstream stages see a single, taller image.
+
+use super::{Camera, CameraCapabilities, CameraError, CameraState, CaptureConfig, Frame};
+
+/// Combines several cameras into one [`Camera`].
+///
+/// All cameras are opened with the same configuration. On capture,
+/// one frame is taken from each open camera and the frames are
+/// concatenated top to bottom. Individual camera failures are
+/// tolerated as long as at least `quorum` cameras succeed. Every
+/// frame must share one width and channel count; a camera that
+/// negotiated another shape fails the capture.
+pub struct MultiCamera {
+    cameras: Vec<Box<dyn Camera>>,
+    /// Minimum cameras that must succeed for open/capture to succeed.
+    quorum: usize,
+    config: Option<CaptureConfig>,
+    sequence: u64,
+    state: CameraState,
+}
+
+impl MultiCamera {
+    /// Creates a multi-camera that requires every camera to succeed.
+    pub fn new(cameras: Vec<Box<dyn Camera>>) -> Self {
+        let quorum = cameras.len();
+        Self::with_quorum(cameras, quorum)
+    }
+
+    /// Creates a multi-camera that tolerates failures down to `quorum` cameras.
+    pub fn with_quorum(cameras: Vec<Box<dyn Camera>>, quorum: usize) -> Self {
+        Self {
+            quorum: quorum.clamp(1, cameras.len().max(1)),
+            cameras,
+            config: None,
+            sequence: 0,
+            state: CameraState::Unopened,
+        }
+    }
+
+    /// Returns the number of cameras, open or not.
+    pub fn camera_count(&self) -> usize {
+        self.cameras.len()
+    }
+
+    /// Returns the number of currently open cameras.
+    pub fn open_count(&self) -> usize {
+        self.cameras.iter().filter(|c| c.is_open()).count()
+    }
+
+    /// Returns the required quorum.
+    pub fn quorum(&self) -> usize {
+        self.quorum
+    }
+}
+
+impl Camera for MultiCamera {
+    fn open(&mut self, config: &CaptureConfig) -> Result<(), CameraError> {
+        config
+            .validate()
+            .map_err(|e| CameraError::ConfigFailed(e.to_string()))?;
+
+        let mut opened = 0;
+        for (index, camera) in self.cameras.iter_mut().enumerate() {
+            match camera.open(config) {
+                Ok(()) => opened += 1,
+                Err(e) => tracing::warn!(camera = index, error = %e, "Camera failed to open"),
+            }
+        }
+
+        if opened < self.quorum {
+            self.close();
+            return Err(CameraError::OpenFailed(format!(
+                "{} of {} cameras opened, quorum is {}",
+                opened,
+                self.cameras.len(),
+                self.quorum
+            )));
+        }
+
+        self.config = Some(config.clone());
+        self.sequence = 0;
+        self.state = CameraState::Open;
+        tracing::info!(
+            opened,
+            total = self.cameras.len(),
+            "MultiCamera opened"
+        );
+        Ok(())
+    }
+
+    fn capture(&mut self) -> Result<Frame, CameraError> {
+        if self.state == CameraState::Paused {
+            return Err(CameraError::Paused);
+        }
+        if self.config.is_none() {
+            return Err(self.state.unavailable_error());
+        }
+
+        let mut pixels = Vec::new();
+        let mut shape = None;
+        let mut height = 0;
+        let mut captured = 0;
+
+        for (index, camera) in self.cameras.iter_mut().enumerate() {
+            if !camera.is_open() {
+                continue;
+            }
+            match camera.capture() {
+                Ok(frame) => {
+                    // Rows only stack if every frame has the same layout
+                    let frame_shape = (frame.width(), frame.channels());
+                    let expected = *shape.get_or_insert(frame_shape);
+                    if frame_shape != expected {
+                        return Err(CameraError::CaptureFailed(format!(
+                            "camera {} delivered width {} with {} channels, expected width {} with {}",
+                            index, frame_shape.0, frame_shape.1, expected.0, expected.1
+                        )));
+                    }
+                    pixels.extend_from_slice(frame.pixels());
+                    height += frame.height();
+                    captured += 1;
+                }
+                Err(e) => tracing::warn!(camera = index, error = %e, "Camera capture failed"),
+            }
+        }
+
+        if captured < self.quorum {
+            return Err(CameraError::CaptureFailed(format!(
+                "{} cameras captured, quorum is {}",
+                captured, self.quorum
+            )));
+        }
+
+        let (width, _) = shape.unwrap_or_default();
+        self.sequence += 1;
+        Ok(Frame::new(pixels, width, height, self.sequence))
+    }
+
+    fn is_open(&self) -> bool {
+        self.config.is_some()
+    }
+
+    fn state(&self) -> CameraState {
+        self.state
+    }
+
+    fn close(&mut self) {
+        for camera in &mut self.cameras {
+            if camera.is_open() {
+                camera.close();
+            }
+        }
+        self.config = None;
+        if matches!(self.state, CameraState::Open | CameraState::Paused) {
+            self.state = CameraState::Closed;
+        }
+        tracing::info!("MultiCamera closed");
+    }
+
+    /// Pauses every open camera.
+    fn pause(&mut self) -> Result<(), CameraError> {
+        match self.state {
+            CameraState::Open => {}
+            CameraState::Paused => return Ok(()),
+            state => return Err(state.unavailable_error()),
+        }
+        for (index, camera) in self.cameras.iter_mut().enumerate() {
+            if camera.is_open() {
+                if let Err(e) = camera.pause() {
+                    tracing::warn!(camera = index, error = %e, "Camera failed to pause");
+                }
+            }
+        }
+        self.state = CameraState::Paused;
+        Ok(())
+    }
+
+    /// Resumes every camera; fails if fewer than `quorum` resume.
+    fn resume(&mut self, config: &CaptureConfig) -> Result<(), CameraError> {
+        match self.state {
+            CameraState::Paused => {}
+            CameraState::Open => return Ok(()),
+            state => return Err(state.unavailable_error()),
+        }
+        let mut resumed = 0;
+        // Cameras without in-place pause were closed and reopen here
+        for (index, camera) in self.cameras.iter_mut().enumerate() {
+            match camera.resume(config) {
+                Ok(()) => resumed += 1,
+                Err(e) => tracing::warn!(camera = index, error = %e, "Camera failed to resume"),
+            }
+        }
+        if resumed < self.quorum {
+            return Err(CameraError::OpenFailed(format!(
+                "{} cameras resumed, quorum is {}",
+                resumed, self.quorum
+            )));
+        }
+        self.state = CameraState::Open;
+        Ok(())
+    }
+
+    /// Reports the modes every camera supports, since all are opened
+    /// with the same configuration.
+    fn capabilities(&self) -> CameraCapabilities {
+        let mut all = self.cameras.iter().map(|c| c.capabilities());
+        let Some(mut common) = all.next() else {
+            return CameraCapabilities::default();
+        };
+        for other in all {
+            common.modes.retain(|mode| other.modes.contains(mode));
+        }
+        common
+    }
+
+    fn format_mismatches(&self) -> u64 {
+        self.cameras.iter().map(|c| c.format_mismatches()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capture::MockCamera;
+
+    fn two_mocks() -> Vec<Box<dyn Camera>> {
+        vec![Box::new(MockCamera::new()), Box::new(MockCamera::new())]
+    }
+
+    #[test]
+    fn test_combined_frame_stacks_cameras() {
+        let config = CaptureConfig::with_dimensions(32, 16);
+        let mut camera = MultiCamera::new(two_mocks());
+        camera.open(&config).unwrap();
+
+        let frame = camera.capture().unwrap();
+        assert!(frame.is_valid());
+        assert_eq!(frame.width(), 32);
+        assert_eq!(frame.height(), 32);
+        assert_eq!(frame.pixels().len(), 2 * 32 * 16);
+        assert_eq!(frame.sequence(), 1);
+
+        let frame2 = camera.capture().unwrap();
+        assert_eq!(frame2.sequence(), 2);
+
+        camera.close();
+        assert!(!camera.is_open());
+        assert_eq!(camera.open_count(), 0);
+    }
+
+    /// A mock camera that opens at its own width.
+    struct WidthCamera {
+        inner: MockCamera,
+        width: u32,
+    }
+
+    impl Camera for WidthCamera {
+        fn open(&mut self, config: &CaptureConfig) -> Result<(), CameraError> {
+            self.inner.open(&CaptureConfig {
+                width: self.width,
+                ..config.clone()
+            })
+        }
+
+        fn capture(&mut self) -> Result<Frame, CameraError> {
+            self.inner.capture()
+        }
+
+        fn is_open(&self) -> bool {
+            self.inner.is_open()
+        }
+
+        fn close(&mut self) {
+            self.inner.close();
+        }
+    }
+
+    #[test]
+    fn test_mismatched_shapes_rejected() {
+        let cameras: Vec<Box<dyn Camera>> = vec![
+            Box::new(MockCamera::new()),
+            Box::new(WidthCamera {
+                inner: MockCamera::new(),
+                width: 16,
+            }),
+        ];
+        let mut camera = MultiCamera::new(cameras);
+        camera
+            .open(&CaptureConfig::with_dimensions(32, 16))
+            .unwrap();
+
+        assert!(matches!(
+            camera.capture(),
+            Err(CameraError::CaptureFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_capture_without_open() {
+        let mut camera = MultiCamera::new(two_mocks());
+        assert!(matches!(
+            camera.capture(),
+            Err(CameraError::NotInitialized)
+        ));
+    }
+}
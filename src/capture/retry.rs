@@ -0,0 +1,203 @@
+//! Capture retries with exponential backoff.
+//!
+//! A disconnected or stalled camera fails every capture immediately;
+//! retrying without delay would spin the CPU. [`RetryingCamera`] backs
+//! off between attempts and gives up cleanly after a bounded number
+//! of consecutive failures.
+
+use super::{Camera, CameraCapabilities, CameraError, CameraState, CaptureConfig, Frame};
+use std::time::Duration;
+
+/// Default number of consecutive capture attempts before giving up.
+const DEFAULT_MAX_ATTEMPTS: u32 = 5;
+
+/// Default delay after the first failure.
+const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(10);
+
+/// Default cap on the delay between attempts.
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Wraps a [`Camera`], retrying failed captures with capped exponential backoff.
+///
+/// Each failed attempt doubles the delay before the next, up to the
+/// configured maximum. After `max_attempts` consecutive failures the
+/// last error is returned. `open` and `close` pass straight through.
+pub struct RetryingCamera<C: Camera> {
+    inner: C,
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl<C: Camera> RetryingCamera<C> {
+    /// Wraps a camera with the default retry policy (5 attempts, 10ms-1s backoff).
+    pub fn new(inner: C) -> Self {
+        Self {
+            inner,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+        }
+    }
+
+    /// Sets the number of consecutive attempts per capture (at least 1).
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Sets the initial delay and the cap on the delay between attempts.
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max.max(initial);
+        self
+    }
+
+    /// Returns the wrapped camera.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Unwraps the camera.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+}
+
+impl<C: Camera> Camera for RetryingCamera<C> {
+    fn open(&mut self, config: &CaptureConfig) -> Result<(), CameraError> {
+        self.inner.open(config)
+    }
+
+    fn capture(&mut self) -> Result<Frame, CameraError> {
+        let mut backoff = self.initial_backoff;
+        let mut attempt = 1;
+
+        loop {
+            match self.inner.capture() {
+                Ok(frame) => return Ok(frame),
+                // Retrying cannot help a camera that is not capturing
+                Err(
+                    e @ (CameraError::NotInitialized | CameraError::Closed | CameraError::Paused),
+                ) => return Err(e),
+                Err(e) if attempt >= self.max_attempts => {
+                    tracing::error!(attempts = attempt, error = %e, "Capture failed, giving up");
+                    return Err(e);
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        attempt,
+                        backoff_ms = backoff.as_millis() as u64,
+                        error = %e,
+                        "Capture failed, retrying"
+                    );
+                    std::thread::sleep(backoff);
+                    backoff = (backoff * 2).min(self.max_backoff);
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    fn is_open(&self) -> bool {
+        self.inner.is_open()
+    }
+
+    fn state(&self) -> CameraState {
+        self.inner.state()
+    }
+
+    fn close(&mut self) {
+        self.inner.close();
+    }
+
+    fn pause(&mut self) -> Result<(), CameraError> {
+        self.inner.pause()
+    }
+
+    fn resume(&mut self, config: &CaptureConfig) -> Result<(), CameraError> {
+        self.inner.resume(config)
+    }
+
+    fn capabilities(&self) -> CameraCapabilities {
+        self.inner.capabilities()
+    }
+
+    fn format_mismatches(&self) -> u64 {
+        self.inner.format_mismatches()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capture::MockCamera;
+
+    /// Fails a fixed number of captures, then delegates to a mock.
+    struct FlakyCamera {
+        inner: MockCamera,
+        failures_left: u32,
+        attempts: u32,
+    }
+
+    impl FlakyCamera {
+        fn new(failures: u32) -> Self {
+            Self {
+                inner: MockCamera::new(),
+                failures_left: failures,
+                attempts: 0,
+            }
+        }
+    }
+
+    impl Camera for FlakyCamera {
+        fn open(&mut self, config: &CaptureConfig) -> Result<(), CameraError> {
+            self.inner.open(config)
+        }
+
+        fn capture(&mut self) -> Result<Frame, CameraError> {
+            self.attempts += 1;
+            if self.failures_left > 0 {
+                self.failures_left -= 1;
+                return Err(CameraError::CaptureFailed("unplugged".into()));
+            }
+            self.inner.capture()
+        }
+
+        fn is_open(&self) -> bool {
+            self.inner.is_open()
+        }
+
+        fn close(&mut self) {
+            self.inner.close();
+        }
+    }
+
+    fn retrying(failures: u32) -> RetryingCamera<FlakyCamera> {
+        let mut camera = RetryingCamera::new(FlakyCamera::new(failures))
+            .with_max_attempts(4)
+            .with_backoff(Duration::from_millis(1), Duration::from_millis(2));
+        camera.open(&CaptureConfig::with_dimensions(8, 8)).unwrap();
+        camera
+    }
+
+    #[test]
+    fn test_recovers_after_transient_failures() {
+        let mut camera = retrying(3);
+
+        let frame = camera.capture().unwrap();
+        assert!(frame.is_valid());
+        assert_eq!(camera.inner().attempts, 4);
+    }
+
+    #[test]
+    fn test_gives_up_after_max_attempts() {
+        let mut camera = retrying(10);
+
+        assert!(matches!(
+            camera.capture(),
+            Err(CameraError::CaptureFailed(_))
+        ));
+        assert_eq!(camera.inner().attempts, 4);
+    }
+}
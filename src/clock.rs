@@ -0,0 +1,50 @@
+//! Injectable time source.
+//!
+//! Rate gating in the entropy pool and rate-limited health logging
+//! both depend on elapsed time. Taking a [`Clock`] lets tests drive
+//! them without sleeping.
+
+use std::time::Instant;
+
+/// Source of the current time.
+///
+/// Injectable so time-dependent behavior can be tested without sleeping.
+pub trait Clock: Send {
+    /// Returns the current instant.
+    fn now(&self) -> Instant;
+}
+
+/// [`Clock`] backed by [`Instant::now`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Test clock that only moves when advanced; clones share one time.
+#[cfg(test)]
+#[derive(Clone)]
+pub(crate) struct ManualClock(std::sync::Arc<std::sync::Mutex<Instant>>);
+
+#[cfg(test)]
+impl ManualClock {
+    /// Creates a clock stopped at the current instant.
+    pub(crate) fn new() -> Self {
+        Self(std::sync::Arc::new(std::sync::Mutex::new(Instant::now())))
+    }
+
+    /// Moves the clock forward by `by`.
+    pub(crate) fn advance(&self, by: std::time::Duration) {
+        *self.0.lock().unwrap() += by;
+    }
+}
+
+#[cfg(test)]
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        *self.0.lock().unwrap()
+    }
+}
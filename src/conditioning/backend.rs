@@ -0,0 +1,25 @@
+//! Pluggable conditioning backends.
+//!
+//! The entropy pool conditions its buffer through this trait, so
+//! alternatives to the built-in hashes (e.g. Toeplitz or two-source
+//! extractors) can be plugged in for research.
+
+use super::hash::SEED_LEN;
+
+/// A conditioning function from raw bytes to seed material.
+///
+/// Backends must be shareable across threads, since the pool may
+/// condition independent segments in parallel.
+pub trait ConditioningBackend: Send + Sync {
+    /// Conditions the input into a fixed-size output.
+    fn condition(&self, input: &[u8]) -> [u8; SEED_LEN];
+
+    /// Conditions the input into the backend's full output.
+    ///
+    /// Must return at least [`SEED_LEN`] bytes. Defaults to
+    /// [`condition`](Self::condition); override for backends that
+    /// produce longer output.
+    fn condition_extended(&self, input: &[u8]) -> Vec<u8> {
+        self.condition(input).to_vec()
+    }
+}
@@ -3,45 +3,260 @@
 //! Uses standard hash functions to transform biased, correlated
 //! raw bits into uniformly distributed output.
 
+use super::backend::ConditioningBackend;
+use crate::analysis::StatisticalTests;
 use crate::extraction::RawBits;
 use blake3::Hasher as Blake3Hasher;
 use sha2::{Digest, Sha256};
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+use thiserror::Error;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Size of the fixed seed prefix returned by [`ConditionedSeed::as_bytes`].
+pub const SEED_LEN: usize = 32;
+
+/// Inputs from this size up are hashed across threads by BLAKE3 with
+/// the `parallel` feature; below it, thread overhead outweighs the gain.
+#[cfg(feature = "parallel")]
+const PARALLEL_HASH_MIN_BYTES: usize = 128 * 1024;
+
+/// BLAKE3 key-derivation context for the audit hash chain.
+const CHAIN_CONTEXT: &str = "optical-entropy-audit-chain-v1";
+
+/// Published digests of fixed inputs (BLAKE3 and FIPS 180-2 examples).
+const TEST_VECTORS: &[TestVector] = &[
+    TestVector {
+        algorithm: HashAlgorithm::Blake3,
+        input: b"",
+        expected: [
+            0xaf, 0x13, 0x49, 0xb9, 0xf5, 0xf9, 0xa1, 0xa6, 0xa0, 0x40, 0x4d, 0xea, 0x36, 0xdc,
+            0xc9, 0x49, 0x9b, 0xcb, 0x25, 0xc9, 0xad, 0xc1, 0x12, 0xb7, 0xcc, 0x9a, 0x93, 0xca,
+            0xe4, 0x1f, 0x32, 0x62,
+        ],
+    },
+    TestVector {
+        algorithm: HashAlgorithm::Blake3,
+        input: b"abc",
+        expected: [
+            0x64, 0x37, 0xb3, 0xac, 0x38, 0x46, 0x51, 0x33, 0xff, 0xb6, 0x3b, 0x75, 0x27, 0x3a,
+            0x8d, 0xb5, 0x48, 0xc5, 0x58, 0x46, 0x5d, 0x79, 0xdb, 0x03, 0xfd, 0x35, 0x9c, 0x6c,
+            0xd5, 0xbd, 0x9d, 0x85,
+        ],
+    },
+    TestVector {
+        algorithm: HashAlgorithm::Sha256,
+        input: b"",
+        expected: [
+            0xe3, 0xb0, 0xc4, 0x42, 0x98, 0xfc, 0x1c, 0x14, 0x9a, 0xfb, 0xf4, 0xc8, 0x99, 0x6f,
+            0xb9, 0x24, 0x27, 0xae, 0x41, 0xe4, 0x64, 0x9b, 0x93, 0x4c, 0xa4, 0x95, 0x99, 0x1b,
+            0x78, 0x52, 0xb8, 0x55,
+        ],
+    },
+    TestVector {
+        algorithm: HashAlgorithm::Sha256,
+        input: b"abc",
+        expected: [
+            0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea, 0x41, 0x41, 0x40, 0xde, 0x5d, 0xae,
+            0x22, 0x23, 0xb0, 0x03, 0x61, 0xa3, 0x96, 0x17, 0x7a, 0x9c, 0xb4, 0x10, 0xff, 0x61,
+            0xf2, 0x00, 0x15, 0xad,
+        ],
+    },
+];
+
+/// A known-answer vector for conditioning.
+#[derive(Debug, Clone, Copy)]
+pub struct TestVector {
+    /// Algorithm the vector applies to.
+    pub algorithm: HashAlgorithm,
+    /// Raw input bytes.
+    pub input: &'static [u8],
+    /// Expected conditioned output.
+    pub expected: [u8; SEED_LEN],
+}
 
 /// Supported hash algorithms for conditioning.
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum HashAlgorithm {
     /// BLAKE3 - fast, secure, recommended default.
     #[default]
     Blake3,
     /// SHA-256 - widely deployed, conservative choice.
     Sha256,
+    /// SHAKE256 XOF - variable-length output for consumers
+    /// needing more than 256 bits.
+    Shake256 {
+        /// Output length in bytes (at least [`SEED_LEN`]).
+        out_len: usize,
+    },
+}
+
+impl HashAlgorithm {
+    /// Returns the conditioned output length in bytes.
+    pub fn output_len(&self) -> usize {
+        match self {
+            HashAlgorithm::Blake3 | HashAlgorithm::Sha256 => SEED_LEN,
+            HashAlgorithm::Shake256 { out_len } => (*out_len).max(SEED_LEN),
+        }
+    }
+}
+
+/// How the entropy content of conditioned output is estimated.
+///
+/// Whatever the policy, the estimate never exceeds the output size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EntropyPolicy {
+    /// Assume a fixed number of bits of entropy per input byte.
+    PerByteAssumed(f64),
+    /// Use the measured min-entropy of the input bytes.
+    Measured,
+    /// Report a fixed estimate in bits, regardless of input.
+    Fixed(usize),
+}
+
+impl Default for EntropyPolicy {
+    fn default() -> Self {
+        // Conservative: ~1 bit per input byte
+        EntropyPolicy::PerByteAssumed(1.0)
+    }
+}
+
+impl EntropyPolicy {
+    /// Estimates the entropy of `input` in bits, capped at `max_bits`.
+    pub fn estimate(&self, input: &[u8], max_bits: usize) -> usize {
+        let bits = match *self {
+            EntropyPolicy::PerByteAssumed(per_byte) => {
+                (input.len() as f64 * per_byte.max(0.0)) as usize
+            }
+            EntropyPolicy::Measured => {
+                (input.len() as f64 * StatisticalTests::min_entropy_per_byte(input)) as usize
+            }
+            EntropyPolicy::Fixed(bits) => bits,
+        };
+        bits.min(max_bits)
+    }
+}
+
+/// Errors from [`Conditioner::try_condition`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ConditioningError {
+    /// The input's measured min-entropy is below the required minimum.
+    #[error("input min-entropy of {measured} bits is below the required {required} bits")]
+    InsufficientEntropy {
+        /// Measured min-entropy of the input, in bits.
+        measured: usize,
+        /// Required minimum, in bits.
+        required: usize,
+    },
 }
 
 /// Conditioned entropy output.
 ///
-/// Fixed-size output from the conditioning hash, ready for
-/// use as CSPRNG seed material.
+/// Output from the conditioning hash, ready for use as CSPRNG seed
+/// material. Always at least [`SEED_LEN`] bytes; XOF algorithms may
+/// produce more. The bytes are zeroized when the seed is dropped.
 #[derive(Clone)]
 pub struct ConditionedSeed {
-    /// The conditioned bytes (32 bytes for both BLAKE3 and SHA-256).
-    data: [u8; 32],
+    /// The conditioned bytes (at least `SEED_LEN`).
+    data: Vec<u8>,
     /// Source entropy estimate in bits.
     entropy_estimate: usize,
+    /// Number of source frames that contributed.
+    source_frames: u64,
+    /// Chain digest of the previous extraction (zeros for the first).
+    previous_digest: [u8; 32],
 }
 
 impl ConditionedSeed {
-    /// Returns the seed bytes.
+    /// Returns the first [`SEED_LEN`] seed bytes.
     #[inline]
-    pub fn as_bytes(&self) -> &[u8; 32] {
+    pub fn as_bytes(&self) -> &[u8; SEED_LEN] {
+        self.data[..SEED_LEN]
+            .try_into()
+            .expect("conditioned seed shorter than SEED_LEN")
+    }
+
+    /// Returns the full conditioned output.
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
         &self.data
     }
 
-    /// Returns the entropy estimate.
+    /// Returns the entropy estimate of the full conditioned output.
+    ///
+    /// XOF output longer than [`SEED_LEN`] can carry more than 256
+    /// bits; see [`seed_entropy_estimate`](Self::seed_entropy_estimate)
+    /// for the bytes returned by [`as_bytes`](Self::as_bytes).
     #[inline]
     pub fn entropy_estimate(&self) -> usize {
         self.entropy_estimate
     }
 
+    /// Returns the entropy estimate of the [`SEED_LEN`] seed bytes,
+    /// capped at `SEED_LEN * 8` bits.
+    ///
+    /// Reseeding only consumes [`as_bytes`](Self::as_bytes), so this,
+    /// not [`entropy_estimate`](Self::entropy_estimate), is what a
+    /// reseed may credit.
+    #[inline]
+    pub fn seed_entropy_estimate(&self) -> usize {
+        self.entropy_estimate.min(SEED_LEN * 8)
+    }
+
+    /// Returns the number of source frames conditioned into this seed.
+    ///
+    /// Carried from [`RawBits::source_frames`] and summed by the pool,
+    /// so callers can check a seed drew on enough independent frames.
+    #[inline]
+    pub fn source_frames(&self) -> u64 {
+        self.source_frames
+    }
+
+    /// Returns the chain digest of the extraction before this one.
+    ///
+    /// All zeros for the first seed from a pool, and for seeds
+    /// conditioned outside a pool.
+    #[inline]
+    pub fn previous_digest(&self) -> &[u8; 32] {
+        &self.previous_digest
+    }
+
+    /// Returns this seed's link in the audit hash chain.
+    ///
+    /// The BLAKE3 derived-key hash of the previous digest and the full
+    /// conditioned output. The next seed from the same pool carries it
+    /// as its [`previous_digest`](Self::previous_digest); see
+    /// [`verify_chain`].
+    pub fn chain_digest(&self) -> [u8; 32] {
+        let mut hasher = Blake3Hasher::new_derive_key(CHAIN_CONTEXT);
+        hasher.update(&self.previous_digest);
+        hasher.update(&self.data);
+        *hasher.finalize().as_bytes()
+    }
+
+    /// Creates a seed from conditioned output and its entropy estimate.
+    pub(crate) fn from_parts(data: Vec<u8>, entropy_estimate: usize) -> Self {
+        debug_assert!(data.len() >= SEED_LEN);
+        Self {
+            data,
+            entropy_estimate,
+            source_frames: 0,
+            previous_digest: [0; 32],
+        }
+    }
+
+    /// Links the seed to the previous extraction's chain digest.
+    pub(crate) fn with_previous_digest(mut self, digest: [u8; 32]) -> Self {
+        self.previous_digest = digest;
+        self
+    }
+
+    /// Records how many source frames contributed.
+    pub(crate) fn with_source_frames(mut self, source_frames: u64) -> Self {
+        self.source_frames = source_frames;
+        self
+    }
+
     /// Creates a seed for testing purposes only.
     ///
     /// This bypasses the normal conditioning pipeline and should
@@ -49,12 +264,47 @@ impl ConditionedSeed {
     #[cfg(test)]
     pub(crate) fn new_for_testing(data: [u8; 32], entropy_estimate: usize) -> Self {
         Self {
-            data,
+            data: data.to_vec(),
             entropy_estimate,
+            source_frames: 1,
+            previous_digest: [0; 32],
         }
     }
 }
 
+/// Checks that consecutive seeds form an unbroken audit hash chain.
+///
+/// Each seed must carry the [`chain_digest`](ConditionedSeed::chain_digest)
+/// of the one before it, so a seed inserted, dropped, reordered or
+/// altered anywhere but last breaks the chain. The first seed's own
+/// link is not checked, so a chain may be verified from any point.
+/// An empty slice verifies trivially.
+///
+/// Seeds are secret; this is for offline audits of recorded or test
+/// runs, not for publishing production seeds.
+pub fn verify_chain(seeds: &[ConditionedSeed]) -> bool {
+    seeds
+        .windows(2)
+        .all(|pair| pair[1].previous_digest == pair[0].chain_digest())
+}
+
+impl Zeroize for ConditionedSeed {
+    /// Wipes the conditioned bytes in place; the metadata is not secret.
+    ///
+    /// The length is kept, so a wiped seed reads as all zeros.
+    fn zeroize(&mut self) {
+        self.data.as_mut_slice().zeroize();
+    }
+}
+
+impl Drop for ConditionedSeed {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for ConditionedSeed {}
+
 impl std::fmt::Debug for ConditionedSeed {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ConditionedSeed")
@@ -69,44 +319,168 @@ impl std::fmt::Debug for ConditionedSeed {
 /// seed material using a cryptographic hash function.
 pub struct Conditioner {
     algorithm: HashAlgorithm,
+    policy: EntropyPolicy,
+    /// Length-prefixed salt absorbed before the input; empty if unsalted.
+    salt_prefix: Vec<u8>,
 }
 
 impl Conditioner {
     /// Creates a new conditioner with the specified algorithm.
     pub fn new(algorithm: HashAlgorithm) -> Self {
-        Self { algorithm }
+        Self {
+            algorithm,
+            policy: EntropyPolicy::default(),
+            salt_prefix: Vec::new(),
+        }
+    }
+
+    /// Sets the entropy estimate policy.
+    pub fn with_policy(mut self, policy: EntropyPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Returns the hash algorithm.
+    pub fn algorithm(&self) -> HashAlgorithm {
+        self.algorithm
+    }
+
+    /// Switches the hash algorithm, keeping the policy and salt.
+    pub fn set_algorithm(&mut self, algorithm: HashAlgorithm) {
+        self.algorithm = algorithm;
+    }
+
+    /// Binds the output to a per-device salt, absorbed before the input.
+    ///
+    /// Two devices with identical sensors and scenes could in principle
+    /// produce identical raw bits; with distinct salts their seeds still
+    /// differ. This is domain separation only: the salt adds no entropy
+    /// and need not be secret, and the entropy estimate ignores it. The
+    /// salt is length-prefixed, so no salt and input pair can collide
+    /// with another. An empty salt leaves conditioning unsalted.
+    pub fn with_salt(mut self, salt: Vec<u8>) -> Self {
+        self.salt_prefix.clear();
+        if !salt.is_empty() {
+            self.salt_prefix
+                .extend_from_slice(&(salt.len() as u64).to_le_bytes());
+            self.salt_prefix.extend_from_slice(&salt);
+        }
+        self
+    }
+
+    /// Returns the embedded known-answer vectors for BLAKE3 and SHA-256.
+    ///
+    /// Auditors can hash each `input` with an independent
+    /// implementation and compare against `expected`.
+    pub fn test_vectors() -> &'static [TestVector] {
+        TEST_VECTORS
+    }
+
+    /// Checks conditioning against the embedded known-answer vectors.
+    ///
+    /// A failure means a hash dependency is broken or miscompiled, and
+    /// no seed conditioned by this build should be trusted.
+    pub fn self_test() -> bool {
+        let passed = TEST_VECTORS
+            .iter()
+            .all(|v| Conditioner::new(v.algorithm).hash(v.input) == v.expected);
+        if !passed {
+            tracing::error!("Conditioning known-answer self-test failed");
+        }
+        passed
     }
 
     /// Conditions raw bits into a fixed-size seed.
     ///
-    /// The entropy estimate is conservative: we assume the raw bits
-    /// contain at most 1 bit of entropy per byte of input, capped
-    /// at the output size.
+    /// The entropy estimate follows the configured [`EntropyPolicy`].
+    /// By default it is conservative: we assume the raw bits contain
+    /// at most 1 bit of entropy per byte of input, capped at the
+    /// output size.
     pub fn condition(&self, raw: &RawBits) -> ConditionedSeed {
-        let data = match self.algorithm {
+        let data = self.hash(raw.data());
+        let entropy_estimate = self.policy.estimate(raw.data(), data.len() * 8);
+
+        ConditionedSeed {
+            data,
+            entropy_estimate,
+            source_frames: raw.source_frames(),
+            previous_digest: [0; 32],
+        }
+    }
+
+    /// Conditions raw bits, refusing input with too little entropy.
+    ///
+    /// The input's min-entropy is measured from its byte histogram
+    /// (see [`StatisticalTests::min_entropy_per_byte`]) whatever the
+    /// configured [`EntropyPolicy`], so garbage such as a constant
+    /// buffer is rejected here rather than at reseed time. The seed's
+    /// own estimate still follows the policy.
+    pub fn try_condition(
+        &self,
+        raw: &RawBits,
+        min_bits: usize,
+    ) -> Result<ConditionedSeed, ConditioningError> {
+        let measured = EntropyPolicy::Measured.estimate(raw.data(), usize::MAX);
+        if measured < min_bits {
+            tracing::warn!(measured, required = min_bits, "Rejected low-entropy input");
+            return Err(ConditioningError::InsufficientEntropy {
+                measured,
+                required: min_bits,
+            });
+        }
+        Ok(self.condition(raw))
+    }
+
+    /// Hashes the input with the configured algorithm.
+    fn hash(&self, input: &[u8]) -> Vec<u8> {
+        match self.algorithm {
             HashAlgorithm::Blake3 => {
                 let mut hasher = Blake3Hasher::new();
-                hasher.update(raw.data());
-                *hasher.finalize().as_bytes()
+                hasher.update(&self.salt_prefix);
+                update_blake3(&mut hasher, input);
+                hasher.finalize().as_bytes().to_vec()
             }
             HashAlgorithm::Sha256 => {
                 let mut hasher = Sha256::new();
-                hasher.update(raw.data());
-                let result = hasher.finalize();
-                let mut data = [0u8; 32];
-                data.copy_from_slice(&result);
+                Digest::update(&mut hasher, &self.salt_prefix);
+                Digest::update(&mut hasher, input);
+                hasher.finalize().to_vec()
+            }
+            HashAlgorithm::Shake256 { .. } => {
+                let mut hasher = Shake256::default();
+                hasher.update(&self.salt_prefix);
+                hasher.update(input);
+                let mut data = vec![0u8; self.algorithm.output_len()];
+                hasher.finalize_xof().read(&mut data);
                 data
             }
-        };
+        }
+    }
+}
 
-        // Conservative entropy estimate: assume ~1 bit per input byte,
-        // but never more than output size (256 bits).
-        let entropy_estimate = raw.len().min(256);
+/// Absorbs `input` into a BLAKE3 hasher.
+///
+/// With the `parallel` feature, large inputs are hashed across threads
+/// with `update_rayon`. BLAKE3's tree structure makes the digest
+/// identical to the serial path.
+fn update_blake3(hasher: &mut Blake3Hasher, input: &[u8]) {
+    #[cfg(feature = "parallel")]
+    if input.len() >= PARALLEL_HASH_MIN_BYTES {
+        hasher.update_rayon(input);
+        return;
+    }
+    hasher.update(input);
+}
 
-        ConditionedSeed {
-            data,
-            entropy_estimate,
-        }
+impl ConditioningBackend for Conditioner {
+    fn condition(&self, input: &[u8]) -> [u8; SEED_LEN] {
+        let mut out = [0u8; SEED_LEN];
+        out.copy_from_slice(&self.hash(input)[..SEED_LEN]);
+        out
+    }
+
+    fn condition_extended(&self, input: &[u8]) -> Vec<u8> {
+        self.hash(input)
     }
 }
 
@@ -120,6 +494,16 @@ impl Default for Conditioner {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_condition_matches_test_vectors() {
+        for vector in Conditioner::test_vectors() {
+            let raw = RawBits::from_bytes(vector.input.to_vec(), 1);
+            let seed = Conditioner::new(vector.algorithm).condition(&raw);
+            assert_eq!(seed.as_bytes(), &vector.expected, "{:?}", vector.algorithm);
+        }
+        assert!(Conditioner::self_test());
+    }
+
     #[test]
     fn test_blake3_conditioning() {
         let conditioner = Conditioner::new(HashAlgorithm::Blake3);
@@ -152,6 +536,45 @@ mod tests {
         assert_ne!(seed1.as_bytes(), seed2.as_bytes());
     }
 
+    #[test]
+    fn test_large_blake3_input_matches_serial_hash() {
+        // Above PARALLEL_HASH_MIN_BYTES: hashed across threads with `parallel`
+        let data: Vec<u8> = (0..1 << 20).map(|i: u32| (i * 53 + 31) as u8).collect();
+        let seed = Conditioner::new(HashAlgorithm::Blake3)
+            .condition(&RawBits::from_bytes(data.clone(), 1));
+
+        let mut serial = Blake3Hasher::new();
+        serial.update(&data);
+        assert_eq!(seed.as_bytes(), serial.finalize().as_bytes());
+    }
+
+    #[test]
+    fn test_salt_separates_devices() {
+        let raw = RawBits::from_bytes(vec![0x42; 1000], 1);
+
+        for algorithm in [
+            HashAlgorithm::Blake3,
+            HashAlgorithm::Sha256,
+            HashAlgorithm::Shake256 { out_len: 32 },
+        ] {
+            let unsalted = Conditioner::new(algorithm).condition(&raw);
+            let a = Conditioner::new(algorithm)
+                .with_salt(b"device-a".to_vec())
+                .condition(&raw);
+            let b = Conditioner::new(algorithm)
+                .with_salt(b"device-b".to_vec())
+                .condition(&raw);
+
+            assert_ne!(a.as_bytes(), b.as_bytes(), "{:?}", algorithm);
+            assert_ne!(a.as_bytes(), unsalted.as_bytes(), "{:?}", algorithm);
+            assert_eq!(a.entropy_estimate(), unsalted.entropy_estimate());
+
+            // An empty salt is no salt
+            let empty = Conditioner::new(algorithm).with_salt(Vec::new());
+            assert_eq!(empty.condition(&raw).as_bytes(), unsalted.as_bytes());
+        }
+    }
+
     #[test]
     fn test_small_input_limited_entropy() {
         let conditioner = Conditioner::default();
@@ -160,4 +583,105 @@ mod tests {
         let seed = conditioner.condition(&raw);
         assert_eq!(seed.entropy_estimate(), 10); // limited by input size
     }
+
+    #[test]
+    fn test_shake256_extended_output() {
+        let conditioner = Conditioner::new(HashAlgorithm::Shake256 { out_len: 64 });
+        let raw = RawBits::from_bytes(vec![0x42; 1000], 1);
+
+        let seed = conditioner.condition(&raw);
+        assert_eq!(seed.as_slice().len(), 64);
+        assert_eq!(&seed.as_slice()[..32], seed.as_bytes());
+        assert_eq!(seed.entropy_estimate(), 512);
+        assert_eq!(seed.seed_entropy_estimate(), 256);
+
+        // Deterministic for the same input
+        let again = conditioner.condition(&raw);
+        assert_eq!(seed.as_slice(), again.as_slice());
+    }
+
+    #[test]
+    fn test_audit_chain_detects_tampering() {
+        use crate::conditioning::{EntropyPool, PoolConfig};
+
+        let mut pool = EntropyPool::new(PoolConfig {
+            min_bits: 64,
+            ..Default::default()
+        });
+        let mut seeds = Vec::new();
+        for i in 0..3u8 {
+            pool.add(&RawBits::from_bytes(vec![i; 8], 1));
+            seeds.push(pool.extract().unwrap());
+        }
+
+        assert_eq!(seeds[0].previous_digest(), &[0; 32]);
+        assert_eq!(pool.chain_head(), &seeds[2].chain_digest());
+        assert!(verify_chain(&seeds));
+
+        let mut tampered = seeds.clone();
+        tampered[1].data[0] ^= 1;
+        assert!(!verify_chain(&tampered));
+
+        let mut reordered = seeds.clone();
+        reordered.swap(0, 1);
+        assert!(!verify_chain(&reordered));
+    }
+
+    #[test]
+    fn test_entropy_policies() {
+        let raw = RawBits::from_bytes(vec![0x42; 40], 1);
+
+        let assumed = Conditioner::default().with_policy(EntropyPolicy::PerByteAssumed(4.0));
+        assert_eq!(assumed.condition(&raw).entropy_estimate(), 160);
+
+        let fixed = Conditioner::default().with_policy(EntropyPolicy::Fixed(200));
+        assert_eq!(fixed.condition(&raw).entropy_estimate(), 200);
+
+        // Fixed estimates are still capped at the output size
+        let too_high = Conditioner::default().with_policy(EntropyPolicy::Fixed(1000));
+        assert_eq!(too_high.condition(&raw).entropy_estimate(), 256);
+
+        // Constant input has zero measured min-entropy
+        let measured = Conditioner::default().with_policy(EntropyPolicy::Measured);
+        assert_eq!(measured.condition(&raw).entropy_estimate(), 0);
+
+        // 16 equally likely values: 4 bits per byte
+        let varied = RawBits::from_bytes((0..64).map(|i| (i % 16) as u8).collect(), 1);
+        assert_eq!(measured.condition(&varied).entropy_estimate(), 256);
+        let short = RawBits::from_bytes((0..32).map(|i| (i % 16) as u8).collect(), 1);
+        assert_eq!(measured.condition(&short).entropy_estimate(), 128);
+    }
+
+    #[test]
+    fn test_try_condition_rejects_low_entropy() {
+        let conditioner = Conditioner::default();
+        let constant = RawBits::from_bytes(vec![0x5A; 256], 1);
+        assert_eq!(
+            conditioner.try_condition(&constant, 128).unwrap_err(),
+            ConditioningError::InsufficientEntropy {
+                measured: 0,
+                required: 128,
+            }
+        );
+
+        // 16 equally likely values: 4 bits per byte
+        let varied = RawBits::from_bytes((0..64).map(|i| (i % 16) as u8).collect(), 1);
+        let seed = conditioner.try_condition(&varied, 256).unwrap();
+        assert_eq!(seed.as_bytes(), conditioner.condition(&varied).as_bytes());
+        assert!(conditioner.try_condition(&varied, 257).is_err());
+    }
+
+    #[test]
+    fn test_seed_zeroizes_on_drop() {
+        fn assert_zeroize_on_drop<T: ZeroizeOnDrop>() {}
+        assert_zeroize_on_drop::<ConditionedSeed>();
+
+        // Drop runs the same wipe
+        let mut seed = ConditionedSeed::new_for_testing([0xAB; 32], 256);
+        seed.zeroize();
+        assert_eq!(seed.as_slice().len(), 32);
+        assert!(seed.as_slice().iter().all(|&b| b == 0));
+        assert_eq!(seed.as_bytes(), &[0u8; 32]);
+        assert_eq!(seed.entropy_estimate(), 256);
+    }
 }
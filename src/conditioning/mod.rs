@@ -4,8 +4,16 @@
 //! entropy suitable for CSPRNG reseeding. It uses well-established
 //! cryptographic hash functions to remove bias and correlations.
 
+mod backend;
 mod hash;
 mod pool;
+mod toeplitz;
 
-pub use hash::{ConditionedSeed, Conditioner, HashAlgorithm};
-pub use pool::{EntropyPool, PoolConfig};
+pub use crate::clock::{Clock, SystemClock};
+pub use backend::ConditioningBackend;
+pub use hash::{
+    verify_chain, ConditionedSeed, Conditioner, ConditioningError, EntropyPolicy, HashAlgorithm,
+    SEED_LEN,
+};
+pub use pool::{CheckpointError, EntropyPool, PoolConfig, PoolWeighting};
+pub use toeplitz::{ToeplitzError, ToeplitzExtractor};
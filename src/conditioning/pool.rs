@@ -3,26 +3,109 @@
 //! Collects entropy from multiple extractions before conditioning,
 //! ensuring sufficient entropy has been gathered before reseeding.
 
-use super::hash::{ConditionedSeed, Conditioner, HashAlgorithm};
+use super::backend::ConditioningBackend;
+use super::hash::{ConditionedSeed, Conditioner, EntropyPolicy, HashAlgorithm};
+use crate::analysis::StatisticalTests;
+use crate::clock::{Clock, SystemClock};
 use crate::extraction::RawBits;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+/// How added samples are credited towards the pool's entropy budget.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PoolWeighting {
+    /// Every byte is credited 8 bits.
+    #[default]
+    Uniform,
+    /// Bytes are credited in proportion to the sample's measured
+    /// quality, so marginal samples fill the budget more slowly.
+    ///
+    /// Quality is the product of the bias and variance components of
+    /// [`quality_score`](StatisticalTests::quality_score), 1.0 for
+    /// ideal bytes and 0 for constant or fully biased ones. The product
+    /// is deliberately stricter than the score's geometric mean, since
+    /// it sets how much entropy a seed is credited with.
+    Quality,
+}
+
+/// Version of the on-disk checkpoint format.
+const CHECKPOINT_VERSION: u32 = 1;
+
+/// Errors that can occur saving or restoring a pool checkpoint.
+#[derive(Debug, Error)]
+pub enum CheckpointError {
+    /// The checkpoint file could not be read or written.
+    #[error("checkpoint I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    /// The checkpoint file is corrupt.
+    #[error("invalid checkpoint: {0}")]
+    Format(#[from] bincode::Error),
+    /// The checkpoint was written by an incompatible version.
+    #[error("unsupported checkpoint version {0}")]
+    UnsupportedVersion(u32),
+}
+
+/// Pool state persisted by [`EntropyPool::checkpoint`].
+#[derive(Serialize, Deserialize)]
+struct Checkpoint {
+    version: u32,
+    buffer: Vec<u8>,
+    total_bits_added: u64,
+    total_extractions: u64,
+    source_frames: u64,
+    credited_bits: f64,
+    chain_head: [u8; 32],
+}
 
 /// Configuration for the entropy pool.
 #[derive(Debug, Clone)]
 pub struct PoolConfig {
     /// Minimum bits to accumulate before allowing extraction.
     pub min_bits: usize,
+    /// Minimum raw bytes fed to the conditioner, regardless of `min_bits` (0 disables).
+    ///
+    /// A floor on input size, independent of how readiness is counted,
+    /// so a small low-entropy buffer can never produce a seed.
+    pub min_conditioning_input_bytes: usize,
     /// Maximum bytes to buffer (prevents unbounded growth).
     pub max_bytes: usize,
-    /// Hash algorithm for conditioning.
+    /// Hash algorithm for conditioning (ignored by custom backends).
     pub algorithm: HashAlgorithm,
+    /// How the entropy of conditioned output is estimated.
+    pub entropy_policy: EntropyPolicy,
+    /// Minimum accepted entropy rate in bits/second (`None` disables the gate).
+    pub min_rate_bits_per_sec: Option<f64>,
+    /// Window over which the entropy rate is measured.
+    pub rate_window: Duration,
+    /// Number of independent segments the pool is conditioned in (1 disables).
+    ///
+    /// Each segment is conditioned separately and the digests are
+    /// combined with a final pass, in segment order. With the
+    /// `parallel` feature the segments are conditioned concurrently.
+    pub parallel_segments: usize,
+    /// How added samples are credited towards `min_bits`.
+    pub weighting: PoolWeighting,
+    /// Per-device salt for conditioning (empty disables; ignored by
+    /// custom backends). See [`Conditioner::with_salt`].
+    pub salt: Vec<u8>,
 }
 
 impl Default for PoolConfig {
     fn default() -> Self {
         Self {
-            min_bits: 512,        // Require 512 bits minimum
+            min_bits: 512, // Require 512 bits minimum
+            min_conditioning_input_bytes: 0,
             max_bytes: 64 * 1024, // Cap at 64KB
             algorithm: HashAlgorithm::Blake3,
+            entropy_policy: EntropyPolicy::default(),
+            min_rate_bits_per_sec: None,
+            rate_window: Duration::from_secs(10),
+            parallel_segments: 1,
+            weighting: PoolWeighting::Uniform,
+            salt: Vec::new(),
         }
     }
 }
@@ -32,29 +115,115 @@ impl Default for PoolConfig {
 /// The pool collects raw bits from multiple extraction cycles,
 /// ensuring sufficient entropy has been gathered before producing
 /// conditioned output for reseeding.
-pub struct EntropyPool {
+///
+/// Generic over the [`ConditioningBackend`]; the default is the
+/// hash-based [`Conditioner`] selected by `PoolConfig::algorithm`.
+pub struct EntropyPool<B: ConditioningBackend = Conditioner> {
     /// Accumulated raw bytes.
     buffer: Vec<u8>,
     /// Configuration.
     config: PoolConfig,
-    /// Conditioner instance.
-    conditioner: Conditioner,
+    /// Conditioning backend.
+    backend: B,
     /// Total bits added (for metrics).
     total_bits_added: u64,
     /// Total extractions performed.
     total_extractions: u64,
+    /// Source frames of the bits added since the last extraction.
+    source_frames: u64,
+    /// Entropy budget credited to the buffered bytes, in bits.
+    credited_bits: f64,
+    /// Chain digest of the last extraction (zeros before the first).
+    chain_head: [u8; 32],
+    /// Time source for rate measurement.
+    clock: Box<dyn Clock>,
+    /// When the pool was created.
+    created: Instant,
+    /// Recent additions (timestamp, bits) within the rate window.
+    recent: VecDeque<(Instant, u64)>,
 }
 
 impl EntropyPool {
     /// Creates a new entropy pool with the given configuration.
     pub fn new(config: PoolConfig) -> Self {
-        let conditioner = Conditioner::new(config.algorithm);
+        Self::with_clock(config, Box::new(SystemClock))
+    }
+
+    /// Creates a pool that measures entropy rate with a custom clock.
+    pub fn with_clock(config: PoolConfig, clock: Box<dyn Clock>) -> Self {
+        let conditioner = Conditioner::new(config.algorithm)
+            .with_policy(config.entropy_policy)
+            .with_salt(config.salt.clone());
+        EntropyPool::with_backend_and_clock(config, conditioner, clock)
+    }
+
+    /// Restores a pool saved with [`checkpoint`](Self::checkpoint).
+    ///
+    /// The buffer (truncated to `config.max_bytes`) and counters are
+    /// recovered; the entropy rate is measured afresh. Delete the file
+    /// once restored so the same bits are never conditioned twice.
+    pub fn restore(path: impl AsRef<Path>, config: PoolConfig) -> Result<Self, CheckpointError> {
+        let bytes = std::fs::read(path)?;
+        let checkpoint: Checkpoint = bincode::deserialize(&bytes)?;
+        if checkpoint.version != CHECKPOINT_VERSION {
+            return Err(CheckpointError::UnsupportedVersion(checkpoint.version));
+        }
+
+        let mut pool = Self::new(config);
+        let len = checkpoint.buffer.len().min(pool.config.max_bytes);
+        pool.buffer.extend_from_slice(&checkpoint.buffer[..len]);
+        pool.total_bits_added = checkpoint.total_bits_added;
+        pool.total_extractions = checkpoint.total_extractions;
+        pool.source_frames = checkpoint.source_frames;
+        pool.credited_bits = checkpoint.credited_bits.min((len * 8) as f64);
+        pool.chain_head = checkpoint.chain_head;
+
+        tracing::info!(pool_bytes = len, "Entropy pool restored from checkpoint");
+        Ok(pool)
+    }
+
+    /// Returns the active conditioning hash algorithm.
+    pub fn algorithm(&self) -> HashAlgorithm {
+        self.backend.algorithm()
+    }
+
+    /// Switches the conditioning hash algorithm mid-run.
+    ///
+    /// The buffered bytes are discarded, so no seed mixes input
+    /// gathered under both algorithms. Switching to the active
+    /// algorithm changes nothing.
+    pub fn set_algorithm(&mut self, algorithm: HashAlgorithm) {
+        if algorithm == self.backend.algorithm() {
+            return;
+        }
+        self.backend.set_algorithm(algorithm);
+        self.config.algorithm = algorithm;
+        self.clear();
+        tracing::info!(?algorithm, "Conditioning algorithm switched");
+    }
+}
+
+impl<B: ConditioningBackend> EntropyPool<B> {
+    /// Creates a pool that conditions with a custom backend.
+    pub fn with_backend(config: PoolConfig, backend: B) -> Self {
+        Self::with_backend_and_clock(config, backend, Box::new(SystemClock))
+    }
+
+    /// Creates a pool with a custom backend and clock.
+    pub fn with_backend_and_clock(config: PoolConfig, backend: B, clock: Box<dyn Clock>) -> Self {
+        let created = clock.now();
         Self {
             buffer: Vec::with_capacity(config.max_bytes),
             config,
-            conditioner,
+            backend,
             total_bits_added: 0,
             total_extractions: 0,
+            source_frames: 0,
+            credited_bits: 0.0,
+            chain_head: [0; 32],
+            clock,
+            created,
+            recent: VecDeque::new(),
         }
     }
 
@@ -65,6 +234,28 @@ impl EntropyPool {
 
         self.buffer.extend_from_slice(&raw.data()[..bytes_to_add]);
         self.total_bits_added += (bytes_to_add * 8) as u64;
+        if bytes_to_add > 0 {
+            self.source_frames += raw.source_frames();
+            let weight = match self.config.weighting {
+                PoolWeighting::Uniform => 1.0,
+                PoolWeighting::Quality => {
+                    let quality = StatisticalTests::analyze(raw).quality_components();
+                    quality.bias * quality.variance
+                }
+            };
+            self.credited_bits += (bytes_to_add * 8) as f64 * weight;
+        }
+
+        if self.config.min_rate_bits_per_sec.is_some() {
+            let now = self.clock.now();
+            self.recent.push_back((now, (bytes_to_add * 8) as u64));
+            while let Some(&(t, _)) = self.recent.front() {
+                if now.duration_since(t) <= self.config.rate_window {
+                    break;
+                }
+                self.recent.pop_front();
+            }
+        }
 
         tracing::trace!(
             bytes_added = bytes_to_add,
@@ -74,27 +265,112 @@ impl EntropyPool {
     }
 
     /// Returns true if the pool has enough entropy for extraction.
+    ///
+    /// Compares the credited entropy budget against `min_bits`; with
+    /// uniform weighting that is simply the buffered bit count.
     pub fn is_ready(&self) -> bool {
-        self.buffer.len() * 8 >= self.config.min_bits
+        self.credited_bits >= self.config.min_bits as f64
     }
 
-    /// Extracts conditioned entropy from the pool.
+    /// Returns the entropy budget credited to the buffered bytes, in bits.
+    pub fn credited_bits(&self) -> f64 {
+        self.credited_bits
+    }
+
+    /// Returns the entropy rate accepted over the recent window, in bits/second.
     ///
-    /// Returns `None` if insufficient entropy has been accumulated.
-    /// Clears the pool after extraction.
-    pub fn extract(&mut self) -> Option<ConditionedSeed> {
+    /// Measured over `rate_window`, or over the pool's lifetime if it is
+    /// younger than that. Returns `None` if no time has elapsed yet.
+    pub fn recent_rate(&self) -> Option<f64> {
+        let now = self.clock.now();
+        let span = now
+            .duration_since(self.created)
+            .min(self.config.rate_window);
+        if span.is_zero() {
+            return None;
+        }
+
+        let bits: u64 = self
+            .recent
+            .iter()
+            .filter(|(t, _)| now.duration_since(*t) <= self.config.rate_window)
+            .map(|(_, bits)| bits)
+            .sum();
+
+        Some(bits as f64 / span.as_secs_f64())
+    }
+
+    /// Returns true if the recent entropy rate meets the configured floor.
+    ///
+    /// Always true when no floor is configured. Fails closed when the
+    /// rate cannot yet be measured.
+    pub fn rate_ok(&self) -> bool {
+        match self.config.min_rate_bits_per_sec {
+            None => true,
+            Some(floor) => self.recent_rate().is_some_and(|rate| rate >= floor),
+        }
+    }
+
+    /// Conditions the pool contents without consuming them.
+    ///
+    /// Subject to the same checks as [`extract`](Self::extract). Until
+    /// more bits are added, repeated calls return the same seed, so a
+    /// caller can retry a failed reseed with the same material.
+    pub fn peek(&self) -> Option<ConditionedSeed> {
         if !self.is_ready() {
             tracing::debug!(
                 pool_bits = self.buffer.len() * 8,
+                credited_bits = self.credited_bits,
                 min_bits = self.config.min_bits,
                 "Pool not ready for extraction"
             );
             return None;
         }
 
-        let raw = RawBits::from_bytes(std::mem::take(&mut self.buffer), self.total_extractions);
-        let seed = self.conditioner.condition(&raw);
+        if self.buffer.len() < self.config.min_conditioning_input_bytes {
+            tracing::debug!(
+                pool_bytes = self.buffer.len(),
+                min_bytes = self.config.min_conditioning_input_bytes,
+                "Pool below conditioning input floor"
+            );
+            return None;
+        }
 
+        if !self.rate_ok() {
+            tracing::debug!(
+                rate = self.recent_rate(),
+                min_rate = self.config.min_rate_bits_per_sec,
+                "Entropy rate below floor"
+            );
+            return None;
+        }
+
+        let data = condition_segmented(&self.backend, &self.buffer, self.config.parallel_segments);
+        let entropy_estimate = self
+            .config
+            .entropy_policy
+            .estimate(&self.buffer, data.len() * 8);
+        let seed = ConditionedSeed::from_parts(data, entropy_estimate)
+            .with_source_frames(self.source_frames)
+            .with_previous_digest(self.chain_head);
+        Some(seed)
+    }
+
+    /// Extracts conditioned entropy from the pool.
+    ///
+    /// Returns `None` if insufficient entropy has been accumulated,
+    /// the buffer is below `min_conditioning_input_bytes`, or the
+    /// entropy rate is below the configured floor.
+    /// Clears the pool after extraction.
+    ///
+    /// Successive seeds form an audit hash chain; see
+    /// [`verify_chain`](super::verify_chain).
+    pub fn extract(&mut self) -> Option<ConditionedSeed> {
+        let seed = self.peek()?;
+        self.chain_head = seed.chain_digest();
+        self.buffer.clear();
+        self.source_frames = 0;
+        self.credited_bits = 0.0;
         self.total_extractions += 1;
 
         tracing::debug!(
@@ -126,13 +402,108 @@ impl EntropyPool {
         self.total_extractions
     }
 
+    /// Returns the chain digest of the last extraction, or zeros if
+    /// there has been none.
+    pub fn chain_head(&self) -> &[u8; 32] {
+        &self.chain_head
+    }
+
+    /// Saves the buffer and counters to `path`.
+    ///
+    /// # Security
+    ///
+    /// The buffer is unconditioned entropy that will feed a future
+    /// seed: anyone who reads the file learns that input. The file is
+    /// created readable by the owner only (on Unix) but is not
+    /// encrypted, so keep it on a protected, preferably non-persistent
+    /// volume and never checkpoint to shared storage.
+    ///
+    /// The checkpoint is written to a temporary file beside `path` and
+    /// renamed over it, so a crash never leaves a partial checkpoint
+    /// and an existing file's looser permissions are not inherited.
+    pub fn checkpoint(&self, path: impl AsRef<Path>) -> Result<(), CheckpointError> {
+        use std::io::Write;
+
+        let bytes = bincode::serialize(&Checkpoint {
+            version: CHECKPOINT_VERSION,
+            buffer: self.buffer.clone(),
+            total_bits_added: self.total_bits_added,
+            total_extractions: self.total_extractions,
+            source_frames: self.source_frames,
+            credited_bits: self.credited_bits,
+            chain_head: self.chain_head,
+        })?;
+
+        let path = path.as_ref();
+        let mut tmp_path = path.as_os_str().to_owned();
+        tmp_path.push(".tmp");
+        let tmp_path = PathBuf::from(tmp_path);
+        // A stale temporary file may have other permissions
+        let _ = std::fs::remove_file(&tmp_path);
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let written = options.open(&tmp_path).and_then(|mut file| {
+            file.write_all(&bytes)?;
+            file.sync_all()
+        });
+        if let Err(e) = written.and_then(|()| std::fs::rename(&tmp_path, path)) {
+            let _ = std::fs::remove_file(&tmp_path);
+            return Err(e.into());
+        }
+
+        tracing::debug!(pool_bytes = self.buffer.len(), "Entropy pool checkpointed");
+        Ok(())
+    }
+
     /// Clears the pool without extracting.
     pub fn clear(&mut self) {
         self.buffer.clear();
+        self.source_frames = 0;
+        self.credited_bits = 0.0;
         tracing::info!("Entropy pool cleared");
     }
 }
 
+/// Conditions `input` as `segments` independent pieces, then conditions
+/// the concatenated digests.
+///
+/// Segment order is fixed, so the output depends only on the input and
+/// the segment count, never on thread scheduling. A single segment (or
+/// an input shorter than the segment count) is conditioned directly.
+fn condition_segmented<B: ConditioningBackend>(
+    backend: &B,
+    input: &[u8],
+    segments: usize,
+) -> Vec<u8> {
+    if segments <= 1 || input.len() < segments {
+        return backend.condition_extended(input);
+    }
+
+    let segment_len = input.len().div_ceil(segments);
+
+    #[cfg(feature = "parallel")]
+    let digests: Vec<Vec<u8>> = {
+        use rayon::prelude::*;
+        input
+            .par_chunks(segment_len)
+            .map(|segment| backend.condition_extended(segment))
+            .collect()
+    };
+    #[cfg(not(feature = "parallel"))]
+    let digests: Vec<Vec<u8>> = input
+        .chunks(segment_len)
+        .map(|segment| backend.condition_extended(segment))
+        .collect();
+
+    backend.condition_extended(&digests.concat())
+}
+
 impl Default for EntropyPool {
     fn default() -> Self {
         Self::new(PoolConfig::default())
@@ -142,6 +513,7 @@ impl Default for EntropyPool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clock::ManualClock;
 
     #[test]
     fn test_pool_not_ready_initially() {
@@ -178,6 +550,168 @@ mod tests {
         assert_eq!(pool.size_bytes(), 0);
     }
 
+    #[test]
+    fn test_peek_leaves_pool_unchanged() {
+        let config = PoolConfig {
+            min_bits: 80,
+            ..Default::default()
+        };
+        let mut pool = EntropyPool::new(config);
+        assert!(pool.peek().is_none());
+
+        let data = (0..20).map(|i| (i * 53 + 31) as u8).collect();
+        pool.add(&RawBits::from_bytes(data, 1));
+        let first = pool.peek().unwrap();
+        let second = pool.peek().unwrap();
+        assert_eq!(first.as_bytes(), second.as_bytes());
+        assert_eq!(pool.size_bytes(), 20);
+        assert_eq!(pool.total_extractions(), 0);
+
+        let extracted = pool.extract().unwrap();
+        assert_eq!(extracted.as_bytes(), first.as_bytes());
+        assert_eq!(extracted.source_frames(), 1);
+        assert_eq!(pool.size_bytes(), 0);
+    }
+
+    #[test]
+    fn test_set_algorithm_clears_and_switches() {
+        let config = PoolConfig {
+            min_bits: 80,
+            ..Default::default()
+        };
+        let mut pool = EntropyPool::new(config);
+        assert_eq!(pool.algorithm(), HashAlgorithm::Blake3);
+
+        let data: Vec<u8> = (0..20).map(|i| (i * 53 + 31) as u8).collect();
+        pool.add(&RawBits::from_bytes(data.clone(), 1));
+        pool.set_algorithm(HashAlgorithm::Sha256);
+        assert_eq!(pool.algorithm(), HashAlgorithm::Sha256);
+        assert_eq!(pool.size_bytes(), 0);
+        assert!(!pool.is_ready());
+
+        let raw = RawBits::from_bytes(data, 1);
+        pool.add(&raw);
+        let seed = pool.extract().unwrap();
+        let sha256 = Conditioner::new(HashAlgorithm::Sha256).condition(&raw);
+        let blake3 = Conditioner::new(HashAlgorithm::Blake3).condition(&raw);
+        assert_eq!(seed.as_bytes(), sha256.as_bytes());
+        assert_ne!(seed.as_bytes(), blake3.as_bytes());
+    }
+
+    #[test]
+    fn test_salt_applied_to_conditioning() {
+        let mut pool = EntropyPool::new(PoolConfig {
+            min_bits: 80,
+            salt: b"camera-01".to_vec(),
+            ..Default::default()
+        });
+
+        let raw = RawBits::from_bytes((0..20).map(|i| (i * 53 + 31) as u8).collect(), 1);
+        pool.add(&raw);
+        let seed = pool.extract().unwrap();
+        let salted = Conditioner::default().with_salt(b"camera-01".to_vec());
+        assert_eq!(seed.as_bytes(), salted.condition(&raw).as_bytes());
+        assert_ne!(
+            seed.as_bytes(),
+            Conditioner::default().condition(&raw).as_bytes()
+        );
+    }
+
+    #[test]
+    fn test_seed_source_frames_summed() {
+        let config = PoolConfig {
+            min_bits: 80,
+            ..Default::default()
+        };
+        let mut pool = EntropyPool::new(config);
+
+        pool.add(&RawBits::from_bytes(vec![0x42; 4], 2));
+        pool.add(&RawBits::from_bytes(vec![0x42; 4], 3));
+        pool.add(&RawBits::from_bytes(vec![0x42; 4], 5));
+        assert_eq!(pool.extract().unwrap().source_frames(), 10);
+
+        // Counting restarts after extraction
+        pool.add(&RawBits::from_bytes(vec![0x42; 12], 1));
+        assert_eq!(pool.extract().unwrap().source_frames(), 1);
+    }
+
+    #[test]
+    fn test_checkpoint_round_trip() {
+        let path = std::env::temp_dir().join(format!(
+            "optical-entropy-pool-{}.checkpoint",
+            std::process::id()
+        ));
+        let config = PoolConfig {
+            min_bits: 80,
+            ..Default::default()
+        };
+
+        let mut pool = EntropyPool::new(config.clone());
+        pool.add(&RawBits::from_bytes(vec![0x42; 20], 1));
+        pool.extract().unwrap();
+        pool.add(&RawBits::from_bytes(vec![0x17; 7], 2));
+        pool.checkpoint(&path).unwrap();
+
+        let restored = EntropyPool::restore(&path, config).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(restored.size_bytes(), 7);
+        assert_eq!(restored.total_bits_added(), pool.total_bits_added());
+        assert_eq!(restored.total_extractions(), 1);
+        assert_eq!(restored.buffer, pool.buffer);
+        assert_eq!(restored.source_frames, 2);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_checkpoint_replaces_file_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = std::env::temp_dir().join(format!(
+            "optical-entropy-pool-{}-mode.checkpoint",
+            std::process::id()
+        ));
+        std::fs::write(&path, b"stale").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        let mut pool = EntropyPool::default();
+        pool.add(&RawBits::from_bytes(vec![0x42; 20], 1));
+        pool.checkpoint(&path).unwrap();
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        let restored = EntropyPool::restore(&path, PoolConfig::default()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(restored.size_bytes(), 20);
+    }
+
+    #[test]
+    fn test_quality_weighting_credits_good_samples() {
+        let config = PoolConfig {
+            weighting: PoolWeighting::Quality,
+            ..Default::default()
+        };
+        let good: Vec<u8> = (0..1000).map(|i| (i * 53 + 31) as u8).collect();
+        // Same spread of values, but every byte has its top bit set
+        let marginal: Vec<u8> = good.iter().map(|b| b | 0xC0).collect();
+
+        let mut good_pool = EntropyPool::new(config.clone());
+        good_pool.add(&RawBits::from_bytes(good, 1));
+        let mut marginal_pool = EntropyPool::new(config);
+        marginal_pool.add(&RawBits::from_bytes(marginal, 1));
+
+        assert_eq!(good_pool.size_bytes(), marginal_pool.size_bytes());
+        assert!(good_pool.credited_bits() > 0.9 * 8000.0);
+        // Bias 0.125 and 1/16 of the uniform variance: about 0.75 * 0.0625 of 8000 bits
+        assert!((marginal_pool.credited_bits() - 374.77).abs() < 0.01);
+
+        // Uniform weighting credits every bit
+        let mut uniform = EntropyPool::default();
+        uniform.add(&RawBits::from_bytes(vec![0xFF; 100], 1));
+        assert_eq!(uniform.credited_bits(), 800.0);
+    }
+
     #[test]
     fn test_max_bytes_limit() {
         let config = PoolConfig {
@@ -193,4 +727,101 @@ mod tests {
         // Should be capped at max_bytes
         assert_eq!(pool.size_bytes(), 10);
     }
+
+    #[test]
+    fn test_slow_entropy_fails_rate_gate() {
+        let clock = ManualClock::new();
+        let config = PoolConfig {
+            min_bits: 80,
+            min_rate_bits_per_sec: Some(1000.0),
+            rate_window: Duration::from_secs(10),
+            ..Default::default()
+        };
+        let mut pool = EntropyPool::with_clock(config, Box::new(clock.clone()));
+
+        // 80 bits over 10 seconds = 8 bits/second
+        pool.add(&RawBits::from_bytes(vec![0u8; 10], 1));
+        clock.advance(Duration::from_secs(10));
+
+        assert!(pool.is_ready());
+        assert!(!pool.rate_ok());
+        assert!(pool.extract().is_none());
+
+        // A burst of 80,000 bits within the window lifts the rate
+        pool.add(&RawBits::from_bytes(vec![0u8; 10_000], 2));
+        clock.advance(Duration::from_secs(1));
+
+        assert!(pool.rate_ok());
+        assert!(pool.extract().is_some());
+    }
+
+    #[test]
+    fn test_conditioning_input_floor() {
+        let mut pool = EntropyPool::new(PoolConfig {
+            min_bits: 80,
+            min_conditioning_input_bytes: 64,
+            ..Default::default()
+        });
+
+        pool.add(&RawBits::from_bytes(vec![0x42; 20], 1));
+        assert!(pool.is_ready());
+        assert!(pool.extract().is_none());
+        assert_eq!(pool.size_bytes(), 20);
+
+        pool.add(&RawBits::from_bytes(vec![0x42; 44], 2));
+        assert!(pool.extract().is_some());
+    }
+
+    #[test]
+    fn test_segmented_conditioning_deterministic() {
+        let data: Vec<u8> = (0..4096).map(|i| (i * 53 + 31) as u8).collect();
+        let extract = |segments| {
+            let mut pool = EntropyPool::new(PoolConfig {
+                parallel_segments: segments,
+                ..Default::default()
+            });
+            pool.add(&RawBits::from_bytes(data.clone(), 1));
+            pool.extract().unwrap()
+        };
+
+        // Same data and segment count: same seed, however scheduled
+        let a = extract(4);
+        let b = extract(4);
+        assert_eq!(a.as_slice(), b.as_slice());
+
+        // Matches conditioning the segments in order by hand
+        let conditioner = Conditioner::default();
+        let digests: Vec<u8> = data
+            .chunks(1024)
+            .flat_map(|segment| ConditioningBackend::condition_extended(&conditioner, segment))
+            .collect();
+        assert_eq!(a.as_slice(), conditioner.condition_extended(&digests));
+
+        // A single segment is plain conditioning
+        assert_eq!(extract(1).as_slice(), conditioner.condition_extended(&data));
+        assert_eq!(a.entropy_estimate(), extract(1).entropy_estimate());
+    }
+
+    struct ConstantBackend;
+
+    impl ConditioningBackend for ConstantBackend {
+        fn condition(&self, _input: &[u8]) -> [u8; 32] {
+            [0x5A; 32]
+        }
+    }
+
+    #[test]
+    fn test_custom_backend_is_used() {
+        let config = PoolConfig {
+            min_bits: 80,
+            ..Default::default()
+        };
+        let mut pool = EntropyPool::with_backend(config, ConstantBackend);
+
+        pool.add(&RawBits::from_bytes(vec![1u8; 20], 1));
+        let seed = pool.extract().unwrap();
+
+        assert_eq!(seed.as_bytes(), &[0x5A; 32]);
+        assert_eq!(seed.entropy_estimate(), 20);
+    }
 }
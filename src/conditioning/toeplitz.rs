@@ -0,0 +1,259 @@
+//! Seeded Toeplitz-matrix randomness extractor.
+//!
+//! An information-theoretic alternative to hash conditioning. Toeplitz
+//! matrices form a universal hash family, so by the leftover hash lemma,
+//! multiplying an `n`-bit input with min-entropy `k` by a uniformly
+//! random `m x n` Toeplitz matrix over GF(2) yields output within
+//! statistical distance `ε` of uniform when `m <= k - 2·log2(1/ε)`.
+//! Unlike a hash, this holds without computational assumptions, but
+//! only if the seed is uniformly random, independent of the source,
+//! and the min-entropy estimate is honest.
+//!
+//! The seed (`n + m - 1` bits) is fixed at construction, typically
+//! loaded once at startup.
+
+use super::backend::ConditioningBackend;
+use super::hash::SEED_LEN;
+use rand_core::RngCore;
+use thiserror::Error;
+
+/// Errors constructing a [`ToeplitzExtractor`].
+#[derive(Debug, Clone, Error)]
+pub enum ToeplitzError {
+    /// The seed does not define the whole matrix.
+    #[error("seed too short: got {got} bits, need {need} bits")]
+    SeedTooShort {
+        /// Seed length supplied, in bits.
+        got: usize,
+        /// Seed length required, in bits.
+        need: usize,
+    },
+    /// The output would be shorter than a CSPRNG seed.
+    #[error("output of {bits} bits is below the {min} bit minimum")]
+    OutputTooShort {
+        /// Requested output length, in bits.
+        bits: usize,
+        /// Minimum output length, in bits.
+        min: usize,
+    },
+    /// The output is longer than the input block.
+    #[error("output of {output} bits exceeds input block of {input} bits")]
+    OutputExceedsInput {
+        /// Requested output length, in bits.
+        output: usize,
+        /// Input block length, in bits.
+        input: usize,
+    },
+}
+
+/// Toeplitz-hash extractor mapping `input_bits` to `output_bits`.
+///
+/// Inputs longer than `input_bits` are folded into one block by XOR;
+/// shorter inputs are zero-padded. Size the pool so that one block's
+/// worth of input carries the estimated min-entropy.
+#[derive(Clone)]
+pub struct ToeplitzExtractor {
+    /// Seed bits in reverse order, packed LSB-first, so that each
+    /// matrix row is a contiguous window.
+    reversed_seed: Vec<u64>,
+    input_bits: usize,
+    output_bits: usize,
+}
+
+impl ToeplitzExtractor {
+    /// Creates an extractor from explicit seed bytes.
+    ///
+    /// `output_bits` must be a multiple of 8, at least `SEED_LEN * 8`,
+    /// and no larger than `input_bits`.
+    pub fn new(seed: &[u8], input_bits: usize, output_bits: usize) -> Result<Self, ToeplitzError> {
+        let min = SEED_LEN * 8;
+        if output_bits < min || !output_bits.is_multiple_of(8) {
+            return Err(ToeplitzError::OutputTooShort {
+                bits: output_bits,
+                min,
+            });
+        }
+        if output_bits > input_bits {
+            return Err(ToeplitzError::OutputExceedsInput {
+                output: output_bits,
+                input: input_bits,
+            });
+        }
+
+        let need = Self::seed_bits_required(input_bits, output_bits);
+        if seed.len() * 8 < need {
+            return Err(ToeplitzError::SeedTooShort {
+                got: seed.len() * 8,
+                need,
+            });
+        }
+
+        let mut reversed_seed = vec![0u64; need.div_ceil(64) + 1];
+        for k in 0..need {
+            let src = need - 1 - k;
+            let bit = (seed[src / 8] >> (src % 8)) & 1;
+            reversed_seed[k / 64] |= (bit as u64) << (k % 64);
+        }
+
+        Ok(Self {
+            reversed_seed,
+            input_bits,
+            output_bits,
+        })
+    }
+
+    /// Creates an extractor whose output length follows from the
+    /// input's min-entropy and a security parameter.
+    ///
+    /// Output is `min_entropy_bits - 2 * security_bits`, rounded down
+    /// to whole bytes, where `security_bits` is `log2(1/ε)`.
+    pub fn for_min_entropy(
+        seed: &[u8],
+        input_bits: usize,
+        min_entropy_bits: usize,
+        security_bits: usize,
+    ) -> Result<Self, ToeplitzError> {
+        let output_bits = min_entropy_bits.saturating_sub(2 * security_bits) / 8 * 8;
+        Self::new(seed, input_bits, output_bits)
+    }
+
+    /// Creates an extractor with a fresh seed from the OS.
+    pub fn from_os_seed(input_bits: usize, output_bits: usize) -> Result<Self, ToeplitzError> {
+        let need = Self::seed_bits_required(input_bits, output_bits);
+        let mut seed = vec![0u8; need.div_ceil(8)];
+        rand_core::OsRng.fill_bytes(&mut seed);
+        Self::new(&seed, input_bits, output_bits)
+    }
+
+    /// Returns the seed length required for the given dimensions, in bits.
+    pub fn seed_bits_required(input_bits: usize, output_bits: usize) -> usize {
+        (input_bits + output_bits).saturating_sub(1)
+    }
+
+    /// Returns the input block length in bits.
+    pub fn input_bits(&self) -> usize {
+        self.input_bits
+    }
+
+    /// Returns the output length in bits.
+    pub fn output_bits(&self) -> usize {
+        self.output_bits
+    }
+
+    /// Multiplies the (folded) input by the Toeplitz matrix.
+    fn extract(&self, input: &[u8]) -> Vec<u8> {
+        let n = self.input_bits;
+        let m = self.output_bits;
+
+        // Fold the input into a single n-bit block
+        let mut block = vec![0u64; n.div_ceil(64)];
+        for (j, bit) in input
+            .iter()
+            .flat_map(|&byte| (0..8).map(move |b| (byte >> b) & 1))
+            .enumerate()
+        {
+            let j = j % n;
+            block[j / 64] ^= (bit as u64) << (j % 64);
+        }
+
+        // Row i is the window of the reversed seed starting at m - 1 - i
+        let mut output = vec![0u8; m / 8];
+        for i in 0..m {
+            let offset = m - 1 - i;
+            let mut acc = 0u64;
+            for (w, &x) in block.iter().enumerate() {
+                acc ^= x & self.seed_window(offset + w * 64);
+            }
+            let parity = (acc.count_ones() & 1) as u8;
+            output[i / 8] |= parity << (i % 8);
+        }
+
+        output
+    }
+
+    /// Returns 64 reversed-seed bits starting at `offset`.
+    fn seed_window(&self, offset: usize) -> u64 {
+        let (word, bit) = (offset / 64, offset % 64);
+        let lo = self.reversed_seed.get(word).copied().unwrap_or(0) >> bit;
+        let hi = if bit == 0 {
+            0
+        } else {
+            self.reversed_seed.get(word + 1).copied().unwrap_or(0) << (64 - bit)
+        };
+        lo | hi
+    }
+}
+
+impl ConditioningBackend for ToeplitzExtractor {
+    fn condition(&self, input: &[u8]) -> [u8; SEED_LEN] {
+        let mut out = [0u8; SEED_LEN];
+        out.copy_from_slice(&self.extract(input)[..SEED_LEN]);
+        out
+    }
+
+    fn condition_extended(&self, input: &[u8]) -> Vec<u8> {
+        self.extract(input)
+    }
+}
+
+impl std::fmt::Debug for ToeplitzExtractor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ToeplitzExtractor")
+            .field("input_bits", &self.input_bits)
+            .field("output_bits", &self.output_bits)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_chacha::ChaCha8Rng;
+    use rand_core::SeedableRng;
+
+    fn seed(key: u64, input_bits: usize, output_bits: usize) -> Vec<u8> {
+        let len = ToeplitzExtractor::seed_bits_required(input_bits, output_bits).div_ceil(8);
+        let mut seed = vec![0u8; len];
+        ChaCha8Rng::seed_from_u64(key).fill_bytes(&mut seed);
+        seed
+    }
+
+    #[test]
+    fn test_output_length_matches_config() {
+        let extractor = ToeplitzExtractor::new(&seed(0, 4096, 512), 4096, 512).unwrap();
+        let input: Vec<u8> = (0..512).map(|i| (i * 53 + 31) as u8).collect();
+
+        assert_eq!(extractor.condition_extended(&input).len(), 64);
+
+        let derived =
+            ToeplitzExtractor::for_min_entropy(&seed(0, 4096, 896), 4096, 1024, 64).unwrap();
+        assert_eq!(derived.output_bits(), 896);
+        assert_eq!(derived.condition_extended(&input).len(), 112);
+    }
+
+    #[test]
+    fn test_different_seeds_differ() {
+        let a = ToeplitzExtractor::new(&seed(1, 2048, 256), 2048, 256).unwrap();
+        let b = ToeplitzExtractor::new(&seed(2, 2048, 256), 2048, 256).unwrap();
+        let input: Vec<u8> = (0..256).map(|i| (i * 53 + 31) as u8).collect();
+
+        assert_ne!(a.condition(&input), b.condition(&input));
+        assert_eq!(a.condition(&input), a.condition(&input));
+    }
+
+    #[test]
+    fn test_invalid_dimensions_rejected() {
+        assert!(matches!(
+            ToeplitzExtractor::new(&[0u8; 4], 2048, 256),
+            Err(ToeplitzError::SeedTooShort { .. })
+        ));
+        assert!(matches!(
+            ToeplitzExtractor::new(&seed(0, 2048, 128), 2048, 128),
+            Err(ToeplitzError::OutputTooShort { .. })
+        ));
+        assert!(matches!(
+            ToeplitzExtractor::new(&seed(0, 256, 512), 256, 512),
+            Err(ToeplitzError::OutputExceedsInput { .. })
+        ));
+    }
+}
@@ -0,0 +1,86 @@
+//! Heuristic bit-bias reduction.
+//!
+//! If bits are independent with one-probability `p`, then XORing a bit
+//! with the complement of another yields one with probability
+//! `p² + (1 - p)²`, which lies closer to 0.5 whenever `p` does not
+//! equal 0.5 or 1 (e.g. 0.75 becomes 0.625). This is a heuristic: it
+//! assumes neighboring bytes are independent, and for correlated input
+//! it can increase bias (identical bytes map to all ones). It is no
+//! substitute for conditioning and only improves what the health tests
+//! see before it.
+
+/// XORs each byte with the complement of the byte `delay` positions earlier.
+///
+/// The first `delay` bytes have no partner and are dropped, so the
+/// output is `delay` bytes shorter than the input.
+pub struct BiasCorrector {
+    delay: usize,
+}
+
+impl BiasCorrector {
+    /// Creates a corrector pairing each byte with its predecessor.
+    pub fn new() -> Self {
+        Self { delay: 1 }
+    }
+
+    /// Creates a corrector pairing bytes `delay` positions apart (at least 1).
+    ///
+    /// A larger delay reduces the chance of pairing correlated
+    /// neighbors, such as adjacent pixels.
+    pub fn with_delay(delay: usize) -> Self {
+        Self {
+            delay: delay.max(1),
+        }
+    }
+
+    /// Returns the pairing distance in bytes.
+    pub fn delay(&self) -> usize {
+        self.delay
+    }
+
+    /// Corrects a buffer.
+    pub fn correct(&self, data: &[u8]) -> Vec<u8> {
+        data.iter()
+            .skip(self.delay)
+            .zip(data)
+            .map(|(&byte, &earlier)| byte ^ !earlier)
+            .collect()
+    }
+}
+
+impl Default for BiasCorrector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extraction::RawBits;
+    use rand_chacha::ChaCha8Rng;
+    use rand_core::{RngCore, SeedableRng};
+
+    #[test]
+    fn test_reduces_bias_of_75_percent_ones() {
+        // Each bit is the OR of two fair bits: one with probability 0.75
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        let data: Vec<u8> = (0..4096)
+            .map(|_| (rng.next_u32() as u8) | (rng.next_u32() as u8))
+            .collect();
+        let input = RawBits::from_bytes(data, 1);
+        assert!((input.bit_bias() - 0.25).abs() < 0.01);
+
+        let corrected = RawBits::from_bytes(BiasCorrector::new().correct(input.data()), 1);
+        assert_eq!(corrected.len(), input.len() - 1);
+        assert!(corrected.bit_bias().abs() < input.bit_bias().abs());
+        assert!((corrected.bit_bias() - 0.125).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_delay_drops_leading_bytes() {
+        let corrector = BiasCorrector::with_delay(2);
+        assert_eq!(corrector.correct(&[0x0F, 0xAA, 0x0F]), vec![0xFF]);
+        assert!(corrector.correct(&[0x01]).is_empty());
+    }
+}
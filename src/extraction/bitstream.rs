@@ -1,5 +1,15 @@
 //! Raw bitstream type for extracted entropy.
 
+/// Order in which the bits of each byte are read as a stream.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BitOrder {
+    /// Most significant bit first.
+    #[default]
+    MsbFirst,
+    /// Least significant bit first, as produced by packed LSB extraction.
+    LsbFirst,
+}
+
 /// Raw bits extracted from camera frames.
 ///
 /// This is the output of the extraction stage and input to conditioning.
@@ -10,6 +20,8 @@ pub struct RawBits {
     data: Vec<u8>,
     /// Number of source frames that contributed.
     source_frames: u64,
+    /// How bytes are read as a bit stream.
+    bit_order: BitOrder,
 }
 
 impl RawBits {
@@ -18,9 +30,22 @@ impl RawBits {
         Self {
             data,
             source_frames,
+            bit_order: BitOrder::default(),
         }
     }
 
+    /// Sets the bit order used by stream-based tests.
+    pub fn with_bit_order(mut self, bit_order: BitOrder) -> Self {
+        self.bit_order = bit_order;
+        self
+    }
+
+    /// Returns the bit order.
+    #[inline]
+    pub fn bit_order(&self) -> BitOrder {
+        self.bit_order
+    }
+
     /// Returns the raw byte data.
     #[inline]
     pub fn data(&self) -> &[u8] {
@@ -51,7 +76,73 @@ impl RawBits {
         self.source_frames
     }
 
+    /// Combines the output of two independent sources by interleaving
+    /// their bytes: `self[0] other[0] self[1] other[1] ...`.
+    ///
+    /// If one buffer is longer, its remaining bytes follow in order.
+    /// The source frame counts are summed and the bit order of `self`
+    /// is kept.
+    ///
+    /// For independent sources, the min-entropy of the combined buffer
+    /// is the sum of the two, so the conditioned seed is at least as
+    /// strong as either source alone and stays sound if one of them
+    /// degrades. This holds only if the sources really are independent
+    /// (e.g. separate sensors, not two streams of one sensor), and it
+    /// does not raise the entropy per byte.
+    pub fn combine_interleave(&self, other: &RawBits) -> RawBits {
+        let mut data = Vec::with_capacity(self.len() + other.len());
+        let shared = self.len().min(other.len());
+        for (&a, &b) in self.data.iter().zip(&other.data) {
+            data.push(a);
+            data.push(b);
+        }
+        data.extend_from_slice(&self.data[shared..]);
+        data.extend_from_slice(&other.data[shared..]);
+
+        RawBits::from_bytes(data, self.source_frames + other.source_frames)
+            .with_bit_order(self.bit_order)
+    }
+
+    /// Returns the bit at stream position `index`, honoring the bit order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= bit_count()`.
+    pub fn bit(&self, index: usize) -> bool {
+        let shift = match self.bit_order {
+            BitOrder::MsbFirst => 7 - index % 8,
+            BitOrder::LsbFirst => index % 8,
+        };
+        (self.data[index / 8] >> shift) & 1 == 1
+    }
+
+    /// Iterates over the bit stream in the configured order.
+    pub fn bits(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.bit_count()).map(|i| self.bit(i))
+    }
+
+    /// Counts runs of identical consecutive bits in the stream.
+    ///
+    /// Unlike [`popcount`](Self::popcount), this depends on the bit
+    /// order, since byte boundaries join different bit pairs.
+    pub fn runs(&self) -> usize {
+        let mut bits = self.bits();
+        let Some(mut previous) = bits.next() else {
+            return 0;
+        };
+        let mut runs = 1;
+        for bit in bits {
+            if bit != previous {
+                runs += 1;
+                previous = bit;
+            }
+        }
+        runs
+    }
+
     /// Counts the number of set bits (for bias analysis).
+    ///
+    /// Independent of the bit order.
     pub fn popcount(&self) -> usize {
         self.data.iter().map(|b| b.count_ones() as usize).sum()
     }
@@ -59,6 +150,7 @@ impl RawBits {
     /// Calculates bit bias as deviation from 0.5.
     ///
     /// Returns a value in [-0.5, 0.5] where 0.0 is unbiased.
+    /// Independent of the bit order.
     pub fn bit_bias(&self) -> f64 {
         if self.is_empty() {
             return 0.0;
@@ -74,6 +166,7 @@ impl std::fmt::Debug for RawBits {
         f.debug_struct("RawBits")
             .field("bytes", &self.data.len())
             .field("source_frames", &self.source_frames)
+            .field("bit_order", &self.bit_order)
             .field("bit_bias", &format!("{:.4}", self.bit_bias()))
             .finish()
     }
@@ -110,4 +203,41 @@ mod tests {
         // All zeros = bias of -0.5
         assert!((bits.bit_bias() + 0.5).abs() < 0.001);
     }
+
+    #[test]
+    fn test_combine_interleave() {
+        let a = RawBits::from_bytes(vec![1, 2, 3, 4], 2);
+        let b = RawBits::from_bytes(vec![11, 12], 3);
+
+        let combined = a.combine_interleave(&b);
+        assert_eq!(combined.data(), &[1, 11, 2, 12, 3, 4]);
+        assert_eq!(combined.source_frames(), 5);
+
+        let combined = b.combine_interleave(&a);
+        assert_eq!(combined.data(), &[11, 1, 12, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_bit_order_msb_first() {
+        // Stream: 00000001 00000000
+        let bits = RawBits::from_bytes(vec![0x01, 0x00], 1);
+        assert_eq!(bits.bit_order(), BitOrder::MsbFirst);
+
+        assert!(bits.bit(7));
+        assert!(!bits.bit(0));
+        assert_eq!(bits.runs(), 3);
+        assert_eq!(bits.popcount(), 1);
+    }
+
+    #[test]
+    fn test_bit_order_lsb_first() {
+        // Stream: 10000000 00000000
+        let bits = RawBits::from_bytes(vec![0x01, 0x00], 1).with_bit_order(BitOrder::LsbFirst);
+
+        assert!(bits.bit(0));
+        assert!(!bits.bit(7));
+        assert_eq!(bits.runs(), 2);
+        assert_eq!(bits.popcount(), 1);
+        assert_eq!(bits.bits().filter(|&b| b).count(), 1);
+    }
 }
@@ -0,0 +1,87 @@
+//! Fixed-size output chunking.
+//!
+//! Extraction output is sized by the frame, so the pool fills in steps
+//! that change with the camera resolution. [`Chunker`] buffers output
+//! and re-emits it in chunks of a fixed size, independent of the frame.
+
+use super::RawBits;
+
+/// Re-emits extraction outputs as chunks of exactly `size` bytes.
+///
+/// Each chunk's source frame count is the number of outputs that
+/// contributed since the previous chunk (at least 1). Outputs larger
+/// than the chunk size leave whole chunks buffered; they are returned
+/// by later calls to [`push`](Self::push) or [`pop`](Self::pop).
+pub struct Chunker {
+    size: usize,
+    pending: Vec<u8>,
+    /// Outputs buffered since the last chunk was emitted.
+    pending_frames: u64,
+}
+
+impl Chunker {
+    /// Creates a chunker emitting `size`-byte chunks (at least 1).
+    pub fn new(size: usize) -> Self {
+        let size = size.max(1);
+        Self {
+            size,
+            pending: Vec::with_capacity(size),
+            pending_frames: 0,
+        }
+    }
+
+    /// Returns the chunk size in bytes.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the number of bytes buffered towards the next chunks.
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Buffers an output, returning a chunk once `size` bytes are buffered.
+    pub fn push(&mut self, bits: RawBits) -> Option<RawBits> {
+        self.pending.extend_from_slice(bits.data());
+        self.pending_frames += bits.source_frames();
+        self.pop()
+    }
+
+    /// Returns the next complete chunk, if one is buffered.
+    pub fn pop(&mut self) -> Option<RawBits> {
+        if self.pending.len() < self.size {
+            return None;
+        }
+        let rest = self.pending.split_off(self.size);
+        let chunk = std::mem::replace(&mut self.pending, rest);
+        let source_frames = std::mem::take(&mut self.pending_frames).max(1);
+        Some(RawBits::from_bytes(chunk, source_frames))
+    }
+
+    /// Discards any buffered bytes.
+    pub fn reset(&mut self) {
+        self.pending.clear();
+        self.pending_frames = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_large_output_leaves_chunks_buffered() {
+        let mut chunker = Chunker::new(4);
+
+        let chunk = chunker
+            .push(RawBits::from_bytes((0..10).collect(), 1))
+            .unwrap();
+        assert_eq!(chunk.data(), &[0, 1, 2, 3]);
+        assert_eq!(chunk.source_frames(), 1);
+        assert_eq!(chunker.pending(), 6);
+
+        assert_eq!(chunker.pop().unwrap().data(), &[4, 5, 6, 7]);
+        assert!(chunker.pop().is_none());
+        assert_eq!(chunker.pending(), 2);
+    }
+}
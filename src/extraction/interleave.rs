@@ -0,0 +1,100 @@
+//! Cross-frame byte interleaving.
+//!
+//! Artifacts tied to sensor position, such as rolling-shutter banding,
+//! land at the same offsets in every frame's output. Transposing the
+//! outputs of several consecutive frames places bytes from the same
+//! offset next to each other, so such structure no longer repeats with
+//! the frame period.
+
+use super::RawBits;
+
+/// Buffers `depth` extraction outputs and emits them transposed.
+///
+/// For inputs `A`, `B`, ... the output is `A[0] B[0] ... A[1] B[1] ...`.
+/// Inputs of unequal length are transposed as far as each reaches;
+/// the longer tails follow in order.
+pub struct Interleaver {
+    depth: usize,
+    pending: Vec<RawBits>,
+}
+
+impl Interleaver {
+    /// Creates an interleaver over `depth` outputs (at least 1).
+    ///
+    /// A depth of 1 passes outputs through unchanged.
+    pub fn new(depth: usize) -> Self {
+        let depth = depth.max(1);
+        Self {
+            depth,
+            pending: Vec::with_capacity(depth),
+        }
+    }
+
+    /// Returns the interleaving depth.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the number of outputs buffered towards the next block.
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Buffers an output, returning the interleaved block once `depth`
+    /// outputs have been collected.
+    ///
+    /// The block's source frame count is the sum over its outputs.
+    pub fn push(&mut self, bits: RawBits) -> Option<RawBits> {
+        self.pending.push(bits);
+        if self.pending.len() < self.depth {
+            return None;
+        }
+
+        let longest = self.pending.iter().map(RawBits::len).max().unwrap_or(0);
+        let total = self.pending.iter().map(RawBits::len).sum();
+        let mut data = Vec::with_capacity(total);
+        for i in 0..longest {
+            data.extend(self.pending.iter().filter_map(|b| b.data().get(i)));
+        }
+
+        let source_frames = self.pending.iter().map(RawBits::source_frames).sum();
+        self.pending.clear();
+        Some(RawBits::from_bytes(data, source_frames))
+    }
+
+    /// Discards any buffered outputs.
+    pub fn reset(&mut self) {
+        self.pending.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_depth_two_transposes() {
+        let mut interleaver = Interleaver::new(2);
+
+        assert!(interleaver
+            .push(RawBits::from_bytes(vec![1, 2, 3, 4], 1))
+            .is_none());
+        assert_eq!(interleaver.pending(), 1);
+
+        let block = interleaver
+            .push(RawBits::from_bytes(vec![11, 12, 13, 14], 1))
+            .unwrap();
+        assert_eq!(block.data(), &[1, 11, 2, 12, 3, 13, 4, 14]);
+        assert_eq!(block.source_frames(), 2);
+        assert_eq!(interleaver.pending(), 0);
+    }
+
+    #[test]
+    fn test_unequal_lengths_keep_all_bytes() {
+        let mut interleaver = Interleaver::new(2);
+
+        interleaver.push(RawBits::from_bytes(vec![1, 2, 3], 1));
+        let block = interleaver.push(RawBits::from_bytes(vec![11], 1)).unwrap();
+        assert_eq!(block.data(), &[1, 11, 2, 3]);
+    }
+}
@@ -4,15 +4,56 @@
 //! suitable for entropy conditioning. It applies temporal and spatial
 //! transformations to reduce structure and correlations in the raw data.
 
+mod bias;
 mod bitstream;
+mod channel;
+mod chunk;
+mod interleave;
 mod spatial;
 mod temporal;
+mod timing;
 
-pub use bitstream::RawBits;
+pub use bias::BiasCorrector;
+pub use bitstream::{BitOrder, RawBits};
+pub use channel::CrossChannelMixer;
+pub use chunk::Chunker;
+pub use interleave::Interleaver;
 pub use spatial::SpatialMixer;
-pub use temporal::TemporalDifferencer;
+pub use temporal::{DiffMode, TemporalDifferencer};
+pub use timing::TimingExtractor;
 
 use crate::capture::Frame;
+use thiserror::Error;
+
+/// Reasons a frame produced no output, other than needing more frames.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ExtractionError {
+    /// The frame's shape differs from the previous frame's, so the two
+    /// cannot be differenced. The frame primes a fresh differencer.
+    #[error(
+        "frame shape changed from {}x{}x{} to {}x{}x{}",
+        previous.0, previous.1, previous.2, current.0, current.1, current.2
+    )]
+    DimensionMismatch {
+        /// Width, height and channels of the previous frame.
+        previous: (u32, u32, usize),
+        /// Width, height and channels of this frame.
+        current: (u32, u32, usize),
+    },
+    /// Every stage ran but produced no bytes (e.g. an empty frame).
+    #[error("extraction produced no bytes")]
+    EmptyOutput,
+    /// The camera keeps delivering the same frame.
+    #[error("{identical} consecutive identical frames, camera appears frozen")]
+    FrozenFrames {
+        /// Consecutive byte-identical frames, including this one.
+        identical: u32,
+    },
+}
+
+/// Callback invoked with the sequence number of the frame at which a
+/// frozen camera was detected.
+pub type FrozenFrameCallback = Box<dyn FnMut(u64) + Send>;
 
 /// Extracts raw bits from a sequence of frames.
 ///
@@ -20,34 +61,453 @@ use crate::capture::Frame;
 /// a decorrelated bitstream from raw camera input.
 pub struct Extractor {
     temporal: TemporalDifferencer,
+    /// Applied only to RGB frames.
+    cross_channel: CrossChannelMixer,
     spatial: SpatialMixer,
+    /// Primed outputs still to be discarded after stream start.
+    warmup_remaining: u32,
+    /// Optional frame-timing jitter stage.
+    timing: Option<TimingExtractor>,
+    /// Pixel resolution in bits applied before differencing, if any.
+    quantize_bits: Option<u8>,
+    /// Optional cross-frame interleaving stage.
+    interleaver: Option<Interleaver>,
+    /// Optional fixed-size output stage.
+    chunker: Option<Chunker>,
+    /// Optional heuristic bias reduction after spatial mixing.
+    bias_corrector: Option<BiasCorrector>,
+    /// Sequence number of the last accepted frame.
+    last_sequence: Option<u64>,
+    /// Sequence discontinuities seen so far.
+    sequence_gaps: u64,
+    /// Emit frame pixels unprocessed (testing only).
+    passthrough: bool,
+    /// Consecutive identical frames that count as frozen (0 disables).
+    frozen_frame_limit: u32,
+    /// Digest of the last frame's pixels, while detection is enabled.
+    last_frame_digest: Option<blake3::Hash>,
+    /// Consecutive frames with that digest.
+    identical_frames: u32,
+    /// Frozen-camera detections so far.
+    frozen_frame_events: u64,
+    /// Called on each frozen-camera detection.
+    on_frozen: Option<FrozenFrameCallback>,
 }
 
 impl Extractor {
+    /// Creates an extractor with default temporal and spatial stages.
     pub fn new() -> Self {
         Self {
             temporal: TemporalDifferencer::new(),
+            cross_channel: CrossChannelMixer::new(),
             spatial: SpatialMixer::new(),
+            warmup_remaining: 0,
+            timing: None,
+            quantize_bits: None,
+            interleaver: None,
+            chunker: None,
+            bias_corrector: None,
+            last_sequence: None,
+            sequence_gaps: 0,
+            passthrough: false,
+            frozen_frame_limit: 0,
+            last_frame_digest: None,
+            identical_frames: 0,
+            frozen_frame_events: 0,
+            on_frozen: None,
+        }
+    }
+
+    /// Creates an extractor that discards its first `warmup_frames` outputs.
+    ///
+    /// Cameras often emit settling artifacts right after the stream
+    /// starts; see `CaptureConfig::warmup_frames`.
+    pub fn with_warmup(warmup_frames: u32) -> Self {
+        Self {
+            warmup_remaining: warmup_frames,
+            ..Self::new()
+        }
+    }
+
+    /// Creates an extractor that emits each frame's pixels unchanged.
+    ///
+    /// **Not for entropy.** Every stage (differencing, mixing,
+    /// quantization, timing, warmup, interleaving, bias correction) is
+    /// bypassed, so the output carries the scene itself, fixed-pattern
+    /// noise included. Meant for debugging: with a known input, the
+    /// conditioning, pool and reseed path can be checked independently
+    /// of extraction. Duplicate frames are still dropped.
+    pub fn passthrough() -> Self {
+        tracing::warn!("Pass-through extraction enabled: output is raw pixels, NOT for entropy");
+        Self {
+            passthrough: true,
+            ..Self::new()
         }
     }
 
+    /// Returns true if this extractor passes frame pixels through unchanged.
+    pub fn is_passthrough(&self) -> bool {
+        self.passthrough
+    }
+
+    /// Adds a timing-jitter stage whose bits are appended to each output.
+    pub fn with_timing(mut self, timing: TimingExtractor) -> Self {
+        self.timing = Some(timing);
+        self
+    }
+
+    /// Encodes temporal differences as `mode`; see [`DiffMode`] for
+    /// the bias tradeoff of keeping the sign of each change.
+    pub fn with_diff_mode(mut self, mode: DiffMode) -> Self {
+        self.temporal = TemporalDifferencer::with_mode(mode);
+        self
+    }
+
+    /// Quantizes pixels to `bits` of resolution (1-8) before differencing.
+    ///
+    /// Dropping low-order bits suppresses sensor banding and other
+    /// fine-grained fixed patterns, but also discards the noise those
+    /// bits carry: fluctuations smaller than the quantization step no
+    /// longer register at all. Coarser quantization yields less, but
+    /// more robust, entropy per pixel; validate with the health tests
+    /// before lowering it far.
+    pub fn with_quantization(mut self, bits: u8) -> Self {
+        self.quantize_bits = Some(bits.clamp(1, 8));
+        self
+    }
+
+    /// Interleaves the outputs of `depth` consecutive frames.
+    ///
+    /// `process` then returns one transposed block every `depth` outputs;
+    /// see [`Interleaver`].
+    pub fn with_interleaving(mut self, depth: usize) -> Self {
+        self.interleaver = Some(Interleaver::new(depth));
+        self
+    }
+
+    /// Emits output in chunks of exactly `bytes` bytes (at least 1).
+    ///
+    /// Output is buffered across frames, so the pool fills in steps
+    /// that do not depend on the frame size. With a target smaller
+    /// than a frame's output, whole chunks stay buffered; drain them
+    /// with [`next_chunk`](Self::next_chunk). See [`Chunker`].
+    pub fn with_output_size(mut self, bytes: usize) -> Self {
+        self.chunker = Some(Chunker::new(bytes));
+        self
+    }
+
+    /// Returns the fixed output chunk size, if set.
+    pub fn output_size(&self) -> Option<usize> {
+        self.chunker.as_ref().map(Chunker::size)
+    }
+
+    /// Returns the bytes buffered towards the next output chunks.
+    ///
+    /// Always 0 unless [`with_output_size`](Self::with_output_size) is set.
+    pub fn pending_output(&self) -> usize {
+        self.chunker.as_ref().map_or(0, Chunker::pending)
+    }
+
+    /// Returns the next buffered output chunk without processing a frame.
+    ///
+    /// Always `None` unless [`with_output_size`](Self::with_output_size)
+    /// is set.
+    pub fn next_chunk(&mut self) -> Option<RawBits> {
+        self.chunker.as_mut()?.pop()
+    }
+
+    /// Replaces the spatial mixing stage, e.g. with
+    /// [`SpatialMixer::with_2d`].
+    ///
+    /// The mixer's frame shape is updated from each frame processed.
+    pub fn with_spatial_mixer(mut self, mixer: SpatialMixer) -> Self {
+        self.spatial = mixer;
+        self
+    }
+
+    /// Applies a [`BiasCorrector`] to the spatially mixed bytes.
+    ///
+    /// Heuristic only; conditioning remains what removes bias.
+    pub fn with_bias_correction(mut self, corrector: BiasCorrector) -> Self {
+        self.bias_corrector = Some(corrector);
+        self
+    }
+
+    /// Rejects frames once `limit` consecutive frames are byte-identical.
+    ///
+    /// A hung driver often keeps returning its last frame. Differencing
+    /// turns that into all zeros, which the health tests only catch
+    /// after the fact. With a limit set, the `limit`-th identical frame
+    /// resets the extractor, counts a
+    /// [frozen-frame event](Self::frozen_frame_events) and invokes the
+    /// [callback](Self::on_frozen); it and every further identical
+    /// frame fail with [`ExtractionError::FrozenFrames`]. A limit below
+    /// 2 disables detection. Each frame is hashed to compare it with
+    /// its predecessor.
+    pub fn with_frozen_frame_limit(mut self, limit: u32) -> Self {
+        self.frozen_frame_limit = if limit < 2 { 0 } else { limit };
+        self
+    }
+
+    /// Registers a callback invoked on each frozen-camera detection,
+    /// e.g. to mark the source unhealthy.
+    ///
+    /// Replaces any previously registered callback.
+    pub fn on_frozen<F>(&mut self, callback: F)
+    where
+        F: FnMut(u64) + Send + 'static,
+    {
+        self.on_frozen = Some(Box::new(callback));
+    }
+
+    /// Returns the number of times a frozen camera was detected.
+    pub fn frozen_frame_events(&self) -> u64 {
+        self.frozen_frame_events
+    }
+
+    /// Returns the quantization resolution in bits, if enabled.
+    pub fn quantize_bits(&self) -> Option<u8> {
+        self.quantize_bits
+    }
+
     /// Processes a frame and returns extracted bits if ready.
     ///
-    /// Returns `None` if more frames are needed (e.g., for differencing).
+    /// A lossy wrapper around [`try_process`](Self::try_process) that
+    /// logs errors and returns `None` for them.
     pub fn process(&mut self, frame: &Frame) -> Option<RawBits> {
+        match self.try_process(frame) {
+            Ok(bits) => bits,
+            Err(e @ ExtractionError::DimensionMismatch { .. }) => {
+                tracing::warn!(sequence = frame.sequence(), "{}", e);
+                None
+            }
+            Err(e @ ExtractionError::FrozenFrames { .. }) => {
+                tracing::debug!(sequence = frame.sequence(), "{}", e);
+                None
+            }
+            Err(e @ ExtractionError::EmptyOutput) => {
+                tracing::trace!(sequence = frame.sequence(), "{}", e);
+                None
+            }
+        }
+    }
+
+    /// Processes a frame, reporting why no bits were produced.
+    ///
+    /// Returns `Ok(None)` if more frames are needed (e.g., for
+    /// differencing or interleaving), the warmup period has not yet
+    /// elapsed, or the frame repeats the previous sequence number.
+    /// Returns an error if the frame's shape differs from the previous
+    /// frame's, or extraction produced no bytes at all. Callers never
+    /// see an empty `RawBits`, which would otherwise pool nothing and
+    /// yield meaningless all-zero statistics.
+    ///
+    /// A jump in sequence numbers means frames were dropped; the
+    /// differencer is reset rather than differencing across the gap.
+    /// A shape change resets it the same way.
+    pub fn try_process(&mut self, frame: &Frame) -> Result<Option<RawBits>, ExtractionError> {
+        if !self.check_sequence(frame.sequence()) {
+            return Ok(None);
+        }
+        self.check_frozen(frame)?;
+
+        if self.passthrough {
+            if frame.pixels().is_empty() {
+                return Err(ExtractionError::EmptyOutput);
+            }
+            return Ok(Some(RawBits::from_bytes(frame.pixels().to_vec(), 1)));
+        }
+
+        if let Some(timing) = &mut self.timing {
+            timing.record(frame);
+        }
+
+        // Quantize pixel values before differencing
+        let quantized;
+        let frame = match self.quantize_bits {
+            Some(bits) if bits < 8 => {
+                quantized = quantize(frame, bits);
+                &quantized
+            }
+            _ => frame,
+        };
+
         // Apply temporal differencing
-        let diff = self.temporal.difference(frame)?;
+        let previous = self.temporal.shape();
+        let resets = self.temporal.dimension_resets();
+        let Some(diff) = self.temporal.difference_buffered(frame) else {
+            if self.temporal.dimension_resets() == resets {
+                return Ok(None);
+            }
+            return Err(ExtractionError::DimensionMismatch {
+                previous: previous.unwrap_or_default(),
+                current: (frame.width(), frame.height(), frame.channels()),
+            });
+        };
+
+        if self.warmup_remaining > 0 {
+            self.warmup_remaining -= 1;
+            tracing::trace!(remaining = self.warmup_remaining, "Discarded warmup frame");
+            // Jitter harvested while the sensor settled is discarded too
+            if self.warmup_remaining == 0 {
+                if let Some(timing) = &mut self.timing {
+                    timing.reset();
+                }
+            }
+            return Ok(None);
+        }
+
+        // Packed signs have no pixel layout, so only the per-pixel
+        // differences go through the mixers
+        let (diff, signs) = diff.split_at(frame.pixels().len().min(diff.len()));
+
+        // Apply spatial mixing, first cancelling structure shared by
+        // the color channels of RGB frames
+        let width = frame.width() as usize;
+        let mut mixed = if frame.channels() == CrossChannelMixer::CHANNELS {
+            self.spatial
+                .set_frame_shape(width, CrossChannelMixer::OUTPUT_BYTES_PER_PIXEL);
+            self.spatial.mix(&self.cross_channel.mix(diff))
+        } else {
+            self.spatial.set_frame_shape(width, frame.channels());
+            self.spatial.mix(diff)
+        };
+        mixed.extend_from_slice(signs);
+
+        if let Some(corrector) = &self.bias_corrector {
+            mixed = corrector.correct(&mixed);
+        }
 
-        // Apply spatial mixing
-        let mixed = self.spatial.mix(&diff);
+        // Append any harvested timing jitter
+        if let Some(timing) = &mut self.timing {
+            mixed.extend(timing.take());
+        }
+
+        if mixed.is_empty() {
+            return Err(ExtractionError::EmptyOutput);
+        }
 
-        Some(RawBits::from_bytes(mixed, frame.sequence()))
+        let bits = RawBits::from_bytes(mixed, 1);
+        let bits = match &mut self.interleaver {
+            Some(interleaver) => interleaver.push(bits),
+            None => Some(bits),
+        };
+        Ok(match (&mut self.chunker, bits) {
+            (Some(chunker), Some(bits)) => chunker.push(bits),
+            (_, bits) => bits,
+        })
+    }
+
+    /// Tracks sequence continuity, returning false for a duplicate frame.
+    fn check_sequence(&mut self, sequence: u64) -> bool {
+        match self.last_sequence {
+            Some(last) if sequence == last => {
+                tracing::debug!(sequence, "Dropped duplicate frame");
+                return false;
+            }
+            Some(last) if sequence != last.wrapping_add(1) => {
+                self.sequence_gaps += 1;
+                self.temporal.reset();
+                if let Some(timing) = &mut self.timing {
+                    timing.reset();
+                }
+                tracing::warn!(
+                    expected = last.wrapping_add(1),
+                    got = sequence,
+                    "Frame sequence gap, differencer reset"
+                );
+            }
+            _ => {}
+        }
+        self.last_sequence = Some(sequence);
+        true
+    }
+
+    /// Counts identical consecutive frames, failing once the camera
+    /// appears frozen.
+    fn check_frozen(&mut self, frame: &Frame) -> Result<(), ExtractionError> {
+        if self.frozen_frame_limit == 0 {
+            return Ok(());
+        }
+
+        let digest = blake3::hash(frame.pixels());
+        if self.last_frame_digest.replace(digest) != Some(digest) {
+            self.identical_frames = 1;
+            return Ok(());
+        }
+        self.identical_frames = self.identical_frames.saturating_add(1);
+        if self.identical_frames < self.frozen_frame_limit {
+            return Ok(());
+        }
+
+        if self.identical_frames == self.frozen_frame_limit {
+            self.reset();
+            self.frozen_frame_events += 1;
+            tracing::error!(
+                sequence = frame.sequence(),
+                identical = self.identical_frames,
+                "Camera frozen: repeated identical frames"
+            );
+            if let Some(callback) = self.on_frozen.as_mut() {
+                callback(frame.sequence());
+            }
+        }
+        Err(ExtractionError::FrozenFrames {
+            identical: self.identical_frames,
+        })
+    }
+
+    /// Returns the number of frame sequence gaps detected.
+    pub fn sequence_gaps(&self) -> u64 {
+        self.sequence_gaps
+    }
+
+    /// Returns the number of differencer resets caused by a change in
+    /// frame shape.
+    pub fn dimension_resets(&self) -> u64 {
+        self.temporal.dimension_resets()
     }
 
     /// Resets internal state (e.g., after quality failure).
     pub fn reset(&mut self) {
+        self.last_sequence = None;
         self.temporal.reset();
+        if let Some(timing) = &mut self.timing {
+            timing.reset();
+        }
+        if let Some(interleaver) = &mut self.interleaver {
+            interleaver.reset();
+        }
+        if let Some(chunker) = &mut self.chunker {
+            chunker.reset();
+        }
+    }
+
+    /// Returns the number of warmup outputs still to be discarded.
+    pub fn warmup_remaining(&self) -> u32 {
+        self.warmup_remaining
     }
+
+    /// Returns the difference energy of the most recent frame pair.
+    ///
+    /// See [`TemporalDifferencer::last_difference_energy`].
+    pub fn last_difference_energy(&self) -> Option<u64> {
+        self.temporal.last_difference_energy()
+    }
+
+    /// Returns true if the temporal differencer holds a previous frame.
+    pub fn is_primed(&self) -> bool {
+        self.temporal.is_primed()
+    }
+}
+
+/// Right-shifts every pixel down to `bits` of resolution.
+fn quantize(frame: &Frame, bits: u8) -> Frame {
+    let shift = 8 - u32::from(bits);
+    let pixels = frame.pixels().iter().map(|&p| p >> shift).collect();
+    Frame::new(pixels, frame.width(), frame.height(), frame.sequence())
+        .with_timestamp(frame.timestamp())
+        .with_wall_clock(frame.wall_clock())
 }
 
 impl Default for Extractor {
@@ -74,4 +534,285 @@ mod tests {
         let bits = extractor.process(&frame2);
         assert!(bits.is_some());
     }
+
+    #[test]
+    fn test_dimension_change_is_reported() {
+        let mut extractor = Extractor::new();
+
+        extractor.process(&Frame::new(vec![100u8; 64], 8, 8, 1));
+        let result = extractor.try_process(&Frame::new(vec![150u8; 256], 16, 16, 2));
+        assert_eq!(
+            result.unwrap_err(),
+            ExtractionError::DimensionMismatch {
+                previous: (8, 8, 1),
+                current: (16, 16, 1),
+            }
+        );
+        assert_eq!(extractor.dimension_resets(), 1);
+
+        // The mismatched frame primed a fresh differencer
+        let bits = extractor
+            .try_process(&Frame::new(vec![120u8; 256], 16, 16, 3))
+            .unwrap()
+            .unwrap();
+        assert_eq!(bits.len(), 256);
+    }
+
+    #[test]
+    fn test_passthrough_emits_pixels() {
+        let mut extractor = Extractor::passthrough().with_interleaving(2);
+        assert!(extractor.is_passthrough());
+
+        let frame = Frame::new((0..64).collect(), 8, 8, 1);
+        let bits = extractor.process(&frame).unwrap();
+        assert_eq!(bits.data(), frame.pixels());
+        assert_eq!(bits.source_frames(), 1);
+
+        // No priming: the very next frame is emitted as well
+        let frame = Frame::new(vec![7u8; 64], 8, 8, 2);
+        assert_eq!(extractor.process(&frame).unwrap().data(), frame.pixels());
+    }
+
+    #[test]
+    fn test_output_size_accumulates_frames() {
+        let frame = |sequence: u64| {
+            let pixels = (0..64u64).map(|i| (i * 53 + sequence * 97) as u8).collect();
+            Frame::new(pixels, 8, 8, sequence)
+        };
+        let mut extractor = Extractor::new().with_output_size(256);
+        assert_eq!(extractor.output_size(), Some(256));
+
+        // The first frame primes the differencer; each later one adds 64 bytes
+        for sequence in 1..=4 {
+            assert!(extractor.process(&frame(sequence)).is_none());
+        }
+
+        let chunk = extractor.process(&frame(5)).unwrap();
+        assert_eq!(chunk.len(), 256);
+        assert_eq!(chunk.source_frames(), 4);
+        assert!(extractor.next_chunk().is_none());
+    }
+
+    #[test]
+    fn test_frozen_frames_detected_after_limit() {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        use std::sync::Arc;
+
+        let mut extractor = Extractor::new().with_frozen_frame_limit(3);
+        let detected_at = Arc::new(AtomicU64::new(0));
+        let observer = Arc::clone(&detected_at);
+        extractor.on_frozen(move |sequence| observer.store(sequence, Ordering::SeqCst));
+
+        let pixels: Vec<u8> = (0..64).collect();
+        for sequence in 1..=2 {
+            let frame = Frame::new(pixels.clone(), 8, 8, sequence);
+            assert!(extractor.try_process(&frame).is_ok());
+        }
+        assert_eq!(extractor.frozen_frame_events(), 0);
+
+        let frame = Frame::new(pixels.clone(), 8, 8, 3);
+        assert_eq!(
+            extractor.try_process(&frame).unwrap_err(),
+            ExtractionError::FrozenFrames { identical: 3 }
+        );
+        assert_eq!(extractor.frozen_frame_events(), 1);
+        assert_eq!(detected_at.load(Ordering::SeqCst), 3);
+        assert!(!extractor.is_primed());
+
+        // Still frozen, but the event fires once per episode
+        let frame = Frame::new(pixels, 8, 8, 4);
+        assert!(extractor.try_process(&frame).is_err());
+        assert_eq!(extractor.frozen_frame_events(), 1);
+
+        // A changed frame ends the episode
+        let frame = Frame::new(vec![9u8; 64], 8, 8, 5);
+        assert!(extractor.try_process(&frame).is_ok());
+    }
+
+    #[test]
+    fn test_empty_extraction_is_none() {
+        let mut extractor = Extractor::new();
+
+        // Frames that carry no pixels difference to nothing
+        let frame1 = Frame::new(Vec::new(), 0, 0, 1);
+        let frame2 = Frame::new(Vec::new(), 0, 0, 2);
+
+        assert!(extractor.process(&frame1).is_none());
+        assert!(extractor.process(&frame2).is_none());
+        assert!(extractor.is_primed());
+    }
+
+    #[test]
+    fn test_quantization_hides_small_fluctuations() {
+        // Both frames stay within one 2-bit step (64..128)
+        let frame1 = Frame::new((0..64).map(|i| 64 + (i % 16) as u8).collect(), 8, 8, 1);
+        let frame2 = Frame::new((0..64).map(|i| 64 + (i * 7 % 16) as u8).collect(), 8, 8, 2);
+
+        let mut plain = Extractor::new();
+        plain.process(&frame1);
+        let bits = plain.process(&frame2).unwrap();
+        assert!(bits.data().iter().any(|&b| b != 0));
+
+        let mut quantized = Extractor::new().with_quantization(2);
+        assert_eq!(quantized.quantize_bits(), Some(2));
+        quantized.process(&frame1);
+        let bits = quantized.process(&frame2).unwrap();
+        assert!(bits.data().iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_rgb_frames_cross_channel_mixed() {
+        let mut extractor = Extractor::new();
+
+        let frame1 = Frame::new(vec![100u8; 3 * 64], 8, 8, 1);
+        let frame2 = Frame::new(vec![150u8; 3 * 64], 8, 8, 2);
+        extractor.process(&frame1);
+
+        // Two bytes per RGB pixel
+        let bits = extractor.process(&frame2).unwrap();
+        assert_eq!(bits.len(), 2 * 64);
+    }
+
+    #[test]
+    fn test_rgb_frames_mixed_in_2d() {
+        let mut extractor = Extractor::new().with_spatial_mixer(SpatialMixer::with_2d(4, (0, 1)));
+
+        let pixels: Vec<u8> = (0..3 * 16).map(|i| (i * 53 + 31) as u8).collect();
+        extractor.process(&Frame::new(vec![0u8; 3 * 16], 4, 4, 1));
+        let bits = extractor
+            .process(&Frame::new(pixels.clone(), 4, 4, 2))
+            .unwrap();
+
+        // Rows of the cross-channel output are two bytes per pixel wide
+        let mut expected = SpatialMixer::with_2d(4, (0, 1));
+        expected.set_frame_shape(4, CrossChannelMixer::OUTPUT_BYTES_PER_PIXEL);
+        let cross = CrossChannelMixer::new().mix(&pixels);
+        assert_eq!(bits.data(), expected.mix(&cross));
+        assert_eq!(bits.data()[0], cross[0] ^ cross[8]);
+    }
+
+    #[test]
+    fn test_signed_signs_bypass_mixers() {
+        let mut extractor = Extractor::new()
+            .with_diff_mode(DiffMode::Signed)
+            .with_spatial_mixer(SpatialMixer::with_2d(4, (0, 1)));
+
+        let previous: Vec<u8> = (0..3 * 16).map(|i| (i * 53 + 31) as u8).collect();
+        let current: Vec<u8> = previous.iter().map(|&p| p.wrapping_add(7)).collect();
+        extractor.process(&Frame::new(previous.clone(), 4, 4, 1));
+        let bits = extractor
+            .process(&Frame::new(current.clone(), 4, 4, 2))
+            .unwrap();
+
+        let mut temporal = TemporalDifferencer::with_mode(DiffMode::Signed);
+        temporal.difference(&Frame::new(previous, 4, 4, 1));
+        let diff = temporal.difference(&Frame::new(current, 4, 4, 2)).unwrap();
+        let (pixels, signs) = diff.split_at(3 * 16);
+
+        let mut expected = SpatialMixer::with_2d(4, (0, 1));
+        expected.set_frame_shape(4, CrossChannelMixer::OUTPUT_BYTES_PER_PIXEL);
+        let mut expected = expected.mix(&CrossChannelMixer::new().mix(pixels));
+        expected.extend_from_slice(signs);
+        assert_eq!(bits.data(), expected);
+    }
+
+    #[test]
+    fn test_empty_frames_with_2d_mixer() {
+        let mut extractor = Extractor::new().with_spatial_mixer(SpatialMixer::with_2d(0, (1, 1)));
+
+        assert!(extractor
+            .process(&Frame::new(Vec::new(), 0, 0, 1))
+            .is_none());
+        assert!(extractor
+            .process(&Frame::new(Vec::new(), 0, 0, 2))
+            .is_none());
+    }
+
+    #[test]
+    fn test_timing_bits_appended() {
+        use std::time::{Duration, Instant};
+
+        let start = Instant::now();
+        let mut extractor = Extractor::new().with_timing(TimingExtractor::with_bits_per_sample(8));
+
+        let mut output = None;
+        for (sequence, nanos) in [0u64, 33_000_123, 66_000_870, 99_001_020]
+            .into_iter()
+            .enumerate()
+        {
+            let frame = Frame::new(vec![sequence as u8; 64], 8, 8, sequence as u64)
+                .with_timestamp(start + Duration::from_nanos(nanos));
+            output = extractor.process(&frame);
+        }
+
+        // 64 pixel bytes plus one jitter byte from the last delta
+        assert_eq!(output.unwrap().len(), 65);
+    }
+
+    #[test]
+    fn test_warmup_jitter_discarded() {
+        use std::time::{Duration, Instant};
+
+        let start = Instant::now();
+        let mut extractor =
+            Extractor::with_warmup(2).with_timing(TimingExtractor::with_bits_per_sample(8));
+
+        let mut outputs = Vec::new();
+        for (sequence, nanos) in [0u64, 33_000_123, 66_000_870, 99_001_020, 132_000_457]
+            .into_iter()
+            .enumerate()
+        {
+            let frame = Frame::new(vec![sequence as u8; 64], 8, 8, sequence as u64)
+                .with_timestamp(start + Duration::from_nanos(nanos));
+            outputs.push(extractor.process(&frame));
+        }
+
+        // Priming plus two warmup frames, then no jitter carried over
+        assert!(outputs[..3].iter().all(Option::is_none));
+        assert_eq!(outputs[3].as_ref().unwrap().len(), 64);
+        assert_eq!(outputs[4].as_ref().unwrap().len(), 65);
+    }
+
+    #[test]
+    fn test_interleaving_emits_every_depth_outputs() {
+        let mut extractor = Extractor::new().with_interleaving(2);
+        let frame = |seq: u64| {
+            let pixels = (0..64).map(|i| (i as u64 * 53 + seq * 31) as u8).collect();
+            Frame::new(pixels, 8, 8, seq)
+        };
+
+        assert!(extractor.process(&frame(1)).is_none());
+        assert!(extractor.process(&frame(2)).is_none());
+        let block = extractor.process(&frame(3)).unwrap();
+
+        let mut plain = Extractor::new();
+        plain.process(&frame(1));
+        let a = plain.process(&frame(2)).unwrap();
+        let b = plain.process(&frame(3)).unwrap();
+        assert_eq!(block.len(), a.len() + b.len());
+        assert_eq!(
+            &block.data()[..4],
+            &[a.data()[0], b.data()[0], a.data()[1], b.data()[1]]
+        );
+    }
+
+    #[test]
+    fn test_sequence_gap_resets_differencer() {
+        let mut extractor = Extractor::new();
+        let frame = |seq: u64| Frame::new(vec![seq as u8; 64], 8, 8, seq);
+
+        extractor.process(&frame(1));
+        assert!(extractor.process(&frame(2)).is_some());
+
+        // Duplicate is dropped without touching the differencer
+        assert!(extractor.process(&frame(2)).is_none());
+        assert!(extractor.is_primed());
+        assert_eq!(extractor.sequence_gaps(), 0);
+
+        // Frames 3 and 4 were dropped
+        assert!(extractor.process(&frame(5)).is_none());
+        assert_eq!(extractor.sequence_gaps(), 1);
+        assert!(extractor.is_primed());
+        assert!(extractor.process(&frame(6)).is_some());
+    }
 }
@@ -11,29 +11,79 @@
 pub struct SpatialMixer {
     /// Mixing stride (pixels apart to XOR).
     stride: usize,
+    /// Partner offset `(dx, dy)` in pixels, for 2D mixing.
+    offset_2d: Option<(usize, usize)>,
+    /// Frame width in pixels, for 2D mixing.
+    width: usize,
+    /// Bytes per pixel, for 2D mixing.
+    channels: usize,
 }
 
 impl SpatialMixer {
+    /// Creates a mixer with a stride of 1 (adjacent bytes).
     pub fn new() -> Self {
-        Self { stride: 1 }
+        Self::with_stride(1)
     }
 
     /// Creates a mixer with a custom stride.
     pub fn with_stride(stride: usize) -> Self {
         Self {
             stride: stride.max(1),
+            offset_2d: None,
+            width: 0,
+            channels: 1,
         }
     }
 
+    /// Creates a mixer that XORs each pixel with the one `(dx, dy)`
+    /// pixels away in a frame `width` pixels wide.
+    ///
+    /// Unlike a flat stride, the partner wraps within its own row and
+    /// column, so pixels at the right edge are never paired with the
+    /// start of the next row. Pairing across both axes breaks vertical
+    /// structure (edges, banding) that 1D mixing leaves intact. The
+    /// [`Extractor`](super::Extractor) updates the width from each frame
+    /// via [`set_frame_shape`](Self::set_frame_shape).
+    pub fn with_2d(width: usize, offset: (usize, usize)) -> Self {
+        Self {
+            offset_2d: Some(offset),
+            width,
+            ..Self::new()
+        }
+    }
+
+    /// Sets the frame width in pixels and the bytes per pixel.
+    ///
+    /// Only 2D mixing uses the frame shape.
+    pub fn set_frame_shape(&mut self, width: usize, channels: usize) {
+        self.width = width;
+        self.channels = channels.max(1);
+    }
+
+    /// Returns the 2D partner offset, if mixing in 2D.
+    pub fn offset_2d(&self) -> Option<(usize, usize)> {
+        self.offset_2d
+    }
+
     /// Mixes the input data spatially.
     ///
     /// XORs each byte with a byte `stride` positions away,
-    /// wrapping around at boundaries.
+    /// wrapping around at boundaries. In 2D mode, each byte is XORed
+    /// with the same channel of the pixel `(dx, dy)` away, wrapping
+    /// around the frame edges. If `data` is not a whole number of rows
+    /// of the configured width, it falls back to the flat stride.
     pub fn mix(&self, data: &[u8]) -> Vec<u8> {
         if data.is_empty() {
             return Vec::new();
         }
 
+        let row_len = self.width * self.channels;
+        if let Some((dx, dy)) = self.offset_2d {
+            if row_len > 0 && data.len().is_multiple_of(row_len) {
+                return mix_2d(data, row_len, dx * self.channels, dy);
+            }
+        }
+
         let len = data.len();
         let stride = self.stride % len.max(1);
 
@@ -47,6 +97,22 @@ impl SpatialMixer {
     }
 }
 
+/// XORs each byte with the one `dx` bytes along and `dy` rows down,
+/// wrapping within the row and the frame.
+fn mix_2d(data: &[u8], row_len: usize, dx: usize, dy: usize) -> Vec<u8> {
+    let rows = data.len() / row_len;
+    let dx = dx % row_len;
+    let dy = dy % rows;
+
+    data.chunks_exact(row_len)
+        .enumerate()
+        .flat_map(|(y, row)| {
+            let partner = &data[(y + dy) % rows * row_len..][..row_len];
+            (0..row_len).map(move |x| row[x] ^ partner[(x + dx) % row_len])
+        })
+        .collect()
+}
+
 impl Default for SpatialMixer {
     fn default() -> Self {
         Self::new()
@@ -93,4 +159,34 @@ mod tests {
         // Should produce non-zero output for varied input
         assert!(result.iter().any(|&v| v != 0));
     }
+
+    #[test]
+    fn test_2d_differs_from_1d() {
+        // A 4x4 frame
+        let data: Vec<u8> = (0..16).map(|i| i * 17).collect();
+        let flat = SpatialMixer::new().mix(&data);
+
+        // Horizontal neighbours: differs only at the row ends, where
+        // the flat stride runs on into the next row
+        let horizontal = SpatialMixer::with_2d(4, (1, 0)).mix(&data);
+        for (i, (&h, &f)) in horizontal.iter().zip(&flat).enumerate() {
+            assert_eq!(h == f, i % 4 != 3, "byte {i}");
+        }
+        assert_eq!(horizontal[3], data[3] ^ data[0]);
+
+        // Vertical neighbours: each pixel pairs with the one below
+        let vertical = SpatialMixer::with_2d(4, (0, 1)).mix(&data);
+        assert_ne!(vertical, flat);
+        assert_eq!(vertical[1], data[1] ^ data[5]);
+        assert_eq!(vertical[13], data[13] ^ data[1]);
+    }
+
+    #[test]
+    fn test_2d_zero_width_falls_back() {
+        let data: Vec<u8> = (0..16).map(|i| i * 17).collect();
+        let mixer = SpatialMixer::with_2d(0, (1, 1));
+
+        assert!(mixer.mix(&[]).is_empty());
+        assert_eq!(mixer.mix(&data), SpatialMixer::new().mix(&data));
+    }
 }
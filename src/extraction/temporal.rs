@@ -6,49 +6,229 @@
 
 use crate::capture::Frame;
 
+/// How the change in each pixel is encoded.
+///
+/// [`Absolute`](Self::Absolute) discards the direction of the change,
+/// one bit per pixel that may carry entropy. The other modes keep it,
+/// at a cost: scenes that brighten or darken steadily make the sign
+/// predictable, which can reintroduce the DC bias that differencing
+/// otherwise removes. Validate with the health tests before relying
+/// on the extra bits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DiffMode {
+    /// `|current - previous|`; darkening and brightening look alike.
+    #[default]
+    Absolute,
+    /// `current - previous` modulo 256, so small darkening changes map
+    /// to values near 255.
+    Wrapping,
+    /// Absolute differences followed by the signs, packed eight pixels
+    /// per byte (most significant bit first, set for darkening).
+    Signed,
+}
+
 /// Computes differences between consecutive frames.
 ///
 /// This reduces static patterns (dead pixels, fixed noise) and
 /// emphasizes temporal changes in the optical signal.
+///
+/// Only the previous pixel buffer is retained, and both it and the
+/// output buffer are reused across calls, so steady-state differencing
+/// of same-sized frames does not allocate.
+///
+/// A frame whose width, height or channel count differs from the
+/// previous frame's cannot be differenced pixel by pixel; it resets the
+/// differencer and primes it afresh instead.
 pub struct TemporalDifferencer {
-    /// Previous frame for differencing.
-    previous: Option<Frame>,
+    /// Pixels of the previous frame.
+    previous: Vec<u8>,
+    /// Width, height and channels of the previous frame.
+    shape: Option<(u32, u32, usize)>,
+    /// Resets caused by a change in frame shape.
+    dimension_resets: u64,
+    /// Whether `previous` holds a frame.
+    primed: bool,
+    /// Reusable output buffer.
+    buffer: Vec<u8>,
+    /// Sum of absolute differences of the most recent diff.
+    last_energy: Option<u64>,
+    /// Encoding of each pixel's change.
+    mode: DiffMode,
 }
 
 impl TemporalDifferencer {
+    /// Creates an unprimed differencer.
     pub fn new() -> Self {
-        Self { previous: None }
+        Self {
+            previous: Vec::new(),
+            shape: None,
+            dimension_resets: 0,
+            primed: false,
+            buffer: Vec::new(),
+            last_energy: None,
+            mode: DiffMode::Absolute,
+        }
+    }
+
+    /// Creates an unprimed differencer encoding changes as `mode`.
+    pub fn with_mode(mode: DiffMode) -> Self {
+        Self {
+            mode,
+            ..Self::new()
+        }
+    }
+
+    /// Returns how pixel changes are encoded.
+    pub fn mode(&self) -> DiffMode {
+        self.mode
     }
 
-    /// Computes the absolute difference with the previous frame.
+    /// Computes the difference with the previous frame, encoded
+    /// according to the [`DiffMode`].
     ///
-    /// Returns `None` on the first frame (no previous to compare).
+    /// Returns `None` on the first frame (no previous to compare) and
+    /// on a change in frame shape. Allocates a new vector; see
+    /// [`difference_buffered`](Self::difference_buffered) to avoid that.
     pub fn difference(&mut self, current: &Frame) -> Option<Vec<u8>> {
-        let result = self.previous.as_ref().map(|prev| {
-            // Compute absolute difference pixel by pixel
-            current
-                .pixels()
-                .iter()
-                .zip(prev.pixels().iter())
-                .map(|(&c, &p)| c.abs_diff(p))
-                .collect()
-        });
+        self.difference_buffered(current).map(<[u8]>::to_vec)
+    }
+
+    /// Computes the difference into the internal buffer and borrows it.
+    ///
+    /// The slice is valid until the next call. Returns `None` on the
+    /// first frame and on a change in frame shape.
+    pub fn difference_buffered(&mut self, current: &Frame) -> Option<&[u8]> {
+        let shape = (current.width(), current.height(), current.channels());
+        if self.primed && self.shape != Some(shape) {
+            tracing::debug!(
+                previous = ?self.shape,
+                current = ?shape,
+                "Frame shape changed, differencer reset"
+            );
+            self.reset();
+            self.dimension_resets += 1;
+        }
+        self.shape = Some(shape);
+
+        let primed = self.primed;
+        if primed {
+            let (current, previous) = (current.pixels(), &self.previous);
+            self.buffer.clear();
+            match self.mode {
+                DiffMode::Absolute => abs_diff_into(current, previous, &mut self.buffer),
+                DiffMode::Wrapping => self.buffer.extend(
+                    current
+                        .iter()
+                        .zip(previous)
+                        .map(|(&c, &p)| c.wrapping_sub(p)),
+                ),
+                DiffMode::Signed => {
+                    abs_diff_into(current, previous, &mut self.buffer);
+                    pack_signs(current, previous, &mut self.buffer);
+                }
+            }
+
+            let energy = match self.mode {
+                DiffMode::Wrapping => current
+                    .iter()
+                    .zip(previous)
+                    .map(|(&c, &p)| u64::from(c.abs_diff(p)))
+                    .sum(),
+                _ => {
+                    let len = current.len().min(previous.len());
+                    self.buffer[..len].iter().map(|&d| u64::from(d)).sum()
+                }
+            };
+            self.last_energy = Some(energy);
+        }
 
         // Store current as previous for next call
-        self.previous = Some(current.clone());
+        self.previous.clear();
+        self.previous.extend_from_slice(current.pixels());
+        self.primed = true;
 
-        result
+        primed.then_some(self.buffer.as_slice())
     }
 
     /// Resets the differencer state.
+    ///
+    /// Buffers keep their capacity for reuse.
     pub fn reset(&mut self) {
-        self.previous = None;
+        self.previous.clear();
+        self.shape = None;
+        self.primed = false;
+        self.last_energy = None;
+    }
+
+    /// Returns the sum of absolute differences from the most recent diff.
+    ///
+    /// A measure of scene motion: static scenes difference to near
+    /// zero and carry little entropy. `None` until a diff is computed.
+    pub fn last_difference_energy(&self) -> Option<u64> {
+        self.last_energy
     }
 
     /// Returns true if ready to produce output.
     pub fn is_primed(&self) -> bool {
-        self.previous.is_some()
+        self.primed
     }
+
+    /// Returns the width, height and channels of the previous frame,
+    /// if primed.
+    pub fn shape(&self) -> Option<(u32, u32, usize)> {
+        self.shape
+    }
+
+    /// Returns the number of resets caused by a change in frame shape.
+    pub fn dimension_resets(&self) -> u64 {
+        self.dimension_resets
+    }
+}
+
+/// Appends `|a[i] - b[i]|` for the common length of `a` and `b`.
+#[cfg(not(feature = "simd"))]
+fn abs_diff_into(a: &[u8], b: &[u8], out: &mut Vec<u8>) {
+    abs_diff_scalar(a, b, out);
+}
+
+/// Appends `|a[i] - b[i]|` for the common length of `a` and `b`,
+/// sixteen lanes at a time.
+#[cfg(feature = "simd")]
+fn abs_diff_into(a: &[u8], b: &[u8], out: &mut Vec<u8>) {
+    use wide::u8x16;
+
+    let len = a.len().min(b.len());
+    let (a, b) = (&a[..len], &b[..len]);
+    out.reserve(len);
+
+    let mut a_chunks = a.chunks_exact(16);
+    let mut b_chunks = b.chunks_exact(16);
+    for (ca, cb) in a_chunks.by_ref().zip(b_chunks.by_ref()) {
+        let va = u8x16::new(ca.try_into().expect("16-byte chunk"));
+        let vb = u8x16::new(cb.try_into().expect("16-byte chunk"));
+        // max - min never wraps, so it equals the absolute difference
+        out.extend_from_slice(&(va.max(vb) - va.min(vb)).to_array());
+    }
+
+    abs_diff_scalar(a_chunks.remainder(), b_chunks.remainder(), out);
+}
+
+/// Appends `|a[i] - b[i]|` pixel by pixel.
+fn abs_diff_scalar(a: &[u8], b: &[u8], out: &mut Vec<u8>) {
+    out.extend(a.iter().zip(b).map(|(&c, &p)| c.abs_diff(p)));
+}
+
+/// Appends one bit per pixel, set where `a[i] < b[i]`, packed most
+/// significant bit first. A partial final byte is zero-padded.
+fn pack_signs(a: &[u8], b: &[u8], out: &mut Vec<u8>) {
+    out.extend(a.chunks(8).zip(b.chunks(8)).map(|(a, b)| {
+        a.iter()
+            .zip(b)
+            .enumerate()
+            .fold(0u8, |byte, (i, (&c, &p))| {
+                byte | (u8::from(c < p) << (7 - i))
+            })
+    }));
 }
 
 impl Default for TemporalDifferencer {
@@ -84,6 +264,62 @@ mod tests {
         assert!(result.iter().all(|&v| v == 50));
     }
 
+    #[test]
+    fn test_dimension_change_resets() {
+        let mut diff = TemporalDifferencer::new();
+
+        diff.difference(&Frame::new(vec![100u8; 64], 8, 8, 1));
+        assert!(diff
+            .difference(&Frame::new(vec![150u8; 256], 16, 16, 2))
+            .is_none());
+        assert_eq!(diff.dimension_resets(), 1);
+        assert_eq!(diff.shape(), Some((16, 16, 1)));
+
+        // The next same-sized frame differences against the 16x16 frame only
+        let result = diff
+            .difference(&Frame::new(vec![120u8; 256], 16, 16, 3))
+            .unwrap();
+        assert_eq!(result.len(), 256);
+        assert!(result.iter().all(|&v| v == 30));
+        assert_eq!(diff.dimension_resets(), 1);
+    }
+
+    #[test]
+    fn test_wrapping_keeps_direction_of_darkening() {
+        let bright = Frame::new(vec![150u8; 64], 8, 8, 1);
+        let dark = Frame::new(vec![100u8; 64], 8, 8, 2);
+
+        let mut absolute = TemporalDifferencer::new();
+        absolute.difference(&bright);
+        let absolute = absolute.difference(&dark).unwrap();
+
+        let mut wrapping = TemporalDifferencer::with_mode(DiffMode::Wrapping);
+        wrapping.difference(&bright);
+        let darkening = wrapping.difference(&dark).unwrap();
+        // 100 - 150 wraps to 206
+        assert!(darkening.iter().all(|&v| v == 206));
+        assert_ne!(darkening, absolute);
+        assert_eq!(wrapping.last_difference_energy(), Some(50 * 64));
+
+        // Brightening is identical in both modes
+        let brightening = wrapping.difference(&bright).unwrap();
+        assert!(brightening.iter().all(|&v| v == 50));
+    }
+
+    #[test]
+    fn test_signed_appends_packed_signs() {
+        let mut diff = TemporalDifferencer::with_mode(DiffMode::Signed);
+        let first = Frame::new(vec![100u8; 10], 10, 1, 1);
+        let second = Frame::new(vec![90, 110, 90, 110, 90, 110, 90, 110, 90, 110], 10, 1, 2);
+
+        diff.difference(&first);
+        let result = diff.difference(&second).unwrap();
+
+        assert_eq!(&result[..10], &[10u8; 10]);
+        assert_eq!(&result[10..], &[0b1010_1010, 0b1000_0000]);
+        assert_eq!(diff.last_difference_energy(), Some(100));
+    }
+
     #[test]
     fn test_identical_frames_zero_difference() {
         let mut diff = TemporalDifferencer::new();
@@ -98,6 +334,63 @@ mod tests {
         assert!(result.iter().all(|&v| v == 0));
     }
 
+    #[test]
+    fn test_difference_energy() {
+        let mut diff = TemporalDifferencer::new();
+        assert_eq!(diff.last_difference_energy(), None);
+
+        let frame = Frame::new(vec![100u8; 64], 8, 8, 1);
+        diff.difference(&frame);
+        diff.difference(&frame);
+        assert_eq!(diff.last_difference_energy(), Some(0));
+
+        diff.difference(&Frame::new(vec![150u8; 64], 8, 8, 3));
+        assert_eq!(diff.last_difference_energy(), Some(50 * 64));
+    }
+
+    #[test]
+    fn test_buffers_reused_across_frames() {
+        let mut diff = TemporalDifferencer::new();
+
+        let frames: Vec<Frame> = (0..4u8)
+            .map(|s| Frame::new((0..64).map(|i| i * s).collect(), 8, 8, s as u64))
+            .collect();
+
+        diff.difference(&frames[0]);
+        let first = diff.difference_buffered(&frames[1]).unwrap().as_ptr();
+        let previous = diff.previous.as_ptr();
+
+        for frame in &frames[2..] {
+            let result = diff.difference_buffered(frame).unwrap();
+            assert_eq!(result.as_ptr(), first);
+            // |i*s - i*(s-1)| = i
+            assert!(result.iter().enumerate().all(|(i, &v)| v == i as u8));
+        }
+        assert_eq!(diff.previous.as_ptr(), previous);
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    fn test_simd_matches_scalar() {
+        use rand_chacha::ChaCha8Rng;
+        use rand_core::{RngCore, SeedableRng};
+
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        // Lengths around the 16-byte lane width, plus a 4K-ish row
+        for len in [0, 1, 15, 16, 17, 255, 3840 + 7] {
+            let mut a = vec![0u8; len];
+            let mut b = vec![0u8; len];
+            rng.fill_bytes(&mut a);
+            rng.fill_bytes(&mut b);
+
+            let mut simd = Vec::new();
+            let mut scalar = Vec::new();
+            abs_diff_into(&a, &b, &mut simd);
+            abs_diff_scalar(&a, &b, &mut scalar);
+            assert_eq!(simd, scalar, "length {}", len);
+        }
+    }
+
     #[test]
     fn test_reset_requires_new_prime() {
         let mut diff = TemporalDifferencer::new();
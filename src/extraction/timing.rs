@@ -0,0 +1,141 @@
+//! Frame arrival jitter as a supplementary entropy source.
+//!
+//! Inter-frame arrival times wobble with OS scheduling and sensor
+//! timing, independently of pixel content. The low bits of the
+//! nanosecond deltas carry that jitter.
+
+use crate::capture::Frame;
+use std::time::Instant;
+
+/// Default number of low bits taken from each timestamp delta.
+const DEFAULT_BITS_PER_SAMPLE: u32 = 4;
+
+/// Harvests the low bits of inter-frame timestamp deltas.
+///
+/// Bits are packed LSB-first into bytes and drained with
+/// [`take`](Self::take). A delta identical to the previous one is
+/// skipped, since it indicates a clock too coarse to show jitter.
+pub struct TimingExtractor {
+    /// Timestamp of the previous frame.
+    previous: Option<Instant>,
+    /// Previous delta in nanoseconds.
+    last_delta: Option<u128>,
+    /// Low bits kept per delta (1-8).
+    bits_per_sample: u32,
+    /// Bits not yet forming a whole byte.
+    accumulator: u16,
+    accumulated_bits: u32,
+    /// Completed bytes awaiting [`take`](Self::take).
+    output: Vec<u8>,
+}
+
+impl TimingExtractor {
+    /// Creates an extractor keeping 4 bits per delta.
+    pub fn new() -> Self {
+        Self::with_bits_per_sample(DEFAULT_BITS_PER_SAMPLE)
+    }
+
+    /// Creates an extractor keeping `bits` low bits per delta (clamped to 1-8).
+    pub fn with_bits_per_sample(bits: u32) -> Self {
+        Self {
+            previous: None,
+            last_delta: None,
+            bits_per_sample: bits.clamp(1, 8),
+            accumulator: 0,
+            accumulated_bits: 0,
+            output: Vec::new(),
+        }
+    }
+
+    /// Records a frame's timestamp, harvesting jitter from the delta.
+    pub fn record(&mut self, frame: &Frame) {
+        let timestamp = frame.timestamp();
+        let Some(previous) = self.previous.replace(timestamp) else {
+            return;
+        };
+
+        let delta = timestamp.saturating_duration_since(previous).as_nanos();
+        if self.last_delta.replace(delta) == Some(delta) {
+            tracing::trace!(delta, "Skipped constant timestamp delta");
+            return;
+        }
+
+        let mask = (1u16 << self.bits_per_sample) - 1;
+        self.accumulator |= (delta as u16 & mask) << self.accumulated_bits;
+        self.accumulated_bits += self.bits_per_sample;
+        if self.accumulated_bits >= 8 {
+            self.output.push(self.accumulator as u8);
+            self.accumulator >>= 8;
+            self.accumulated_bits -= 8;
+        }
+    }
+
+    /// Drains the completed jitter bytes.
+    pub fn take(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.output)
+    }
+
+    /// Returns the number of completed bytes awaiting [`take`](Self::take).
+    pub fn available(&self) -> usize {
+        self.output.len()
+    }
+
+    /// Forgets the previous timestamp and discards pending bits.
+    pub fn reset(&mut self) {
+        self.previous = None;
+        self.last_delta = None;
+        self.accumulator = 0;
+        self.accumulated_bits = 0;
+        self.output.clear();
+    }
+}
+
+impl Default for TimingExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn frame_at(start: Instant, nanos: u64, sequence: u64) -> Frame {
+        Frame::new(vec![0u8; 64], 8, 8, sequence)
+            .with_timestamp(start + Duration::from_nanos(nanos))
+    }
+
+    #[test]
+    fn test_varying_deltas_produce_output() {
+        let start = Instant::now();
+        let mut timing = TimingExtractor::new();
+
+        let mut nanos = 0u64;
+        for sequence in 0..33 {
+            // ~30 fps with pseudo-random jitter
+            nanos += 33_333_333 + (sequence * 7919 % 1013);
+            timing.record(&frame_at(start, nanos, sequence));
+        }
+
+        // 32 deltas at 4 bits each
+        let bytes = timing.take();
+        assert_eq!(bytes.len(), 16);
+        assert!(bytes.iter().any(|&b| b != bytes[0]));
+        assert_eq!(timing.available(), 0);
+    }
+
+    #[test]
+    fn test_constant_deltas_skipped() {
+        let start = Instant::now();
+        let mut timing = TimingExtractor::new();
+
+        for sequence in 0..20 {
+            timing.record(&frame_at(start, sequence * 1_000_000, sequence));
+        }
+
+        // Only the first delta has nothing to compare against
+        assert_eq!(timing.available(), 0);
+        assert_eq!(timing.accumulated_bits, 4);
+    }
+}
@@ -65,9 +65,11 @@
 
 pub mod analysis;
 pub mod capture;
+pub mod clock;
 pub mod conditioning;
 pub mod extraction;
 pub mod metrics;
+pub mod pipeline;
 pub mod reseeding;
 
 // Re-export commonly used types at crate root
@@ -75,6 +77,7 @@ pub use analysis::{HealthMetrics, HealthMonitor, QualityThresholds};
 pub use capture::{Camera, CaptureConfig, Frame, MockCamera};
 pub use conditioning::{Conditioner, ConditionedSeed, EntropyPool, HashAlgorithm};
 pub use extraction::{Extractor, RawBits};
+pub use pipeline::{quick_seed, FrameOutcome, Pipeline, PipelineBuilder};
 pub use reseeding::ReseedableRng;
 
 /// Library version.
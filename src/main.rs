@@ -3,19 +3,18 @@
 //! Command-line interface for the optical entropy generation system.
 //! Captures frames from a camera, extracts entropy, and reseeds a CSPRNG.
 
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use optical_entropy::{
-    analysis::HealthMonitor,
-    capture::{Camera, CaptureConfig, MockCamera},
-    conditioning::EntropyPool,
+    analysis::{HealthMonitor, HealthSummary},
+    capture::{Camera, CameraError, CaptureConfig, ConfigError, FileConfig, MockCamera},
+    conditioning::{Conditioner, EntropyPool, HashAlgorithm, PoolConfig},
     extraction::Extractor,
-    reseeding::ReseedableRng,
+    pipeline::{FrameOutcome, Pipeline},
+    reseeding::{ReseedLog, ReseedableRng},
 };
-#[cfg(feature = "camera")]
-use optical_entropy::capture::FileConfig;
 use rand_core::RngCore;
-use std::path::PathBuf;
-use tracing::{info, warn};
+use std::path::{Path, PathBuf};
+use tracing::{error, info, warn};
 
 #[derive(Parser)]
 #[command(name = "optical-entropy")]
@@ -25,7 +24,7 @@ struct Cli {
     #[command(subcommand)]
     command: Option<Commands>,
 
-    /// Path to configuration file
+    /// Path to configuration file, or `-` to read it from stdin
     #[arg(short, long, value_name = "FILE")]
     config: Option<PathBuf>,
 
@@ -33,13 +32,57 @@ struct Cli {
     #[arg(short, long)]
     device: Option<u32>,
 
-    /// Run continuously until interrupted
+    /// Run continuously until interrupted (overrides config file)
     #[arg(long)]
     continuous: bool,
 
-    /// Number of frames to process (ignored if --continuous)
-    #[arg(short = 'n', long, default_value = "100")]
-    frames: u32,
+    /// Number of frames to process (overrides config file; ignored if
+    /// --continuous)
+    #[arg(short = 'n', long)]
+    frames: Option<u32>,
+
+    /// Analyze and pool entropy but never reseed the CSPRNG
+    #[arg(long, global = true)]
+    dry_run: bool,
+
+    /// Write raw extracted bits (before conditioning) to FILE for
+    /// external statistical testing
+    #[arg(long, value_name = "FILE", global = true)]
+    dump_bits: Option<PathBuf>,
+
+    /// Write every conditioned seed to FILE to verify the output side.
+    /// SECURITY: these are the exact seeds fed to the CSPRNG; anyone who
+    /// reads FILE can follow its reseeds. Use with --dry-run or on test
+    /// systems only
+    #[arg(long, value_name = "FILE", global = true)]
+    dump_output: Option<PathBuf>,
+
+    /// Conditioning hash algorithm
+    #[arg(long, value_enum, default_value = "blake3", global = true)]
+    hash: HashChoice,
+
+    /// Write a JSON summary of the session to FILE on exit
+    #[arg(long, value_name = "FILE", global = true)]
+    summary: Option<PathBuf>,
+}
+
+/// Hash algorithms selectable from the command line.
+#[derive(Clone, Copy, ValueEnum)]
+enum HashChoice {
+    Blake3,
+    Sha256,
+    /// SHAKE256 with 64 bytes of output
+    Shake256,
+}
+
+impl HashChoice {
+    fn algorithm(self) -> HashAlgorithm {
+        match self {
+            HashChoice::Blake3 => HashAlgorithm::Blake3,
+            HashChoice::Sha256 => HashAlgorithm::Sha256,
+            HashChoice::Shake256 => HashAlgorithm::Shake256 { out_len: 64 },
+        }
+    }
 }
 
 #[derive(Subcommand)]
@@ -51,6 +94,42 @@ enum Commands {
         /// Number of frames to process
         #[arg(short = 'n', long, default_value = "20")]
         frames: u32,
+        /// Capture seeded noise instead of the fixed test pattern, which
+        /// never passes health checks
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// Capture one frame and print a per-region noise heatmap
+    AnalyzeFrame {
+        /// Grid columns
+        #[arg(long, default_value = "4")]
+        cols: usize,
+        /// Grid rows
+        #[arg(long, default_value = "4")]
+        rows: usize,
+    },
+    /// Run built-in known-answer checks and exit non-zero on failure
+    SelfTest,
+    /// Print the conditioning known-answer vectors
+    #[command(hide = true)]
+    TestVectors,
+    /// Measure pipeline throughput and per-frame latency
+    Bench {
+        /// How long to run, in seconds
+        #[arg(long, default_value = "10", value_parser = parse_seconds)]
+        seconds: f64,
+        /// Use the mock camera instead of a real device
+        #[arg(long)]
+        mock: bool,
+        /// Print JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print the configuration after merging the file and CLI overrides
+    EffectiveConfig {
+        /// Print JSON instead of TOML
+        #[arg(long)]
+        json: bool,
     },
     /// Generate random bytes to stdout
     Generate {
@@ -60,9 +139,24 @@ enum Commands {
         /// Output as hex instead of raw bytes
         #[arg(long)]
         hex: bool,
+        /// Bytes generated and written at a time, bounding memory use
+        #[arg(long, default_value_t = DEFAULT_BLOCK_SIZE)]
+        block_size: usize,
     },
 }
 
+/// Default block size for `generate`, in bytes.
+const DEFAULT_BLOCK_SIZE: usize = 64 * 1024;
+
+/// Parses a non-negative, finite duration in seconds.
+fn parse_seconds(value: &str) -> Result<f64, String> {
+    let seconds = value.parse::<f64>().map_err(|e| e.to_string())?;
+    if seconds < 0.0 || std::time::Duration::try_from_secs_f64(seconds).is_err() {
+        return Err(format!("{} is not a valid duration", value));
+    }
+    Ok(seconds)
+}
+
 fn main() {
     // Initialize logging
     tracing_subscriber::fmt()
@@ -76,9 +170,22 @@ fn main() {
 
     match cli.command {
         Some(Commands::ListDevices) => list_devices(),
-        Some(Commands::Mock { frames }) => run_mock(frames),
-        Some(Commands::Generate { bytes, hex }) => {
-            generate_random(&cli, bytes, hex);
+        Some(Commands::Mock { frames, seed }) => run_mock(&cli, frames, seed),
+        Some(Commands::AnalyzeFrame { cols, rows }) => analyze_frame(&cli, cols, rows),
+        Some(Commands::SelfTest) => self_test(),
+        Some(Commands::TestVectors) => print_test_vectors(),
+        Some(Commands::Bench {
+            seconds,
+            mock,
+            json,
+        }) => run_bench(&cli, seconds, mock, json),
+        Some(Commands::EffectiveConfig { json }) => print_effective_config(&cli, json),
+        Some(Commands::Generate {
+            bytes,
+            hex,
+            block_size,
+        }) => {
+            generate_random(&cli, bytes, hex, block_size);
         }
         None => run_capture(&cli),
     }
@@ -119,19 +226,19 @@ fn list_devices() {
     }
 }
 
-fn run_mock(frame_count: u32) {
+fn run_mock(cli: &Cli, frame_count: u32, seed: Option<u64>) {
     info!("Optical Entropy Generator v{}", optical_entropy::VERSION);
     info!("Running with mock camera (testing mode)");
 
     let config = CaptureConfig::default();
-    let mut camera = MockCamera::new();
+    let mut camera = seed.map_or_else(MockCamera::new, MockCamera::with_seed);
 
     if let Err(e) = camera.open(&config) {
         eprintln!("Failed to open mock camera: {}", e);
         std::process::exit(1);
     }
 
-    run_pipeline(&mut camera, frame_count, false);
+    run_pipeline(&mut camera, &config, frame_count, false, cli.dry_run, cli);
 }
 
 fn run_capture(#[allow(unused)] cli: &Cli) {
@@ -139,34 +246,19 @@ fn run_capture(#[allow(unused)] cli: &Cli) {
 
     #[cfg(feature = "camera")]
     {
-        use optical_entropy::capture::NokhwaCamera;
-
-        // Load configuration
-        let file_config = cli.config.as_ref().map(|path| {
-            FileConfig::from_file(path).unwrap_or_else(|e| {
-                eprintln!("Failed to load config file: {}", e);
-                std::process::exit(1);
-            })
-        });
+        use optical_entropy::capture::{NokhwaCamera, RetryingCamera};
 
-        let mut capture_config = file_config
-            .as_ref()
-            .map(|c| c.capture.clone())
-            .unwrap_or_default();
+        let config = effective_config(cli);
+        let capture_config = config.capture;
 
-        // CLI overrides
-        if let Some(device_id) = cli.device {
-            capture_config.device_id = device_id;
-        }
-
-        let frame_count = if cli.continuous {
+        let frame_count = if config.output.continuous {
             u32::MAX
         } else {
-            cli.frames
+            config.output.frame_count
         };
 
         info!("Opening camera device {}...", capture_config.device_id);
-        let mut camera = NokhwaCamera::new();
+        let mut camera = RetryingCamera::new(NokhwaCamera::new());
 
         if let Err(e) = camera.open(&capture_config) {
             eprintln!("Failed to open camera: {}", e);
@@ -177,7 +269,14 @@ fn run_capture(#[allow(unused)] cli: &Cli) {
             std::process::exit(1);
         }
 
-        run_pipeline(&mut camera, frame_count, cli.continuous);
+        run_pipeline(
+            &mut camera,
+            &capture_config,
+            frame_count,
+            config.output.continuous,
+            cli.dry_run,
+            cli,
+        );
     }
 
     #[cfg(not(feature = "camera"))]
@@ -191,7 +290,305 @@ fn run_capture(#[allow(unused)] cli: &Cli) {
     }
 }
 
-fn generate_random(#[allow(unused)] cli: &Cli, byte_count: usize, hex_output: bool) {
+fn analyze_frame(#[allow(unused)] cli: &Cli, cols: usize, rows: usize) {
+    #[cfg(feature = "camera")]
+    {
+        use optical_entropy::{
+            analysis::{spatial_heatmap, StatisticalTests},
+            capture::NokhwaCamera,
+        };
+
+        let capture_config = effective_config(cli).capture;
+
+        let mut camera = NokhwaCamera::new();
+        if let Err(e) = camera.open(&capture_config) {
+            eprintln!("Failed to open camera: {}", e);
+            std::process::exit(1);
+        }
+
+        let frame = match camera.capture() {
+            Ok(f) => f,
+            Err(e) => {
+                eprintln!("Frame capture failed: {}", e);
+                std::process::exit(1);
+            }
+        };
+
+        let heatmap = spatial_heatmap(&frame, (cols, rows));
+        println!("Per-region variance ({}x{} grid, higher = noisier):", cols, rows);
+        for row in heatmap.chunks(cols.max(1)) {
+            println!(
+                "  {}",
+                row.iter()
+                    .map(|v| format!("{:>9.1}", v))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            );
+        }
+
+        println!("\nPixel value histogram:");
+        print_histogram(&StatisticalTests::byte_histogram(frame.pixels()));
+    }
+
+    #[cfg(not(feature = "camera"))]
+    {
+        let _ = (cols, rows);
+        eprintln!("Camera support not compiled. Rebuild with:");
+        eprintln!("  cargo build --features camera");
+        std::process::exit(1);
+    }
+}
+
+/// Throughput and latency measured by the `bench` subcommand.
+struct BenchReport {
+    seconds: f64,
+    frames: u64,
+    entropy_bytes: u64,
+    reseeds: u64,
+    latency_p50_us: f64,
+    latency_p99_us: f64,
+}
+
+impl BenchReport {
+    fn per_second(&self, count: u64) -> f64 {
+        count as f64 / self.seconds
+    }
+
+    fn print(&self, json: bool) {
+        if json {
+            let report = serde_json::json!({
+                "seconds": self.seconds,
+                "frames": self.frames,
+                "frames_per_sec": self.per_second(self.frames),
+                "entropy_bytes_per_sec": self.per_second(self.entropy_bytes),
+                "reseeds_per_sec": self.per_second(self.reseeds),
+                "latency_p50_us": self.latency_p50_us,
+                "latency_p99_us": self.latency_p99_us,
+            });
+            println!("{}", report);
+            return;
+        }
+
+        println!("Frames:              {}", self.frames);
+        println!("Frames/sec:          {:.1}", self.per_second(self.frames));
+        println!(
+            "Entropy bytes/sec:   {:.0}",
+            self.per_second(self.entropy_bytes)
+        );
+        println!("Reseeds/sec:         {:.2}", self.per_second(self.reseeds));
+        println!("Latency p50:         {:.0} us", self.latency_p50_us);
+        println!("Latency p99:         {:.0} us", self.latency_p99_us);
+    }
+}
+
+fn run_bench(cli: &Cli, seconds: f64, mock: bool, json: bool) {
+    let duration = std::time::Duration::from_secs_f64(seconds);
+
+    if mock {
+        let mut camera = MockCamera::with_seed(0);
+        if let Err(e) = camera.open(&CaptureConfig::default()) {
+            eprintln!("Failed to open mock camera: {}", e);
+            std::process::exit(1);
+        }
+        bench_pipeline(&mut camera, duration, cli.hash.algorithm()).print(json);
+        return;
+    }
+
+    #[cfg(feature = "camera")]
+    {
+        use optical_entropy::capture::NokhwaCamera;
+
+        let capture_config = effective_config(cli).capture;
+        let mut camera = NokhwaCamera::new();
+        if let Err(e) = camera.open(&capture_config) {
+            eprintln!("Failed to open camera: {}", e);
+            std::process::exit(1);
+        }
+        bench_pipeline(&mut camera, duration, cli.hash.algorithm()).print(json);
+    }
+
+    #[cfg(not(feature = "camera"))]
+    {
+        eprintln!("Camera support not compiled; use 'optical-entropy bench --mock'");
+        std::process::exit(1);
+    }
+}
+
+/// Runs the full pipeline for `duration`, timing `Pipeline::process`.
+fn bench_pipeline<C: Camera>(
+    camera: &mut C,
+    duration: std::time::Duration,
+    algorithm: HashAlgorithm,
+) -> BenchReport {
+    use optical_entropy::analysis::RollingQuantiles;
+    use std::time::Instant;
+
+    const LATENCY_WINDOW: usize = 100_000;
+
+    let mut pipeline = Pipeline::new(
+        Extractor::new(),
+        EntropyPool::new(PoolConfig {
+            algorithm,
+            ..Default::default()
+        }),
+        HealthMonitor::default(),
+        ReseedableRng::from_os_entropy(),
+    );
+    let mut latencies = RollingQuantiles::new(LATENCY_WINDOW);
+    let mut frames = 0u64;
+    let mut reseeds = 0u64;
+
+    let start = Instant::now();
+    while start.elapsed() < duration {
+        let frame = match camera.capture() {
+            Ok(f) => f,
+            Err(e) => {
+                error!("Frame capture failed: {}", e);
+                break;
+            }
+        };
+
+        let began = Instant::now();
+        let outcome = pipeline.process(&frame);
+        latencies.push(began.elapsed().as_secs_f64() * 1e6);
+
+        frames += 1;
+        if let Ok(FrameOutcome::Reseeded { .. }) = outcome {
+            reseeds += 1;
+        }
+    }
+
+    let quantiles = latencies.quantiles();
+    BenchReport {
+        seconds: start.elapsed().as_secs_f64(),
+        frames,
+        entropy_bytes: pipeline.pool().total_bits_added() / 8,
+        reseeds,
+        latency_p50_us: quantiles.map_or(0.0, |q| q.p50),
+        latency_p99_us: quantiles.map_or(0.0, |q| q.p99),
+    }
+}
+
+/// Config path that reads the configuration from stdin.
+const STDIN_CONFIG: &str = "-";
+
+/// Loads the config file at `path`, or from stdin for `-`.
+///
+/// Stdin is read once and kept, so every later load (including a
+/// SIGHUP reload) sees the same configuration.
+fn load_config(path: &Path) -> Result<FileConfig, ConfigError> {
+    static STDIN: std::sync::OnceLock<Result<String, ConfigError>> = std::sync::OnceLock::new();
+
+    if path != Path::new(STDIN_CONFIG) {
+        return FileConfig::from_file(path);
+    }
+    let content = STDIN.get_or_init(|| {
+        let mut content = String::new();
+        std::io::Read::read_to_string(&mut std::io::stdin().lock(), &mut content)
+            .map(|_| content)
+            .map_err(|e| ConfigError::FileReadError(e.to_string()))
+    });
+    FileConfig::from_reader(content.clone()?.as_bytes())
+}
+
+/// Loads the config file and applies command-line overrides, exactly
+/// as a capture run does. Exits if the file cannot be loaded.
+fn effective_config(cli: &Cli) -> FileConfig {
+    let mut config = cli
+        .config
+        .as_ref()
+        .map(|path| {
+            load_config(path).unwrap_or_else(|e| {
+                eprintln!("Failed to load config file: {}", e);
+                std::process::exit(1);
+            })
+        })
+        .unwrap_or_default();
+
+    if let Some(device_id) = cli.device {
+        config.capture.device_id = device_id;
+    }
+    // Flags left unset keep the config file's values
+    if cli.continuous {
+        config.output.continuous = true;
+    }
+    if let Some(frames) = cli.frames {
+        config.output.frame_count = frames;
+    }
+    config
+}
+
+fn print_effective_config(cli: &Cli, json: bool) {
+    let config = effective_config(cli);
+    let output = if json {
+        serde_json::to_string_pretty(&config).map_err(|e| e.to_string())
+    } else {
+        toml::to_string(&config).map_err(|e| e.to_string())
+    };
+
+    match output {
+        Ok(text) => println!("{}", text),
+        Err(e) => {
+            eprintln!("Failed to serialize config: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn self_test() {
+    let mut passed = true;
+    for (name, ok) in [
+        ("CSPRNG", ReseedableRng::self_test()),
+        ("Conditioning", Conditioner::self_test()),
+    ] {
+        let status = if ok { "ok" } else { "FAILED" };
+        println!("{} known-answer test: {}", name, status);
+        passed &= ok;
+    }
+    if !passed {
+        std::process::exit(1);
+    }
+}
+
+fn print_test_vectors() {
+    for vector in Conditioner::test_vectors() {
+        let expected: String = vector
+            .expected
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        println!(
+            "{:?} input={:?} expected={}",
+            vector.algorithm,
+            String::from_utf8_lossy(vector.input),
+            expected
+        );
+    }
+    if !Conditioner::self_test() {
+        eprintln!("Conditioning does not match the vectors above");
+        std::process::exit(1);
+    }
+}
+
+/// Prints a byte histogram as 16 ASCII bars of 16 values each.
+#[cfg(feature = "camera")]
+fn print_histogram(histogram: &[u64; 256]) {
+    const BAR_WIDTH: u64 = 50;
+
+    let buckets: Vec<u64> = histogram.chunks(16).map(|c| c.iter().sum()).collect();
+    let max = buckets.iter().copied().max().unwrap_or(0).max(1);
+    for (i, &count) in buckets.iter().enumerate() {
+        let bar = "#".repeat((count * BAR_WIDTH / max) as usize);
+        println!("  {:3}-{:3} | {:<50} {}", i * 16, i * 16 + 15, bar, count);
+    }
+}
+
+fn generate_random(
+    #[allow(unused)] cli: &Cli,
+    byte_count: usize,
+    hex_output: bool,
+    block_size: usize,
+) {
     // Silently initialize RNG and generate output
     let mut rng = ReseedableRng::from_os_entropy();
 
@@ -199,63 +596,160 @@ fn generate_random(#[allow(unused)] cli: &Cli, byte_count: usize, hex_output: bo
     #[cfg(feature = "camera")]
     if cli.device.is_some() || cli.config.is_some() {
         // Quick reseed from camera
-        let capture_config = cli
+        let file_config = cli
             .config
             .as_ref()
-            .and_then(|p| FileConfig::from_file(p).ok())
-            .map(|c| c.capture)
+            .and_then(|p| load_config(p).ok())
             .unwrap_or_default();
 
         use optical_entropy::capture::NokhwaCamera;
-        let mut camera = NokhwaCamera::new();
-        if camera.open(&capture_config).is_ok() {
-            let mut extractor = Extractor::new();
-            let mut pool = EntropyPool::default();
-            let mut health = HealthMonitor::default();
-
-            // Collect enough entropy
+        use optical_entropy::pipeline::PipelineBuilder;
+        let built = PipelineBuilder::new(NokhwaCamera::new())
+            .with_capture_config(file_config.capture)
+            .with_pool_config(PoolConfig {
+                algorithm: cli.hash.algorithm(),
+                salt: file_config.reseed.salt.into_bytes(),
+                ..Default::default()
+            })
+            .build();
+        if let Ok(mut pipeline) = built {
+            // Collect enough entropy, then generate from the reseeded CSPRNG
             for _ in 0..50 {
-                if let Ok(frame) = camera.capture() {
-                    if let Some(bits) = extractor.process(&frame) {
-                        let metrics = health.analyze(&bits);
-                        if metrics.is_healthy {
-                            pool.add(&bits);
-                        }
+                if let Ok(step) = pipeline.step() {
+                    if let FrameOutcome::Reseeded { .. } = step.outcome {
+                        std::mem::swap(&mut rng, pipeline.rng_mut());
+                        break;
                     }
                 }
-                if pool.is_ready() && health.allow_reseed() {
-                    break;
-                }
             }
+        }
+    }
+
+    // Fill and write one block at a time so memory stays bounded
+    use std::io::Write;
+    let mut stdout = std::io::stdout().lock();
+    let mut block = vec![0u8; block_size.max(1).min(byte_count)];
+    let mut remaining = byte_count;
+    let mut result = Ok(());
+    while remaining > 0 && result.is_ok() {
+        let chunk = &mut block[..remaining.min(block_size.max(1))];
+        rng.fill_bytes(chunk);
+        result = if hex_output {
+            let hex: String = chunk.iter().map(|b| format!("{:02x}", b)).collect();
+            stdout.write_all(hex.as_bytes())
+        } else {
+            stdout.write_all(chunk)
+        };
+        remaining -= chunk.len();
+    }
+    if hex_output && result.is_ok() {
+        result = writeln!(stdout);
+    }
+
+    if let Err(e) = result.and_then(|()| stdout.flush()) {
+        error!("Failed to write output: {}", e);
+        std::process::exit(1);
+    }
+}
+
+/// How often a paused capture loop checks for resume or shutdown.
+const PAUSED_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
 
-            if let Some(seed) = pool.extract() {
-                let _ = rng.reseed(&seed);
+/// Delay before capturing again after a recoverable capture failure.
+const CAPTURE_RETRY_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Consecutive identical frames after which the camera counts as frozen.
+const FROZEN_FRAME_LIMIT: u32 = 5;
+
+fn run_pipeline<C: Camera>(
+    camera: &mut C,
+    config: &CaptureConfig,
+    frame_count: u32,
+    continuous: bool,
+    dry_run: bool,
+    cli: &Cli,
+) {
+    let config_path = cli.config.as_deref();
+    let reseed_config = config_path
+        .and_then(|path| load_config(path).ok())
+        .map(|c| c.reseed)
+        .unwrap_or_default();
+    info!(
+        "CSPRNG requires {} bits of entropy per reseed",
+        reseed_config.min_entropy_bits
+    );
+
+    let mut pipeline = Pipeline::new(
+        Extractor::with_warmup(config.warmup_frames).with_frozen_frame_limit(FROZEN_FRAME_LIMIT),
+        EntropyPool::new(PoolConfig {
+            algorithm: cli.hash.algorithm(),
+            salt: reseed_config.salt.clone().into_bytes(),
+            ..Default::default()
+        }),
+        HealthMonitor::default(),
+        reseed_config.rng(),
+    )
+    .with_reset_on_unhealthy(true)
+    .with_dry_run(dry_run);
+
+    if let Some(path) = &reseed_config.log_path {
+        match ReseedLog::open(path) {
+            Ok(log) => pipeline = pipeline.with_reseed_log(log),
+            Err(e) => {
+                error!("Failed to open reseed log {}: {}", path.display(), e);
+                std::process::exit(1);
             }
         }
     }
 
-    let mut output = vec![0u8; byte_count];
-    rng.fill_bytes(&mut output);
+    if let Some(path) = &cli.dump_bits {
+        info!("Dumping raw extracted bits to {}", path.display());
+        pipeline = pipeline.with_raw_dump(create_dump(path));
+    }
+    if let Some(path) = &cli.dump_output {
+        info!("Dumping conditioned seeds to {}", path.display());
+        if !dry_run {
+            warn!(
+                "{} will hold the seeds used to reseed the CSPRNG; protect or delete it",
+                path.display()
+            );
+        }
+        pipeline = pipeline.with_output_dump(create_dump(path));
+    }
 
-    if hex_output {
-        println!("{}", output.iter().map(|b| format!("{:02x}", b)).collect::<String>());
-    } else {
-        use std::io::Write;
-        std::io::stdout().write_all(&output).unwrap();
+    if dry_run {
+        info!("Dry run: the CSPRNG will not be reseeded");
     }
-}
 
-fn run_pipeline<C: Camera>(camera: &mut C, frame_count: u32, continuous: bool) {
-    let mut extractor = Extractor::new();
-    let mut pool = EntropyPool::default();
-    let mut health = HealthMonitor::default();
-    let mut rng = ReseedableRng::from_os_entropy();
+    if let Some(path) = config_path {
+        reload_health_config(&mut pipeline, path);
+    }
+
+    // Re-read the health section of the config file on SIGHUP
+    let reload = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    #[cfg(unix)]
+    if config_path.is_some() {
+        signal_hook::flag::register(signal_hook::consts::SIGHUP, reload.clone()).ok();
+    }
+
+    // Pause capture on SIGUSR1 and resume on SIGUSR2, keeping the camera
+    let pause_requested = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    let resume_requested = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    #[cfg(unix)]
+    {
+        use signal_hook::consts::{SIGUSR1, SIGUSR2};
+        signal_hook::flag::register(SIGUSR1, pause_requested.clone()).ok();
+        signal_hook::flag::register(SIGUSR2, resume_requested.clone()).ok();
+    }
+    let mut paused = false;
 
     info!("Processing frames...");
 
+    let started = std::time::Instant::now();
     let mut healthy_count = 0u64;
     let mut unhealthy_count = 0u64;
     let mut total_reseeds = 0u64;
+    let mut skipped_reseeds = 0u64;
 
     // Set up Ctrl+C handler for continuous mode
     let running = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(true));
@@ -271,53 +765,85 @@ fn run_pipeline<C: Camera>(camera: &mut C, frame_count: u32, continuous: bool) {
     while (continuous && running.load(std::sync::atomic::Ordering::SeqCst))
         || (!continuous && i < frame_count)
     {
+        if reload.swap(false, std::sync::atomic::Ordering::SeqCst) {
+            if let Some(path) = config_path {
+                reload_health_config(&mut pipeline, path);
+            }
+        }
+
+        if pause_requested.swap(false, std::sync::atomic::Ordering::SeqCst) && !paused {
+            match camera.pause() {
+                Ok(()) => {
+                    paused = true;
+                    info!("Capture paused");
+                }
+                Err(e) => warn!("Failed to pause camera: {}", e),
+            }
+        }
+        if resume_requested.swap(false, std::sync::atomic::Ordering::SeqCst) && paused {
+            match camera.resume(config) {
+                Ok(()) => {
+                    paused = false;
+                    info!("Capture resumed");
+                }
+                Err(e) => warn!("Failed to resume camera: {}", e),
+            }
+        }
+        // Never capture from a paused camera; paused time does not count
+        // towards the frame budget
+        if paused {
+            std::thread::sleep(PAUSED_POLL_INTERVAL);
+            continue;
+        }
+
         let frame = match camera.capture() {
             Ok(f) => f,
+            // A camera that is no longer open cannot recover; stop cleanly
+            Err(e @ (CameraError::Closed | CameraError::NotInitialized)) => {
+                error!("Frame capture failed: {}", e);
+                break;
+            }
             Err(e) => {
-                warn!("Frame capture failed: {}", e);
+                // Skip the frame, counting it towards the frame budget so
+                // a bounded run still ends on a failing device
+                warn!("Frame capture failed, skipping: {}", e);
+                i = i.saturating_add(1);
+                std::thread::sleep(CAPTURE_RETRY_INTERVAL);
                 continue;
             }
         };
 
-        if let Some(bits) = extractor.process(&frame) {
-            let metrics = health.analyze(&bits);
-
-            if metrics.is_healthy {
+        match pipeline.process(&frame) {
+            Ok(FrameOutcome::Pending) => {}
+            Ok(FrameOutcome::Accepted) => healthy_count += 1,
+            Ok(FrameOutcome::Reseeded { entropy_estimate }) => {
                 healthy_count += 1;
-                pool.add(&bits);
-
-                // Attempt reseeding when pool is ready
-                if health.allow_reseed() && pool.is_ready() {
-                    if let Some(seed) = pool.extract() {
-                        match rng.reseed(&seed) {
-                            Ok(()) => {
-                                total_reseeds += 1;
-                                info!(
-                                    "CSPRNG reseeded (#{}, entropy: {} bits)",
-                                    total_reseeds,
-                                    seed.entropy_estimate()
-                                );
-                            }
-                            Err(e) => {
-                                warn!("Reseed failed: {}", e);
-                            }
-                        }
-                    }
-                }
-            } else {
-                unhealthy_count += 1;
-                if let Some(ref violation) = metrics.last_violation {
-                    if unhealthy_count % 100 == 1 {
-                        warn!("Quality violation: {}", violation);
-                    }
-                }
+                total_reseeds += 1;
+                info!(
+                    "CSPRNG reseeded (#{}, entropy: {} bits)",
+                    total_reseeds, entropy_estimate
+                );
+            }
+            Ok(FrameOutcome::WouldReseed { entropy_estimate }) => {
+                healthy_count += 1;
+                skipped_reseeds += 1;
+                info!(
+                    "Dry run: would reseed (#{}, entropy: {} bits)",
+                    skipped_reseeds, entropy_estimate
+                );
+            }
+            // The health monitor logs violations, rate-limited
+            Ok(FrameOutcome::Rejected) => unhealthy_count += 1,
+            Err(e) => {
+                healthy_count += 1;
+                warn!("Reseed failed: {}", e);
             }
         }
 
         i = i.saturating_add(1);
 
         // Periodic status update
-        if i % 1000 == 0 && continuous {
+        if i.is_multiple_of(1000) && continuous {
             info!(
                 "Status: {} frames, {} healthy, {} unhealthy, {} reseeds",
                 i, healthy_count, unhealthy_count, total_reseeds
@@ -333,12 +859,116 @@ fn run_pipeline<C: Camera>(camera: &mut C, frame_count: u32, continuous: bool) {
     );
     info!("Total reseeds: {}", total_reseeds);
 
+    let summary = pipeline.health().summary();
+    info!(
+        "Health: {:?}, {:.1}% of {} samples healthy, last violation: {}",
+        summary.current_state,
+        summary.healthy_fraction * 100.0,
+        summary.uptime_samples,
+        summary.last_violation_kind.unwrap_or("none")
+    );
+    if dry_run {
+        info!("Reseeds that would have occurred: {}", skipped_reseeds);
+    }
+
+    if let Some(path) = &cli.summary {
+        let mut used = effective_config(cli);
+        used.capture = config.clone();
+        let session = SessionSummary {
+            seconds: started.elapsed().as_secs_f64(),
+            healthy_frames: healthy_count,
+            unhealthy_frames: unhealthy_count,
+            reseeds: total_reseeds,
+            skipped_reseeds,
+            dry_run,
+            health: summary,
+            config: used,
+        };
+        write_summary(path, &session.to_json(cli));
+    }
+
     // Generate sample output
     info!("Sample random output:");
     let mut output = [0u8; 32];
-    rng.fill_bytes(&mut output);
+    pipeline.rng_mut().fill_bytes(&mut output);
     println!(
         "{}",
         output.iter().map(|b| format!("{:02x}", b)).collect::<String>()
     );
 }
+
+/// End-of-run record written by `--summary`.
+struct SessionSummary {
+    seconds: f64,
+    healthy_frames: u64,
+    unhealthy_frames: u64,
+    reseeds: u64,
+    /// Reseeds a dry run skipped.
+    skipped_reseeds: u64,
+    dry_run: bool,
+    health: HealthSummary,
+    /// Effective config, with the capture settings actually used.
+    config: FileConfig,
+}
+
+impl SessionSummary {
+    fn to_json(&self, cli: &Cli) -> serde_json::Value {
+        let frames = self.healthy_frames + self.unhealthy_frames;
+        serde_json::json!({
+            "frames_processed": frames,
+            "healthy_frames": self.healthy_frames,
+            "unhealthy_frames": self.unhealthy_frames,
+            "reseeds": self.reseeds,
+            "skipped_reseeds": self.skipped_reseeds,
+            "health_state": format!("{:?}", self.health.current_state),
+            "healthy_fraction": self.health.healthy_fraction,
+            "quality_score": self.health.latest_stats.as_ref().map(|s| s.quality_score()),
+            "seconds": self.seconds,
+            "frames_per_sec": frames as f64 / self.seconds,
+            "hash": format!("{:?}", cli.hash.algorithm()),
+            "dry_run": self.dry_run,
+            "config": self.config,
+        })
+    }
+}
+
+/// Writes the session summary to `path`, exiting if it cannot be written.
+fn write_summary(path: &Path, summary: &serde_json::Value) {
+    let text = serde_json::to_string_pretty(summary).expect("summary serializes");
+    match std::fs::write(path, text + "\n") {
+        Ok(()) => info!("Session summary written to {}", path.display()),
+        Err(e) => {
+            error!("Failed to write session summary {}: {}", path.display(), e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Creates a dump file, exiting if it cannot be created.
+fn create_dump(path: &Path) -> std::io::BufWriter<std::fs::File> {
+    match std::fs::File::create(path) {
+        Ok(file) => std::io::BufWriter::new(file),
+        Err(e) => {
+            error!("Failed to create dump file {}: {}", path.display(), e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Applies the `[health]` section of the config file to a running pipeline.
+///
+/// An unreadable or invalid file leaves the current thresholds in place.
+fn reload_health_config(pipeline: &mut Pipeline, path: &Path) {
+    match load_config(path) {
+        Ok(config) => {
+            let health = pipeline.health_mut();
+            health.set_thresholds(config.health.thresholds());
+            health.set_streak_requirement(u64::from(config.health.min_healthy_streak));
+            health.set_min_sample_size(config.health.min_sample_size);
+            health.set_analysis_window(config.health.analysis_window);
+            health.set_startup_grace(config.health.startup_grace_samples);
+            info!("Health thresholds loaded from {}", path.display());
+        }
+        Err(e) => warn!("Keeping previous health thresholds: {}", e),
+    }
+}
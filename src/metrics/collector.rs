@@ -1,20 +1,28 @@
 //! Metrics collection and registry.
 
-use prometheus::{Gauge, IntCounter, IntGauge, Registry, TextEncoder, Encoder};
+use crate::analysis::{HealthQuantiles, Quantiles};
+use prometheus::{Gauge, GaugeVec, IntCounter, IntGauge, IntGaugeVec, Opts, Registry, TextEncoder, Encoder};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 /// Errors that can occur during metrics operations.
 #[derive(Debug, Error)]
 pub enum MetricsError {
+    /// The Prometheus registry rejected an operation.
     #[error("prometheus error: {0}")]
     Prometheus(#[from] prometheus::Error),
+    /// A snapshot could not be encoded or decoded.
+    #[error("snapshot serialization error: {0}")]
+    Serialization(#[from] bincode::Error),
 }
 
 /// A snapshot of system state for metrics update.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct MetricsSnapshot {
     /// Whether the entropy source is currently healthy.
     pub is_healthy: bool,
+    /// Whether the source is still within its startup grace window.
+    pub is_warming: bool,
     /// Consecutive healthy samples.
     pub consecutive_healthy: u64,
     /// Consecutive unhealthy samples.
@@ -27,6 +35,10 @@ pub struct MetricsSnapshot {
     pub variance: Option<f64>,
     /// Autocorrelation from latest statistical test.
     pub autocorrelation: Option<f64>,
+    /// Advisory quality score of the latest statistical test, in `[0, 1]`.
+    pub quality_score: Option<f64>,
+    /// Percentiles of recent health statistics, once enough samples exist.
+    pub health_quantiles: Option<HealthQuantiles>,
     /// Total CSPRNG reseeds performed.
     pub reseed_count: u64,
     /// Bytes generated since last reseed.
@@ -37,6 +49,12 @@ pub struct MetricsSnapshot {
     pub pool_total_bits_added: u64,
     /// Total pool extractions performed.
     pub pool_extractions: u64,
+    /// Sum of absolute differences of the latest frame pair.
+    pub difference_energy: Option<u64>,
+    /// Frame sequence gaps detected by the extractor.
+    pub sequence_gaps: u64,
+    /// Camera opens that negotiated a format other than the configured one.
+    pub format_mismatches: u64,
 }
 
 /// Prometheus metrics registry for entropy monitoring.
@@ -45,6 +63,7 @@ pub struct MetricsRegistry {
 
     // Health metrics
     health_status: IntGauge,
+    warming: IntGauge,
     consecutive_healthy: IntGauge,
     consecutive_unhealthy: IntGauge,
     total_samples: IntCounter,
@@ -53,6 +72,8 @@ pub struct MetricsRegistry {
     bit_bias: Gauge,
     variance: Gauge,
     autocorrelation: Gauge,
+    quality_score: Gauge,
+    health_quantiles: GaugeVec,
 
     // CSPRNG metrics
     reseed_total: IntCounter,
@@ -62,6 +83,16 @@ pub struct MetricsRegistry {
     pool_size_bytes: IntGauge,
     pool_total_bits_added: IntCounter,
     pool_extractions_total: IntCounter,
+
+    // Extraction metrics
+    difference_energy: IntGauge,
+    sequence_gaps_total: IntCounter,
+
+    // Capture metrics
+    format_mismatch_total: IntCounter,
+
+    // Debug metrics (opt-in)
+    byte_histogram: Option<IntGaugeVec>,
 }
 
 impl MetricsRegistry {
@@ -74,6 +105,10 @@ impl MetricsRegistry {
             "optical_entropy_health_status",
             "Current health status (1=healthy, 0=unhealthy)",
         )?;
+        let warming = IntGauge::new(
+            "optical_entropy_warming",
+            "Whether the source is still warming up after startup (1=warming)",
+        )?;
         let consecutive_healthy = IntGauge::new(
             "optical_entropy_consecutive_healthy",
             "Number of consecutive healthy samples",
@@ -100,6 +135,18 @@ impl MetricsRegistry {
             "optical_entropy_autocorrelation",
             "Lag-1 autocorrelation from statistical test",
         )?;
+        let quality_score = Gauge::new(
+            "optical_entropy_quality_score",
+            "Advisory quality score combining the statistical tests (0-1)",
+        )?;
+
+        let health_quantiles = GaugeVec::new(
+            Opts::new(
+                "optical_entropy_health_quantile",
+                "Percentiles of health statistics over recent samples",
+            ),
+            &["statistic", "quantile"],
+        )?;
 
         // CSPRNG metrics
         let reseed_total = IntCounter::new(
@@ -125,41 +172,123 @@ impl MetricsRegistry {
             "Total entropy pool extractions performed",
         )?;
 
+        // Extraction metrics
+        let difference_energy = IntGauge::new(
+            "optical_entropy_difference_energy",
+            "Sum of absolute pixel differences between the latest frames",
+        )?;
+        let sequence_gaps_total = IntCounter::new(
+            "optical_entropy_sequence_gaps_total",
+            "Frame sequence discontinuities that reset differencing",
+        )?;
+
+        // Capture metrics
+        let format_mismatch_total = IntCounter::new(
+            "optical_entropy_format_mismatch_total",
+            "Camera opens that negotiated a format other than the configured one",
+        )?;
+
         // Register all metrics
         registry.register(Box::new(health_status.clone()))?;
+        registry.register(Box::new(warming.clone()))?;
         registry.register(Box::new(consecutive_healthy.clone()))?;
         registry.register(Box::new(consecutive_unhealthy.clone()))?;
         registry.register(Box::new(total_samples.clone()))?;
         registry.register(Box::new(bit_bias.clone()))?;
         registry.register(Box::new(variance.clone()))?;
         registry.register(Box::new(autocorrelation.clone()))?;
+        registry.register(Box::new(quality_score.clone()))?;
+        registry.register(Box::new(health_quantiles.clone()))?;
         registry.register(Box::new(reseed_total.clone()))?;
         registry.register(Box::new(bytes_since_reseed.clone()))?;
         registry.register(Box::new(pool_size_bytes.clone()))?;
         registry.register(Box::new(pool_total_bits_added.clone()))?;
         registry.register(Box::new(pool_extractions_total.clone()))?;
+        registry.register(Box::new(difference_energy.clone()))?;
+        registry.register(Box::new(sequence_gaps_total.clone()))?;
+        registry.register(Box::new(format_mismatch_total.clone()))?;
 
         Ok(Self {
             registry,
             health_status,
+            warming,
             consecutive_healthy,
             consecutive_unhealthy,
             total_samples,
             bit_bias,
             variance,
             autocorrelation,
+            quality_score,
+            health_quantiles,
             reseed_total,
             bytes_since_reseed,
             pool_size_bytes,
             pool_total_bits_added,
             pool_extractions_total,
+            difference_energy,
+            sequence_gaps_total,
+            format_mismatch_total,
+            byte_histogram: None,
         })
     }
 
+    /// Creates a registry that also exports the raw byte histogram.
+    ///
+    /// Adds `optical_entropy_byte_histogram`, one series per byte value.
+    /// That is 256 series, so it is meant for debugging a biased source
+    /// rather than routine scraping.
+    pub fn with_byte_histogram() -> Result<Self, MetricsError> {
+        let mut metrics = Self::new()?;
+        let byte_histogram = IntGaugeVec::new(
+            Opts::new(
+                "optical_entropy_byte_histogram",
+                "Occurrences of each byte value in the latest sample",
+            ),
+            &["byte"],
+        )?;
+        metrics.registry.register(Box::new(byte_histogram.clone()))?;
+        metrics.byte_histogram = Some(byte_histogram);
+        Ok(metrics)
+    }
+
+    /// Updates the byte histogram, if enabled.
+    ///
+    /// See [`StatisticalTests::byte_histogram`](crate::analysis::StatisticalTests::byte_histogram).
+    pub fn update_byte_histogram(&self, histogram: &[u64; 256]) {
+        let Some(gauges) = &self.byte_histogram else {
+            return;
+        };
+        for (byte, &count) in histogram.iter().enumerate() {
+            gauges
+                .with_label_values(&[&byte.to_string()])
+                .set(count as i64);
+        }
+    }
+
+    /// Updates the health statistic percentiles.
+    ///
+    /// See [`HealthMonitor::quantiles`](crate::analysis::HealthMonitor::quantiles).
+    pub fn update_quantiles(&self, quantiles: &HealthQuantiles) {
+        let statistics = [
+            ("bias", &quantiles.bias),
+            ("variance", &quantiles.variance),
+            ("autocorrelation", &quantiles.autocorrelation),
+        ];
+        for (statistic, q) in statistics {
+            let Quantiles { p50, p95, p99 } = *q;
+            for (quantile, value) in [("0.5", p50), ("0.95", p95), ("0.99", p99)] {
+                self.health_quantiles
+                    .with_label_values(&[statistic, quantile])
+                    .set(value);
+            }
+        }
+    }
+
     /// Updates all metrics from a snapshot of system state.
     pub fn update(&self, snapshot: &MetricsSnapshot) {
         // Health metrics
         self.health_status.set(if snapshot.is_healthy { 1 } else { 0 });
+        self.warming.set(i64::from(snapshot.is_warming));
         self.consecutive_healthy.set(snapshot.consecutive_healthy as i64);
         self.consecutive_unhealthy.set(snapshot.consecutive_unhealthy as i64);
 
@@ -179,6 +308,12 @@ impl MetricsRegistry {
         if let Some(autocorr) = snapshot.autocorrelation {
             self.autocorrelation.set(autocorr);
         }
+        if let Some(score) = snapshot.quality_score {
+            self.quality_score.set(score);
+        }
+        if let Some(quantiles) = &snapshot.health_quantiles {
+            self.update_quantiles(quantiles);
+        }
 
         // CSPRNG metrics
         let current_reseeds = self.reseed_total.get();
@@ -199,6 +334,23 @@ impl MetricsRegistry {
         if snapshot.pool_extractions > current_extractions {
             self.pool_extractions_total.inc_by(snapshot.pool_extractions - current_extractions);
         }
+
+        // Extraction metrics
+        if let Some(energy) = snapshot.difference_energy {
+            self.difference_energy.set(energy as i64);
+        }
+        let current_gaps = self.sequence_gaps_total.get();
+        if snapshot.sequence_gaps > current_gaps {
+            self.sequence_gaps_total
+                .inc_by(snapshot.sequence_gaps - current_gaps);
+        }
+
+        // Capture metrics
+        let current_mismatches = self.format_mismatch_total.get();
+        if snapshot.format_mismatches > current_mismatches {
+            self.format_mismatch_total
+                .inc_by(snapshot.format_mismatches - current_mismatches);
+        }
     }
 
     /// Returns the underlying Prometheus registry.
@@ -217,7 +369,33 @@ impl MetricsRegistry {
 }
 
 impl MetricsSnapshot {
+    /// Encodes the snapshot in a compact binary form for IPC.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, MetricsError> {
+        Ok(bincode::serialize(self)?)
+    }
+
+    /// Decodes a snapshot produced by [`to_bytes`](Self::to_bytes).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, MetricsError> {
+        Ok(bincode::deserialize(bytes)?)
+    }
+
+    /// Creates a snapshot of every metric a [`Pipeline`](crate::pipeline::Pipeline) tracks.
+    pub fn from_pipeline(pipeline: &crate::pipeline::Pipeline) -> Self {
+        let health = pipeline.health();
+        Self {
+            health_quantiles: health.quantiles(),
+            difference_energy: pipeline.extractor().last_difference_energy(),
+            sequence_gaps: pipeline.extractor().sequence_gaps(),
+            format_mismatches: pipeline.camera().map_or(0, |c| c.format_mismatches()),
+            ..Self::from_components(health.metrics(), pipeline.rng(), pipeline.pool())
+        }
+    }
+
     /// Creates a snapshot from the current state of entropy components.
+    ///
+    /// `health_quantiles`, `difference_energy`, `sequence_gaps` and
+    /// `format_mismatches` are left unset; use
+    /// [`from_pipeline`](Self::from_pipeline) to fill them too.
     pub fn from_components(
         health: &crate::analysis::HealthMetrics,
         rng: &crate::reseeding::ReseedableRng,
@@ -228,20 +406,27 @@ impl MetricsSnapshot {
             .as_ref()
             .map(|s| (Some(s.bit_bias), Some(s.variance), Some(s.autocorrelation)))
             .unwrap_or((None, None, None));
+        let quality_score = health.latest_stats.as_ref().map(|s| s.quality_score());
 
         Self {
             is_healthy: health.is_healthy,
+            is_warming: health.is_warming,
             consecutive_healthy: health.consecutive_healthy,
             consecutive_unhealthy: health.consecutive_unhealthy,
             total_samples: health.total_samples,
             bit_bias,
             variance,
             autocorrelation,
+            quality_score,
+            health_quantiles: None,
             reseed_count: rng.reseed_count(),
             bytes_since_reseed: rng.bytes_since_reseed(),
             pool_size_bytes: pool.size_bytes(),
             pool_total_bits_added: pool.total_bits_added(),
             pool_extractions: pool.total_extractions(),
+            difference_energy: None,
+            sequence_gaps: 0,
+            format_mismatches: 0,
         }
     }
 }
@@ -262,17 +447,23 @@ mod tests {
 
         let snapshot = MetricsSnapshot {
             is_healthy: true,
+            is_warming: false,
             consecutive_healthy: 5,
             consecutive_unhealthy: 0,
             total_samples: 10,
             bit_bias: Some(0.01),
             variance: Some(5000.0),
             autocorrelation: Some(0.02),
+            quality_score: Some(0.97),
+            health_quantiles: None,
             reseed_count: 2,
             bytes_since_reseed: 1024,
             pool_size_bytes: 128,
             pool_total_bits_added: 4096,
             pool_extractions: 1,
+            difference_energy: Some(4096),
+            sequence_gaps: 3,
+            format_mismatches: 1,
         };
 
         registry.update(&snapshot);
@@ -280,8 +471,13 @@ mod tests {
         // Verify metrics were set
         let output = registry.encode().unwrap();
         assert!(output.contains("optical_entropy_health_status 1"));
+        assert!(output.contains("optical_entropy_warming 0"));
         assert!(output.contains("optical_entropy_consecutive_healthy 5"));
         assert!(output.contains("optical_entropy_csprng_reseed_total 2"));
+        assert!(output.contains("optical_entropy_difference_energy 4096"));
+        assert!(output.contains("optical_entropy_sequence_gaps_total 3"));
+        assert!(output.contains("optical_entropy_format_mismatch_total 1"));
+        assert!(output.contains("optical_entropy_quality_score 0.97"));
     }
 
     #[test]
@@ -294,4 +490,130 @@ mod tests {
         assert!(output.contains("optical_entropy_csprng_reseed_total"));
         assert!(output.contains("optical_entropy_pool_size_bytes"));
     }
+
+    #[test]
+    fn test_snapshot_binary_round_trip() {
+        let snapshot = MetricsSnapshot {
+            is_healthy: true,
+            consecutive_healthy: 7,
+            total_samples: 42,
+            bit_bias: Some(-0.003),
+            variance: None,
+            autocorrelation: Some(0.01),
+            reseed_count: 3,
+            pool_size_bytes: 256,
+            ..Default::default()
+        };
+
+        let bytes = snapshot.to_bytes().unwrap();
+        let decoded = MetricsSnapshot::from_bytes(&bytes).unwrap();
+
+        assert_eq!(decoded, snapshot);
+        assert_eq!(decoded.to_bytes().unwrap(), bytes);
+    }
+
+    #[test]
+    fn test_byte_histogram_opt_in() {
+        let histogram = crate::analysis::StatisticalTests::byte_histogram(&[0x00, 0xFF, 0xFF]);
+
+        let plain = MetricsRegistry::new().unwrap();
+        plain.update_byte_histogram(&histogram);
+        assert!(!plain.encode().unwrap().contains("optical_entropy_byte_histogram"));
+
+        let debug = MetricsRegistry::with_byte_histogram().unwrap();
+        debug.update_byte_histogram(&histogram);
+        let output = debug.encode().unwrap();
+        assert!(output.contains("optical_entropy_byte_histogram{byte=\"255\"} 2"));
+        assert!(output.contains("optical_entropy_byte_histogram{byte=\"0\"} 1"));
+    }
+
+    #[test]
+    fn test_quantiles_exported() {
+        let registry = MetricsRegistry::new().unwrap();
+        let q = Quantiles {
+            p50: 0.01,
+            p95: 0.02,
+            p99: 0.25,
+        };
+        registry.update_quantiles(&HealthQuantiles {
+            bias: q,
+            variance: q,
+            autocorrelation: q,
+        });
+
+        let output = registry.encode().unwrap();
+        assert!(output.contains(
+            "optical_entropy_health_quantile{quantile=\"0.99\",statistic=\"bias\"} 0.25"
+        ));
+    }
+
+    #[test]
+    fn test_pipeline_snapshot_exports_quantiles() {
+        use crate::analysis::{HealthMonitor, QualityThresholds};
+        use crate::conditioning::EntropyPool;
+        use crate::extraction::{Extractor, RawBits};
+        use crate::pipeline::Pipeline;
+        use crate::reseeding::ReseedableRng;
+
+        let mut pipeline = Pipeline::new(
+            Extractor::new(),
+            EntropyPool::default(),
+            HealthMonitor::new(QualityThresholds::permissive()),
+            ReseedableRng::from_os_entropy(),
+        );
+        let data: Vec<u8> = (0..1000).map(|i| (i * 53 + 31) as u8).collect();
+        pipeline.health_mut().analyze(&RawBits::from_bytes(data, 1));
+
+        let snapshot = MetricsSnapshot::from_pipeline(&pipeline);
+        assert!(snapshot.health_quantiles.is_some());
+
+        let registry = MetricsRegistry::new().unwrap();
+        registry.update(&snapshot);
+        let output = registry.encode().unwrap();
+        assert!(
+            output.contains("optical_entropy_health_quantile{quantile=\"0.5\",statistic=\"bias\"}")
+        );
+    }
+
+    #[test]
+    fn test_pipeline_snapshot_counts_format_mismatches() {
+        use crate::capture::{Camera, CameraError, CaptureConfig, Frame, MockCamera};
+        use crate::pipeline::PipelineBuilder;
+
+        /// Mock that reports opening in a format other than requested.
+        struct NegotiatingCamera(MockCamera);
+
+        impl Camera for NegotiatingCamera {
+            fn open(&mut self, config: &CaptureConfig) -> Result<(), CameraError> {
+                self.0.open(config)
+            }
+
+            fn capture(&mut self) -> Result<Frame, CameraError> {
+                self.0.capture()
+            }
+
+            fn is_open(&self) -> bool {
+                self.0.is_open()
+            }
+
+            fn close(&mut self) {
+                self.0.close()
+            }
+
+            fn format_mismatches(&self) -> u64 {
+                1
+            }
+        }
+
+        let pipeline = PipelineBuilder::new(NegotiatingCamera(MockCamera::new()))
+            .build()
+            .unwrap();
+        let snapshot = MetricsSnapshot::from_pipeline(&pipeline);
+        assert_eq!(snapshot.format_mismatches, 1);
+
+        let registry = MetricsRegistry::new().unwrap();
+        registry.update(&snapshot);
+        let output = registry.encode().unwrap();
+        assert!(output.contains("optical_entropy_format_mismatch_total 1"));
+    }
 }
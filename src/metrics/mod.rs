@@ -7,6 +7,7 @@
 //!
 //! ## Health Metrics
 //! - `optical_entropy_health_status` - Current health status (1=healthy, 0=unhealthy)
+//! - `optical_entropy_warming` - Still in the startup grace window (1=warming)
 //! - `optical_entropy_consecutive_healthy` - Consecutive healthy samples
 //! - `optical_entropy_consecutive_unhealthy` - Consecutive unhealthy samples
 //! - `optical_entropy_total_samples` - Total samples analyzed
@@ -15,6 +16,11 @@
 //! - `optical_entropy_bit_bias` - Bit bias (deviation from 0.5)
 //! - `optical_entropy_variance` - Byte-level variance
 //! - `optical_entropy_autocorrelation` - Lag-1 autocorrelation
+//! - `optical_entropy_quality_score` - Advisory 0-1 score combining the
+//!   statistical tests (see
+//!   [`StatisticalTests::quality_score`](crate::analysis::StatisticalTests::quality_score))
+//! - `optical_entropy_health_quantile{statistic,quantile}` - p50/p95/p99 of
+//!   absolute bias, variance and absolute autocorrelation over recent samples
 //!
 //! ## CSPRNG Metrics
 //! - `optical_entropy_csprng_reseed_total` - Total reseeds performed
@@ -25,6 +31,23 @@
 //! - `optical_entropy_pool_total_bits_added` - Total bits added to pool
 //! - `optical_entropy_pool_extractions_total` - Total extractions performed
 //!
+//! ## Extraction Metrics
+//! - `optical_entropy_difference_energy` - Sum of absolute differences between the latest frames
+//! - `optical_entropy_sequence_gaps_total` - Frame sequence gaps that reset differencing
+//!
+//! ## Capture Metrics
+//! - `optical_entropy_format_mismatch_total` - Camera opens that negotiated
+//!   a format other than the configured one
+//!
+//! ## Debug Metrics
+//! Only registered by [`MetricsRegistry::with_byte_histogram`]:
+//! - `optical_entropy_byte_histogram{byte}` - Occurrences of each byte value
+//!
+//! # Binary Snapshots
+//! With the `metrics` feature, `/metrics` also returns the latest
+//! [`MetricsSnapshot`] as CBOR or MessagePack when the request sends
+//! `Accept: application/cbor` or `Accept: application/msgpack`.
+//!
 //! # Example
 //!
 //! ```no_run
@@ -36,17 +59,23 @@
 //! // Update metrics from system state
 //! let snapshot = MetricsSnapshot {
 //!     is_healthy: true,
+//!     is_warming: false,
 //!     consecutive_healthy: 5,
 //!     consecutive_unhealthy: 0,
 //!     total_samples: 100,
 //!     bit_bias: Some(0.002),
 //!     variance: Some(5400.0),
 //!     autocorrelation: Some(0.01),
+//!     quality_score: Some(0.98),
+//!     health_quantiles: None,
 //!     reseed_count: 3,
 //!     bytes_since_reseed: 1024,
 //!     pool_size_bytes: 256,
 //!     pool_total_bits_added: 8192,
 //!     pool_extractions: 2,
+//!     difference_energy: Some(1_500_000),
+//!     sequence_gaps: 0,
+//!     format_mismatches: 0,
 //! };
 //!
 //! registry.update(&snapshot);
@@ -1,18 +1,30 @@
 //! HTTP server for Prometheus metrics endpoint.
+//!
+//! `/metrics` serves Prometheus text by default. Collectors that send
+//! `Accept: application/cbor` or `Accept: application/msgpack` instead
+//! receive the latest [`MetricsSnapshot`] in that binary encoding.
+//!
+//! `/debug` returns the latest snapshot as JSON, with the seconds since
+//! it was taken, and `/` serves a small dashboard page that polls it.
 
-use crate::metrics::MetricsRegistry;
+use crate::metrics::{MetricsRegistry, MetricsSnapshot};
 use axum::{
     extract::State,
-    http::StatusCode,
-    response::IntoResponse,
+    http::{header, HeaderMap, StatusCode},
+    response::{Html, IntoResponse, Response},
     routing::get,
-    Router,
+    Json, Router,
 };
+use serde::Serialize;
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Instant;
 use thiserror::Error;
 use tokio::sync::RwLock;
 
+/// Dashboard page served at `/`; polls `/debug` with inline script.
+const DASHBOARD_HTML: &str = include_str!("dashboard.html");
+
 /// Errors that can occur during metrics server operations.
 #[derive(Debug, Error)]
 pub enum ServerError {
@@ -50,6 +62,10 @@ impl MetricsServerConfig {
 /// Shared state for the metrics server.
 pub struct MetricsState {
     registry: MetricsRegistry,
+    /// Most recent snapshot, served to binary-encoding clients.
+    latest: MetricsSnapshot,
+    /// When `latest` was recorded, if ever.
+    updated: Option<Instant>,
 }
 
 /// HTTP server for exposing Prometheus metrics.
@@ -66,7 +82,11 @@ impl MetricsServer {
     ) -> Self {
         Self {
             config,
-            state: Arc::new(RwLock::new(MetricsState { registry })),
+            state: Arc::new(RwLock::new(MetricsState {
+                registry,
+                latest: MetricsSnapshot::default(),
+                updated: None,
+            })),
         }
     }
 
@@ -80,6 +100,8 @@ impl MetricsServer {
     /// This method runs the server until it is shut down.
     pub async fn run(self) -> Result<(), ServerError> {
         let app = Router::new()
+            .route("/", get(dashboard_handler))
+            .route("/debug", get(debug_handler))
             .route("/metrics", get(metrics_handler))
             .route("/health", get(health_handler))
             .with_state(self.state);
@@ -100,18 +122,87 @@ impl MetricsServer {
 }
 
 impl MetricsState {
-    /// Updates the metrics from a snapshot.
-    pub fn update(&self, snapshot: &super::MetricsSnapshot) {
+    /// Updates the metrics from a snapshot and retains it for
+    /// binary-encoded responses.
+    pub fn update(&mut self, snapshot: &MetricsSnapshot) {
         self.registry.update(snapshot);
+        self.latest = snapshot.clone();
+        self.updated = Some(Instant::now());
+    }
+}
+
+/// Body of the /debug endpoint.
+#[derive(Serialize)]
+struct DebugReport<'a> {
+    #[serde(flatten)]
+    snapshot: &'a MetricsSnapshot,
+    /// Seconds since the snapshot was recorded; `None` before the first.
+    seconds_since_update: Option<f64>,
+}
+
+/// Binary snapshot encodings negotiable via the `Accept` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BinaryEncoding {
+    Cbor,
+    MessagePack,
+}
+
+impl BinaryEncoding {
+    /// Picks the first supported binary encoding listed in `Accept`.
+    fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let accept = headers.get(header::ACCEPT)?.to_str().ok()?;
+        accept
+            .split(',')
+            .find_map(|media| match media.split(';').next().unwrap_or("").trim() {
+                "application/cbor" => Some(Self::Cbor),
+                "application/msgpack" | "application/x-msgpack" => Some(Self::MessagePack),
+                _ => None,
+            })
+    }
+
+    fn content_type(self) -> &'static str {
+        match self {
+            Self::Cbor => "application/cbor",
+            Self::MessagePack => "application/msgpack",
+        }
+    }
+
+    fn encode(self, snapshot: &MetricsSnapshot) -> Result<Vec<u8>, String> {
+        match self {
+            Self::Cbor => {
+                let mut buffer = Vec::new();
+                ciborium::into_writer(snapshot, &mut buffer).map_err(|e| e.to_string())?;
+                Ok(buffer)
+            }
+            Self::MessagePack => rmp_serde::to_vec_named(snapshot).map_err(|e| e.to_string()),
+        }
     }
 }
 
 /// Handler for the /metrics endpoint.
 async fn metrics_handler(
     State(state): State<Arc<RwLock<MetricsState>>>,
-) -> impl IntoResponse {
+    headers: HeaderMap,
+) -> Response {
     let state = state.read().await;
 
+    if let Some(encoding) = BinaryEncoding::from_headers(&headers) {
+        return match encoding.encode(&state.latest) {
+            Ok(body) => (
+                StatusCode::OK,
+                [("content-type", encoding.content_type())],
+                body,
+            )
+                .into_response(),
+            Err(e) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                [("content-type", "text/plain; charset=utf-8")],
+                format!("Failed to encode snapshot: {}", e),
+            )
+                .into_response(),
+        };
+    }
+
     match state.registry.encode() {
         Ok(output) => (
             StatusCode::OK,
@@ -124,6 +215,22 @@ async fn metrics_handler(
             format!("Failed to encode metrics: {}", e),
         ),
     }
+    .into_response()
+}
+
+/// Handler for the / endpoint.
+async fn dashboard_handler() -> Html<&'static str> {
+    Html(DASHBOARD_HTML)
+}
+
+/// Handler for the /debug endpoint.
+async fn debug_handler(State(state): State<Arc<RwLock<MetricsState>>>) -> Response {
+    let state = state.read().await;
+    Json(DebugReport {
+        snapshot: &state.latest,
+        seconds_since_update: state.updated.map(|at| at.elapsed().as_secs_f64()),
+    })
+    .into_response()
 }
 
 /// Handler for the /health endpoint.
@@ -146,4 +253,94 @@ mod tests {
         let config = MetricsServerConfig::with_port(8080);
         assert_eq!(config.bind_addr.port(), 8080);
     }
+
+    #[tokio::test]
+    async fn test_metrics_handler_serves_cbor() {
+        let server = MetricsServer::new(
+            MetricsServerConfig::default(),
+            MetricsRegistry::new().unwrap(),
+        );
+        let snapshot = MetricsSnapshot {
+            is_healthy: true,
+            total_samples: 42,
+            bit_bias: Some(0.01),
+            ..Default::default()
+        };
+        server.state().write().await.update(&snapshot);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT, "application/cbor".parse().unwrap());
+        let response = metrics_handler(State(server.state()), headers).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/cbor");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let decoded: MetricsSnapshot = ciborium::from_reader(&body[..]).unwrap();
+        assert_eq!(decoded, snapshot);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_handler_defaults_to_text() {
+        let server = MetricsServer::new(
+            MetricsServerConfig::default(),
+            MetricsRegistry::new().unwrap(),
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT, "text/plain".parse().unwrap());
+        let response = metrics_handler(State(server.state()), headers).await;
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(String::from_utf8_lossy(&body).contains("optical_entropy_health_status"));
+    }
+
+    #[tokio::test]
+    async fn test_dashboard_served_at_root() {
+        let response = dashboard_handler().await.into_response();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers()[header::CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .starts_with("text/html"));
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let page = String::from_utf8_lossy(&body);
+        assert!(page.contains("<html"));
+        assert!(page.contains("fetch(\"/debug\")"));
+    }
+
+    #[tokio::test]
+    async fn test_debug_reports_snapshot_and_age() {
+        let server = MetricsServer::new(
+            MetricsServerConfig::default(),
+            MetricsRegistry::new().unwrap(),
+        );
+        let response = debug_handler(State(server.state())).await;
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let debug: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(debug["seconds_since_update"].is_null());
+
+        let snapshot = MetricsSnapshot {
+            is_healthy: true,
+            reseed_count: 4,
+            ..Default::default()
+        };
+        server.state().write().await.update(&snapshot);
+        let response = debug_handler(State(server.state())).await;
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let debug: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(debug["is_healthy"], true);
+        assert_eq!(debug["reseed_count"], 4);
+        assert!(debug["seconds_since_update"].as_f64().unwrap() >= 0.0);
+    }
 }
@@ -0,0 +1,238 @@
+//! Validated construction of a camera-driven [`Pipeline`].
+//!
+//! Wiring the stages by hand leaves room for settings that can never
+//! produce a reseed, such as a pool cap below its own readiness
+//! threshold. [`PipelineBuilder`] checks the settings together, opens
+//! the camera, and returns a pipeline that captures for itself.
+
+use super::{FrameOutcome, Pipeline};
+use crate::analysis::{HealthMonitor, QualityThresholds, ThresholdError};
+use crate::capture::{Camera, CameraError, CaptureConfig, ConfigError};
+use crate::conditioning::{EntropyPool, PoolConfig};
+use crate::extraction::Extractor;
+use crate::reseeding::{ReseedableRng, ReseedingError};
+use thiserror::Error;
+
+/// Default minimum entropy a seed must carry, in bits.
+pub const DEFAULT_MIN_ENTROPY_BITS: usize = 128;
+
+/// Errors from building or stepping a [`Pipeline`].
+#[derive(Debug, Error)]
+pub enum PipelineError {
+    /// The capture config is invalid.
+    #[error("invalid configuration: {0}")]
+    Config(#[from] ConfigError),
+    /// The health thresholds are invalid.
+    #[error("invalid thresholds: {0}")]
+    InvalidThresholds(#[from] ThresholdError),
+    /// The pool settings can never produce a seed.
+    #[error("invalid pool configuration: {0}")]
+    InvalidPool(String),
+    /// The minimum entropy exceeds what a seed can carry.
+    #[error("minimum entropy of {need} bits exceeds the {max}-bit seed size")]
+    UnreachableMinEntropy {
+        /// Required minimum, in bits.
+        need: usize,
+        /// Largest estimate a seed can carry, in bits.
+        max: usize,
+    },
+    /// The camera failed to open or capture.
+    #[error("camera error: {0}")]
+    Camera(#[from] CameraError),
+    /// The CSPRNG rejected a seed.
+    #[error("reseeding failed: {0}")]
+    Reseeding(#[from] ReseedingError),
+    /// [`Pipeline::step`] was called on a pipeline without a camera.
+    #[error("pipeline has no camera")]
+    NoCamera,
+}
+
+/// Outcome of [`Pipeline::step`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PipelineStepResult {
+    /// Sequence number of the captured frame.
+    pub sequence: u64,
+    /// What processing the frame led to.
+    pub outcome: FrameOutcome,
+}
+
+/// Builds a [`Pipeline`] that owns its camera.
+///
+/// ```no_run
+/// use optical_entropy::pipeline::{FrameOutcome, PipelineBuilder};
+/// use optical_entropy::capture::{CaptureConfig, MockCamera};
+///
+/// let mut pipeline = PipelineBuilder::new(MockCamera::with_seed(1))
+///     .with_capture_config(CaptureConfig::with_dimensions(64, 64))
+///     .build()
+///     .unwrap();
+///
+/// loop {
+///     let step = pipeline.step().unwrap();
+///     if let FrameOutcome::Reseeded { .. } = step.outcome {
+///         break;
+///     }
+/// }
+/// ```
+pub struct PipelineBuilder<C: Camera + 'static> {
+    camera: C,
+    capture: CaptureConfig,
+    thresholds: QualityThresholds,
+    pool: PoolConfig,
+    min_entropy_bits: usize,
+}
+
+impl<C: Camera + 'static> PipelineBuilder<C> {
+    /// Starts a builder around `camera`, with default settings.
+    pub fn new(camera: C) -> Self {
+        Self {
+            camera,
+            capture: CaptureConfig::default(),
+            thresholds: QualityThresholds::default(),
+            pool: PoolConfig::default(),
+            min_entropy_bits: DEFAULT_MIN_ENTROPY_BITS,
+        }
+    }
+
+    /// Sets the config the camera is opened with.
+    ///
+    /// Its `warmup_frames` also configures the extractor.
+    pub fn with_capture_config(mut self, config: CaptureConfig) -> Self {
+        self.capture = config;
+        self
+    }
+
+    /// Sets the health thresholds.
+    pub fn with_thresholds(mut self, thresholds: QualityThresholds) -> Self {
+        self.thresholds = thresholds;
+        self
+    }
+
+    /// Sets the entropy pool configuration.
+    pub fn with_pool_config(mut self, config: PoolConfig) -> Self {
+        self.pool = config;
+        self
+    }
+
+    /// Sets the minimum entropy a seed must carry to reseed, in bits.
+    pub fn with_min_entropy(mut self, bits: usize) -> Self {
+        self.min_entropy_bits = bits;
+        self
+    }
+
+    /// Validates the settings, opens the camera and builds the pipeline.
+    pub fn build(mut self) -> Result<Pipeline, PipelineError> {
+        self.validate()?;
+        self.camera.open(&self.capture)?;
+
+        let mut pipeline = Pipeline::new(
+            Extractor::with_warmup(self.capture.warmup_frames),
+            EntropyPool::new(self.pool),
+            HealthMonitor::new(self.thresholds),
+            ReseedableRng::with_min_entropy(self.min_entropy_bits),
+        );
+        pipeline.camera = Some(Box::new(self.camera));
+        Ok(pipeline)
+    }
+
+    /// Checks every setting before the camera is touched.
+    fn validate(&self) -> Result<(), PipelineError> {
+        self.capture.validate()?;
+        self.thresholds.validate()?;
+
+        if self.pool.min_bits == 0 {
+            return Err(PipelineError::InvalidPool(
+                "min_bits must be positive".into(),
+            ));
+        }
+        if self.pool.min_bits > self.pool.max_bytes.saturating_mul(8) {
+            return Err(PipelineError::InvalidPool(format!(
+                "min_bits {} exceeds the {}-byte buffer cap",
+                self.pool.min_bits, self.pool.max_bytes
+            )));
+        }
+
+        let max = self.pool.algorithm.output_len() * 8;
+        if self.min_entropy_bits > max {
+            return Err(PipelineError::UnreachableMinEntropy {
+                need: self.min_entropy_bits,
+                max,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capture::MockCamera;
+
+    #[test]
+    fn test_steps_mock_until_reseed() {
+        let mut pipeline = PipelineBuilder::new(MockCamera::with_seed(1))
+            .with_capture_config(CaptureConfig::with_dimensions(64, 64))
+            .with_min_entropy(256)
+            .build()
+            .unwrap();
+
+        let mut steps = 0;
+        let entropy_estimate = loop {
+            steps += 1;
+            assert!(steps <= 50, "no reseed within 50 frames");
+            let step = pipeline.step().unwrap();
+            assert_eq!(step.sequence, steps);
+            if let FrameOutcome::Reseeded { entropy_estimate } = step.outcome {
+                break entropy_estimate;
+            }
+        };
+
+        assert_eq!(entropy_estimate, 256);
+        assert_eq!(pipeline.rng().reseed_count(), 1);
+    }
+
+    #[test]
+    fn test_rejects_unreachable_settings() {
+        let result = PipelineBuilder::new(MockCamera::new())
+            .with_pool_config(PoolConfig {
+                min_bits: 1024,
+                max_bytes: 64,
+                ..Default::default()
+            })
+            .build();
+        assert!(matches!(result, Err(PipelineError::InvalidPool(_))));
+
+        let result = PipelineBuilder::new(MockCamera::new())
+            .with_thresholds(QualityThresholds {
+                expected_bias_center: 2.0,
+                ..Default::default()
+            })
+            .build();
+        assert!(matches!(result, Err(PipelineError::InvalidThresholds(_))));
+
+        let result = PipelineBuilder::new(MockCamera::new())
+            .with_min_entropy(512)
+            .build();
+        assert!(matches!(
+            result,
+            Err(PipelineError::UnreachableMinEntropy {
+                need: 512,
+                max: 256
+            })
+        ));
+
+        let result = PipelineBuilder::new(MockCamera::new())
+            .with_capture_config(CaptureConfig::with_dimensions(0, 64))
+            .build();
+        assert!(matches!(
+            result,
+            Err(PipelineError::Config(ConfigError::InvalidDimensions))
+        ));
+    }
+
+    #[test]
+    fn test_step_without_camera() {
+        let mut pipeline = Pipeline::default();
+        assert!(matches!(pipeline.step(), Err(PipelineError::NoCamera)));
+    }
+}
@@ -0,0 +1,625 @@
+//! End-to-end entropy pipeline.
+//!
+//! Coordinates extraction, health analysis, pooling and reseeding
+//! for a stream of frames, applying the fail-closed policy in one
+//! place instead of in every caller.
+
+mod builder;
+
+pub use builder::{PipelineBuilder, PipelineError, PipelineStepResult, DEFAULT_MIN_ENTROPY_BITS};
+
+use crate::analysis::HealthMonitor;
+use crate::capture::{Camera, Frame};
+use crate::conditioning::{ConditionedSeed, EntropyPool};
+use crate::extraction::{Extractor, RawBits};
+use crate::reseeding::{ReseedLog, ReseedRecord, ReseedableRng, ReseedingError};
+use std::io::Write;
+
+/// Mean absolute pixel difference below which a frame pair counts as static.
+const STATIC_SCENE_ENERGY_PER_PIXEL: f64 = 0.5;
+
+/// Consecutive static frame pairs before the scene is reported as static.
+const STATIC_SCENE_FRAMES: u32 = 30;
+
+/// Result of processing a single frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameOutcome {
+    /// No bits were produced (e.g. the differencer is priming).
+    Pending,
+    /// Bits passed health checks and were added to the pool.
+    Accepted,
+    /// Bits failed health checks and were discarded.
+    Rejected,
+    /// Bits were accepted and the CSPRNG was reseeded.
+    Reseeded {
+        /// Entropy estimate of the seed used, in bits.
+        entropy_estimate: usize,
+    },
+    /// Dry run: a reseed would have happened but was skipped.
+    WouldReseed {
+        /// Entropy estimate of the discarded seed, in bits.
+        entropy_estimate: usize,
+    },
+}
+
+/// Coordinates the extraction → conditioning → reseeding data flow.
+pub struct Pipeline {
+    extractor: Extractor,
+    pool: EntropyPool,
+    health: HealthMonitor,
+    rng: ReseedableRng,
+    /// Reset all stages whenever a sample fails health checks.
+    reset_on_unhealthy: bool,
+    /// Run every stage but never reseed the CSPRNG.
+    dry_run: bool,
+    /// Consecutive frame pairs with near-zero difference energy.
+    static_frames: u32,
+    /// Provenance log written on every reseed.
+    reseed_log: Option<ReseedLog>,
+    /// Sink for every extracted bit, before health checks and conditioning.
+    raw_dump: Option<Box<dyn Write + Send>>,
+    /// Sink for every conditioned seed taken from the pool.
+    output_dump: Option<Box<dyn Write + Send>>,
+    /// Camera read by [`step`](Self::step), if built with one.
+    camera: Option<Box<dyn Camera>>,
+}
+
+impl Pipeline {
+    /// Creates a pipeline from its components.
+    pub fn new(
+        extractor: Extractor,
+        pool: EntropyPool,
+        health: HealthMonitor,
+        rng: ReseedableRng,
+    ) -> Self {
+        Self {
+            extractor,
+            pool,
+            health,
+            rng,
+            reset_on_unhealthy: false,
+            dry_run: false,
+            static_frames: 0,
+            reseed_log: None,
+            raw_dump: None,
+            output_dump: None,
+            camera: None,
+        }
+    }
+
+    /// Enables or disables dry-run mode.
+    ///
+    /// In dry-run mode frames are captured, analyzed and pooled as
+    /// usual, but seeds are discarded instead of reseeding the CSPRNG.
+    /// Useful for characterizing a new camera before trusting it.
+    pub fn with_dry_run(mut self, enabled: bool) -> Self {
+        self.dry_run = enabled;
+        self
+    }
+
+    /// Enables or disables a full reset on every health failure.
+    ///
+    /// See [`on_unhealthy`](Self::on_unhealthy).
+    pub fn with_reset_on_unhealthy(mut self, enabled: bool) -> Self {
+        self.reset_on_unhealthy = enabled;
+        self
+    }
+
+    /// Records the provenance of every reseed in `log`.
+    pub fn with_reseed_log(mut self, log: ReseedLog) -> Self {
+        self.reseed_log = Some(log);
+        self
+    }
+
+    /// Writes the raw extractor output to `writer`.
+    ///
+    /// Every extracted byte is written, including samples later
+    /// rejected by health checks, so the stream reflects the source
+    /// itself and can be fed to external suites such as NIST STS or
+    /// dieharder. Conditioned output is never written here.
+    pub fn with_raw_dump<W: Write + Send + 'static>(mut self, writer: W) -> Self {
+        self.raw_dump = Some(Box::new(writer));
+        self
+    }
+
+    /// Writes every conditioned seed taken from the pool to `writer`.
+    ///
+    /// Each extraction appends the 32 seed bytes, whether or not the
+    /// reseed then happens (e.g. in dry-run mode). Conditioned output
+    /// should be indistinguishable from uniform, so testing this
+    /// stream validates the conditioning step independently of the
+    /// raw source.
+    pub fn with_output_dump<W: Write + Send + 'static>(mut self, writer: W) -> Self {
+        self.output_dump = Some(Box::new(writer));
+        self
+    }
+
+    /// Processes one frame through every stage.
+    ///
+    /// Reseeding only happens when the health monitor allows it and
+    /// the pool has accumulated enough entropy. With a fixed extractor
+    /// output size, every chunk the frame completes is processed; a
+    /// reseed from any of them is reported over later acceptances.
+    pub fn process(&mut self, frame: &Frame) -> Result<FrameOutcome, ReseedingError> {
+        let frozen_events = self.extractor.frozen_frame_events();
+        let output = self.extractor.process(frame);
+        if self.extractor.frozen_frame_events() != frozen_events {
+            // Output from the frames leading up to the freeze is suspect
+            self.on_unhealthy();
+        }
+        self.track_scene_motion(frame);
+        let Some(bits) = output else {
+            return Ok(FrameOutcome::Pending);
+        };
+
+        let mut outcome = self.process_bits(bits)?;
+        // Drain chunks left buffered when the frame outgrows the chunk size
+        while !matches!(outcome, FrameOutcome::Rejected) {
+            let Some(bits) = self.extractor.next_chunk() else {
+                break;
+            };
+            let next = self.process_bits(bits)?;
+            if next != FrameOutcome::Accepted {
+                outcome = next;
+            }
+        }
+        Ok(outcome)
+    }
+
+    /// Runs one extraction output through health checks, pooling and
+    /// reseeding.
+    fn process_bits(&mut self, bits: RawBits) -> Result<FrameOutcome, ReseedingError> {
+        write_dump(&mut self.raw_dump, bits.data(), "raw bit");
+
+        let was_quarantined = self.health.is_quarantined();
+        let failures = self.health.metrics().consecutive_unhealthy;
+        let healthy = self.health.analyze(&bits).is_healthy;
+
+        // Drop stale differencing state on entering quarantine, and keep
+        // the generator moving forward from the OS while optical is out
+        if !was_quarantined && self.health.is_quarantined() {
+            self.extractor.reset();
+            if !self.dry_run {
+                self.rng.reseed_from_os();
+            }
+        }
+
+        if !healthy {
+            // Only a failure recorded by this sample resets; samples
+            // skipped in quarantine or still unproven do not
+            let failed = self.health.metrics().consecutive_unhealthy > failures;
+            if self.reset_on_unhealthy && failed {
+                self.on_unhealthy();
+            }
+            return Ok(FrameOutcome::Rejected);
+        }
+
+        self.pool.add(&bits);
+
+        if self.health.allow_reseed() && self.pool.is_ready() {
+            if let Some(seed) = self.pool.extract() {
+                write_dump(&mut self.output_dump, seed.as_bytes(), "output");
+                if self.dry_run {
+                    return Ok(FrameOutcome::WouldReseed {
+                        entropy_estimate: seed.seed_entropy_estimate(),
+                    });
+                }
+                self.rng.reseed(&seed)?;
+                self.log_reseed(&seed);
+                return Ok(FrameOutcome::Reseeded {
+                    entropy_estimate: seed.seed_entropy_estimate(),
+                });
+            }
+        }
+
+        Ok(FrameOutcome::Accepted)
+    }
+
+    /// Captures one frame from the pipeline's camera and processes it.
+    ///
+    /// Only pipelines from a [`PipelineBuilder`] own a camera; others
+    /// return [`PipelineError::NoCamera`].
+    pub fn step(&mut self) -> Result<PipelineStepResult, PipelineError> {
+        let camera = self.camera.as_mut().ok_or(PipelineError::NoCamera)?;
+        let frame = camera.capture()?;
+        let outcome = self.process(&frame)?;
+        Ok(PipelineStepResult {
+            sequence: frame.sequence(),
+            outcome,
+        })
+    }
+
+    /// Appends a provenance record for a reseed that just happened.
+    ///
+    /// A failed write is logged but does not undo or block the reseed.
+    fn log_reseed(&mut self, seed: &ConditionedSeed) {
+        let Some(log) = self.reseed_log.as_mut() else {
+            return;
+        };
+
+        let record = ReseedRecord {
+            timestamp: chrono::Utc::now(),
+            counter: self.rng.reseed_count(),
+            entropy_estimate: seed.seed_entropy_estimate(),
+            source_frames: seed.source_frames(),
+            health_score: self.health.summary().healthy_fraction,
+        };
+        if let Err(e) = log.record(&record) {
+            tracing::warn!("Failed to write reseed log: {}", e);
+        }
+    }
+
+    /// Tracks difference energy and warns once when the scene stays static.
+    fn track_scene_motion(&mut self, frame: &Frame) {
+        let Some(energy) = self.extractor.last_difference_energy() else {
+            return;
+        };
+
+        let per_pixel = energy as f64 / frame.pixels().len().max(1) as f64;
+        if per_pixel >= STATIC_SCENE_ENERGY_PER_PIXEL {
+            self.static_frames = 0;
+            return;
+        }
+
+        self.static_frames = self.static_frames.saturating_add(1);
+        if self.static_frames == STATIC_SCENE_FRAMES {
+            tracing::warn!(
+                frames = self.static_frames,
+                energy_per_pixel = per_pixel,
+                "Scene too static: little temporal difference to extract"
+            );
+        }
+    }
+
+    /// Returns true if recent frames have shown almost no motion.
+    pub fn is_scene_static(&self) -> bool {
+        self.static_frames >= STATIC_SCENE_FRAMES
+    }
+
+    /// Resets every stage after a quality failure.
+    ///
+    /// Drops the differencer's previous frame, discards pooled bits
+    /// that may predate the degradation, and requires a fresh healthy
+    /// streak before the next reseed.
+    pub fn on_unhealthy(&mut self) {
+        self.extractor.reset();
+        self.pool.clear();
+        self.health.reset_streak();
+        tracing::debug!("Pipeline reset after quality failure");
+    }
+
+    /// Returns the extractor.
+    pub fn extractor(&self) -> &Extractor {
+        &self.extractor
+    }
+
+    /// Returns the entropy pool.
+    pub fn pool(&self) -> &EntropyPool {
+        &self.pool
+    }
+
+    /// Returns the health monitor.
+    pub fn health(&self) -> &HealthMonitor {
+        &self.health
+    }
+
+    /// Returns the health monitor mutably (e.g. to register callbacks).
+    pub fn health_mut(&mut self) -> &mut HealthMonitor {
+        &mut self.health
+    }
+
+    /// Returns the camera, if the pipeline owns one.
+    pub fn camera(&self) -> Option<&dyn Camera> {
+        self.camera.as_deref()
+    }
+
+    /// Returns the CSPRNG.
+    pub fn rng(&self) -> &ReseedableRng {
+        &self.rng
+    }
+
+    /// Returns the CSPRNG mutably, for generating output.
+    pub fn rng_mut(&mut self) -> &mut ReseedableRng {
+        &mut self.rng
+    }
+}
+
+impl Default for Pipeline {
+    fn default() -> Self {
+        Self::new(
+            Extractor::new(),
+            EntropyPool::default(),
+            HealthMonitor::default(),
+            ReseedableRng::from_os_entropy(),
+        )
+    }
+}
+
+/// Appends `data` to a dump sink, if one is set.
+///
+/// A failed write is logged and stops further dumping, since a stream
+/// with gaps is useless for statistical testing.
+fn write_dump(dump: &mut Option<Box<dyn Write + Send>>, data: &[u8], kind: &str) {
+    let Some(writer) = dump.as_mut() else {
+        return;
+    };
+    if let Err(e) = writer.write_all(data) {
+        tracing::warn!("Failed to write {} dump, disabling it: {}", kind, e);
+        *dump = None;
+    }
+}
+
+/// Captures up to `frames` frames and returns one conditioned seed.
+///
+/// Runs extraction and health analysis on each frame, pools the
+/// healthy bits, and conditions the pool once it is ready and the
+/// source has proven healthy. Returns `None` if that does not happen
+/// within `frames` frames. Capture errors are skipped.
+pub fn quick_seed<C: Camera>(camera: &mut C, frames: usize) -> Option<ConditionedSeed> {
+    let mut extractor = Extractor::new();
+    let mut pool = EntropyPool::default();
+    let mut health = HealthMonitor::default();
+
+    for _ in 0..frames {
+        let Ok(frame) = camera.capture() else {
+            continue;
+        };
+        if let Some(bits) = extractor.process(&frame) {
+            if health.analyze(&bits).is_healthy {
+                pool.add(&bits);
+            }
+        }
+        if pool.is_ready() && health.allow_reseed() {
+            return pool.extract();
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::QualityThresholds;
+    use crate::capture::CaptureConfig;
+    use crate::conditioning::PoolConfig;
+
+    fn varied_frame(sequence: u64) -> Frame {
+        let pixels = (0..1024u64)
+            .map(|i| ((i ^ (sequence << 32)).wrapping_mul(0x9E37_79B9_7F4A_7C15) >> 56) as u8)
+            .collect();
+        Frame::new(pixels, 32, 32, sequence)
+    }
+
+    #[test]
+    fn test_on_unhealthy_resets_stages() {
+        let pool = EntropyPool::new(PoolConfig {
+            min_bits: 1 << 20,
+            ..Default::default()
+        });
+        let health = HealthMonitor::with_streak_requirement(QualityThresholds::permissive(), 1);
+        let mut pipeline = Pipeline::new(
+            Extractor::new(),
+            pool,
+            health,
+            ReseedableRng::from_os_entropy(),
+        )
+        .with_reset_on_unhealthy(true);
+
+        pipeline.process(&varied_frame(1)).unwrap();
+        pipeline.process(&varied_frame(2)).unwrap();
+        assert!(pipeline.pool().size_bytes() > 0);
+        assert!(pipeline.extractor().is_primed());
+
+        // A frozen frame differences to all zeros and fails health checks
+        let frozen = Frame::new(varied_frame(2).pixels().to_vec(), 32, 32, 3);
+        let outcome = pipeline.process(&frozen).unwrap();
+
+        assert_eq!(outcome, FrameOutcome::Rejected);
+        assert_eq!(pipeline.pool().size_bytes(), 0);
+        assert!(!pipeline.extractor().is_primed());
+        assert_eq!(pipeline.health().metrics().consecutive_healthy, 0);
+    }
+
+    #[test]
+    fn test_quarantined_frames_do_not_reset() {
+        let health = HealthMonitor::with_streak_requirement(QualityThresholds::permissive(), 1)
+            .with_quarantine(1, 5);
+        let mut pipeline = Pipeline::new(
+            Extractor::new(),
+            EntropyPool::default(),
+            health,
+            ReseedableRng::from_os_entropy(),
+        )
+        .with_reset_on_unhealthy(true);
+
+        pipeline.process(&varied_frame(1)).unwrap();
+        pipeline.process(&varied_frame(2)).unwrap();
+        let frozen = Frame::new(varied_frame(2).pixels().to_vec(), 32, 32, 3);
+        assert_eq!(pipeline.process(&frozen).unwrap(), FrameOutcome::Rejected);
+        assert!(pipeline.health().is_quarantined());
+
+        // Skipped samples are rejected without dropping the differencer
+        assert_eq!(
+            pipeline.process(&varied_frame(4)).unwrap(),
+            FrameOutcome::Pending
+        );
+        assert_eq!(
+            pipeline.process(&varied_frame(5)).unwrap(),
+            FrameOutcome::Rejected
+        );
+        assert!(pipeline.extractor().is_primed());
+    }
+
+    #[test]
+    fn test_dry_run_never_reseeds() {
+        let pool = EntropyPool::new(PoolConfig {
+            min_bits: 8 * 1024,
+            ..Default::default()
+        });
+        let health = HealthMonitor::with_streak_requirement(QualityThresholds::permissive(), 1);
+        let mut pipeline = Pipeline::new(
+            Extractor::new(),
+            pool,
+            health,
+            ReseedableRng::from_os_entropy(),
+        )
+        .with_dry_run(true);
+
+        let mut would_reseed = 0;
+        for sequence in 1..=10 {
+            if let FrameOutcome::WouldReseed { .. } =
+                pipeline.process(&varied_frame(sequence)).unwrap()
+            {
+                would_reseed += 1;
+            }
+        }
+
+        assert!(would_reseed > 0);
+        assert_eq!(pipeline.rng().reseed_count(), 0);
+        assert_eq!(pipeline.health().metrics().consecutive_healthy, 9);
+    }
+
+    #[test]
+    fn test_dry_run_quarantine_leaves_rng_untouched() {
+        let health = HealthMonitor::with_streak_requirement(QualityThresholds::permissive(), 1)
+            .with_quarantine(1, 5);
+        let mut pipeline = Pipeline::new(
+            Extractor::new(),
+            EntropyPool::default(),
+            health,
+            ReseedableRng::from_os_entropy(),
+        )
+        .with_dry_run(true);
+
+        pipeline.process(&varied_frame(1)).unwrap();
+        pipeline.process(&varied_frame(2)).unwrap();
+        let frozen = Frame::new(varied_frame(2).pixels().to_vec(), 32, 32, 3);
+        pipeline.process(&frozen).unwrap();
+
+        assert!(pipeline.health().is_quarantined());
+        assert_eq!(pipeline.rng().reseed_count(), 0);
+    }
+
+    #[test]
+    fn test_small_output_size_drains_chunks() {
+        let health = HealthMonitor::with_streak_requirement(QualityThresholds::permissive(), 1);
+        let mut pipeline = Pipeline::new(
+            Extractor::new().with_output_size(100),
+            EntropyPool::new(PoolConfig {
+                min_bits: 1 << 20,
+                ..Default::default()
+            }),
+            health,
+            ReseedableRng::from_os_entropy(),
+        );
+
+        for sequence in 1..=20 {
+            pipeline.process(&varied_frame(sequence)).unwrap();
+            assert!(pipeline.extractor().pending_output() < 100);
+        }
+        // Every full chunk from 19 differenced frames reached the pool
+        assert_eq!(pipeline.pool().size_bytes(), 19 * 1024 / 100 * 100);
+    }
+
+    #[test]
+    fn test_reseed_log_writes_json_lines() {
+        let path = std::env::temp_dir().join(format!(
+            "optical-entropy-reseed-log-{}.jsonl",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        let pool = EntropyPool::new(PoolConfig {
+            min_bits: 8 * 1024,
+            ..Default::default()
+        });
+        let health = HealthMonitor::with_streak_requirement(QualityThresholds::permissive(), 1);
+        let mut pipeline = Pipeline::new(
+            Extractor::new(),
+            pool,
+            health,
+            ReseedableRng::from_os_entropy(),
+        )
+        .with_reseed_log(ReseedLog::open(&path).unwrap());
+
+        let mut sequence = 0;
+        while pipeline.rng().reseed_count() < 2 {
+            sequence += 1;
+            pipeline.process(&varied_frame(sequence)).unwrap();
+        }
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let records: Vec<serde_json::Value> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(records.len(), 2);
+        for (i, record) in records.iter().enumerate() {
+            assert_eq!(record["counter"], i as u64 + 1);
+            assert_eq!(record["entropy_estimate"], 256);
+            assert!(record["source_frames"].as_u64().unwrap() > 0);
+            assert!(record["health_score"].as_f64().unwrap() > 0.0);
+            assert!(record["timestamp"].is_string());
+        }
+    }
+
+    #[test]
+    fn test_warmup_frames_not_pooled() {
+        let config = CaptureConfig {
+            warmup_frames: 5,
+            ..Default::default()
+        };
+        let health = HealthMonitor::with_streak_requirement(QualityThresholds::permissive(), 1);
+        let mut pipeline = Pipeline::new(
+            Extractor::with_warmup(config.warmup_frames),
+            EntropyPool::default(),
+            health,
+            ReseedableRng::from_os_entropy(),
+        );
+
+        // One priming frame plus five discarded outputs
+        for sequence in 1..=6 {
+            let outcome = pipeline.process(&varied_frame(sequence)).unwrap();
+            assert_eq!(outcome, FrameOutcome::Pending);
+            assert_eq!(pipeline.pool().size_bytes(), 0);
+        }
+        assert_eq!(pipeline.extractor().warmup_remaining(), 0);
+
+        let outcome = pipeline.process(&varied_frame(7)).unwrap();
+        assert_ne!(outcome, FrameOutcome::Pending);
+        assert_eq!(pipeline.health().metrics().total_samples, 1);
+    }
+
+    #[test]
+    fn test_static_scene_detected() {
+        let mut pipeline = Pipeline::default();
+
+        let pixels = varied_frame(1).pixels().to_vec();
+        let mut sequence = 0;
+        for _ in 0..=STATIC_SCENE_FRAMES {
+            sequence += 1;
+            pipeline
+                .process(&Frame::new(pixels.clone(), 32, 32, sequence))
+                .unwrap();
+        }
+        assert!(pipeline.is_scene_static());
+
+        pipeline.process(&varied_frame(sequence + 1)).unwrap();
+        assert!(!pipeline.is_scene_static());
+    }
+
+    #[test]
+    fn test_quick_seed_from_mock() {
+        use crate::capture::MockCamera;
+
+        let mut camera = MockCamera::with_seed(1);
+        camera.open(&CaptureConfig::with_dimensions(64, 64)).unwrap();
+
+        let seed = quick_seed(&mut camera, 20);
+        assert!(seed.is_some());
+        assert_eq!(seed.unwrap().entropy_estimate(), 256);
+    }
+}
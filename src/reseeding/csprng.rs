@@ -1,14 +1,16 @@
 //! ChaCha-based CSPRNG with reseeding support.
 //!
 //! Wraps the standard ChaCha20 CSPRNG with an interface for
-//! reseeding from conditioned optical entropy.
+//! reseeding from conditioned optical entropy. ChaCha8 and ChaCha12
+//! can be selected instead; see [`ChaChaRounds`].
 //!
 //! # Reseeding Model
 //!
 //! Reseeding uses BLAKE3 to mix:
 //! - Previous seed material (retained across reseeds)
 //! - New conditioned entropy
-//! - A domain separator and reseed counter
+//! - A domain separator and reseed counter (64-bit, little-endian
+//!   unless configured otherwise; see [`ByteOrder`])
 //!
 //! This follows NIST SP 800-90A style DRBG reseeding logic:
 //! non-linear mixing via a cryptographic hash ensures that
@@ -16,24 +18,257 @@
 
 use blake3::Hasher;
 use crate::conditioning::ConditionedSeed;
-use rand_chacha::ChaCha20Rng;
+use rand_chacha::{ChaCha12Rng, ChaCha20Rng, ChaCha8Rng};
 use rand_core::{RngCore, SeedableRng};
 use thiserror::Error;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
-/// Domain separator for reseeding operations.
+/// Default domain separator for reseeding operations.
 /// Ensures the hash context is distinct from other uses.
-const RESEED_DOMAIN: &[u8] = b"optical-entropy-reseed-v1";
+pub const RESEED_DOMAIN: &[u8] = b"optical-entropy-reseed-v1";
+
+/// First 32 bytes of the ChaCha8 keystream for an all-zero key and
+/// nonce (draft-strombergson-chacha-test-vectors, TC1).
+const KNOWN_ANSWER_8: [u8; 32] = [
+    0x3e, 0x00, 0xef, 0x2f, 0x89, 0x5f, 0x40, 0xd6, 0x7f, 0x5b, 0xb8, 0xe8, 0x1f, 0x09, 0xa5, 0xa1,
+    0x2c, 0x84, 0x0e, 0xc3, 0xce, 0x9a, 0x7f, 0x3b, 0x18, 0x1b, 0xe1, 0x88, 0xef, 0x71, 0x1a, 0x1e,
+];
+
+/// First 32 bytes of the ChaCha12 keystream for an all-zero key and
+/// nonce (draft-strombergson-chacha-test-vectors, TC1).
+const KNOWN_ANSWER_12: [u8; 32] = [
+    0x9b, 0xf4, 0x9a, 0x6a, 0x07, 0x55, 0xf9, 0x53, 0x81, 0x1f, 0xce, 0x12, 0x5f, 0x26, 0x83, 0xd5,
+    0x04, 0x29, 0xc3, 0xbb, 0x49, 0xe0, 0x74, 0x14, 0x7e, 0x00, 0x89, 0xa5, 0x2e, 0xae, 0x15, 0x5f,
+];
+
+/// First 32 bytes of the ChaCha20 keystream for an all-zero key and
+/// nonce (RFC 8439, appendix A.1, test vector #1).
+const KNOWN_ANSWER_20: [u8; 32] = [
+    0x76, 0xb8, 0xe0, 0xad, 0xa0, 0xf1, 0x3d, 0x90, 0x40, 0x5d, 0x6a, 0xe5, 0x53, 0x86, 0xbd, 0x28,
+    0xbd, 0xd2, 0x19, 0xb8, 0xa0, 0x8d, 0xed, 0x1a, 0xa8, 0x36, 0xef, 0xcc, 0x8b, 0x77, 0x0d, 0xc7,
+];
+
+/// Reseeds any 32-byte-seeded generator with conditioned entropy.
+///
+/// Applies the same BLAKE3 mixing as [`ReseedableRng::reseed`] with the
+/// default [`RESEED_DOMAIN`]: `prior` is replaced with
+/// `BLAKE3(domain || counter || prior || seed)` and `rng` is rebuilt from
+/// it. The caller keeps `prior` across calls, increments `counter` after
+/// each one, and is responsible for checking the seed's entropy estimate.
+pub fn reseed_rng<R: SeedableRng<Seed = [u8; 32]>>(
+    rng: &mut R,
+    prior: &mut [u8; 32],
+    seed: &ConditionedSeed,
+    counter: u64,
+) {
+    reseed_rng_with_domain(rng, prior, seed, counter, RESEED_DOMAIN);
+}
+
+/// Like [`reseed_rng`], with a custom domain separator.
+pub fn reseed_rng_with_domain<R: SeedableRng<Seed = [u8; 32]>>(
+    rng: &mut R,
+    prior: &mut [u8; 32],
+    seed: &ConditionedSeed,
+    counter: u64,
+    domain: &[u8],
+) {
+    *prior = mix_seed_material(
+        domain,
+        ByteOrder::LittleEndian,
+        counter,
+        prior,
+        seed.as_bytes(),
+        None,
+    );
+    *rng = R::from_seed(*prior);
+}
+
+/// Derives new seed material:
+/// `BLAKE3(domain || counter || prior || entropy [|| reinforcement])`,
+/// where the reseed counter is hashed as 8 bytes in `order`.
+///
+/// Every reseed path goes through here, so they cannot drift apart.
+fn mix_seed_material(
+    domain: &[u8],
+    order: ByteOrder,
+    counter: u64,
+    prior: &[u8; 32],
+    entropy: &[u8; 32],
+    reinforcement: Option<&[u8; 32]>,
+) -> [u8; 32] {
+    let mut hasher = Hasher::new();
+    hasher.update(domain);
+    hasher.update(&order.encode(counter));
+    hasher.update(prior);
+    hasher.update(entropy);
+    if let Some(reinforcement) = reinforcement {
+        hasher.update(reinforcement);
+    }
+    *hasher.finalize().as_bytes()
+}
+
+/// Byte order of the reseed counter in the mixing input.
+///
+/// The counter is hashed as 8 bytes. Little-endian is the default and
+/// what [`reseed_rng`] always uses; an implementation in another
+/// language must encode it the same way to derive identical seeds.
+/// Both orders encode a counter of 0 identically.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ByteOrder {
+    /// Least significant byte first.
+    #[default]
+    LittleEndian,
+    /// Most significant byte first (network order).
+    BigEndian,
+}
+
+impl ByteOrder {
+    /// Encodes a counter in this byte order.
+    pub fn encode(self, value: u64) -> [u8; 8] {
+        match self {
+            ByteOrder::LittleEndian => value.to_le_bytes(),
+            ByteOrder::BigEndian => value.to_be_bytes(),
+        }
+    }
+}
+
+/// ChaCha variant generating a [`ReseedableRng`]'s output.
+///
+/// Fewer rounds run faster with a smaller security margin. ChaCha8
+/// has no known practical attack, but ChaCha20 is the conservative
+/// default. Reseed mixing is the same for every variant, so variants
+/// seeded identically share seed material but not output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ChaChaRounds {
+    /// ChaCha8.
+    Eight,
+    /// ChaCha12.
+    Twelve,
+    /// ChaCha20.
+    #[default]
+    Twenty,
+}
+
+impl ChaChaRounds {
+    /// Every supported variant, fewest rounds first.
+    pub const ALL: [ChaChaRounds; 3] = [
+        ChaChaRounds::Eight,
+        ChaChaRounds::Twelve,
+        ChaChaRounds::Twenty,
+    ];
+
+    /// Returns the number of rounds.
+    pub fn rounds(self) -> u32 {
+        match self {
+            ChaChaRounds::Eight => 8,
+            ChaChaRounds::Twelve => 12,
+            ChaChaRounds::Twenty => 20,
+        }
+    }
+
+    /// Checks this variant against its known-answer vector.
+    ///
+    /// Seeds the generator a [`ReseedableRng`] would build with the
+    /// all-zero test key and compares its first 32 output bytes with
+    /// the reference keystream.
+    pub fn self_test(self) -> bool {
+        let mut output = [0u8; 32];
+        ChaChaGenerator::new(self, [0u8; 32])
+            .as_rng()
+            .fill_bytes(&mut output);
+        output == self.known_answer()
+    }
+
+    /// Returns the expected keystream for the all-zero key and nonce.
+    fn known_answer(self) -> [u8; 32] {
+        match self {
+            ChaChaRounds::Eight => KNOWN_ANSWER_8,
+            ChaChaRounds::Twelve => KNOWN_ANSWER_12,
+            ChaChaRounds::Twenty => KNOWN_ANSWER_20,
+        }
+    }
+}
+
+/// A ChaCha generator of any supported round count.
+enum ChaChaGenerator {
+    Eight(ChaCha8Rng),
+    Twelve(ChaCha12Rng),
+    Twenty(ChaCha20Rng),
+}
+
+impl ChaChaGenerator {
+    fn new(rounds: ChaChaRounds, seed: [u8; 32]) -> Self {
+        match rounds {
+            ChaChaRounds::Eight => ChaChaGenerator::Eight(ChaCha8Rng::from_seed(seed)),
+            ChaChaRounds::Twelve => ChaChaGenerator::Twelve(ChaCha12Rng::from_seed(seed)),
+            ChaChaRounds::Twenty => ChaChaGenerator::Twenty(ChaCha20Rng::from_seed(seed)),
+        }
+    }
+
+    fn rounds(&self) -> ChaChaRounds {
+        match self {
+            ChaChaGenerator::Eight(_) => ChaChaRounds::Eight,
+            ChaChaGenerator::Twelve(_) => ChaChaRounds::Twelve,
+            ChaChaGenerator::Twenty(_) => ChaChaRounds::Twenty,
+        }
+    }
+
+    fn as_rng(&mut self) -> &mut dyn RngCore {
+        match self {
+            ChaChaGenerator::Eight(rng) => rng,
+            ChaChaGenerator::Twelve(rng) => rng,
+            ChaChaGenerator::Twenty(rng) => rng,
+        }
+    }
+}
+
+/// How [`ReseedableRng::reseed`] derives new seed material.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReseedMode {
+    /// Mix the prior seed material with the conditioned seed.
+    #[default]
+    Standard,
+    /// Also mix in 32 bytes drawn from the current generator.
+    ///
+    /// The new state then depends on the whole output history, not
+    /// just the retained seed material, so an attacker who learns
+    /// every optical seed still cannot follow the rekeying.
+    Reinforce,
+}
 
 /// Errors that can occur during reseeding.
 #[derive(Debug, Error)]
 pub enum ReseedingError {
+    /// The seed's entropy estimate is below the configured minimum.
     #[error("insufficient entropy: got {got} bits, need {need} bits")]
-    InsufficientEntropy { got: usize, need: usize },
+    InsufficientEntropy {
+        /// Entropy estimate of the offered seed, in bits.
+        got: usize,
+        /// Required minimum, in bits.
+        need: usize,
+    },
+}
+
+/// How much output a [`ReseedableRng`] has produced, and under which key.
+///
+/// Each reseed starts a new key, so `(reseed_count, bytes_since_reseed)`
+/// orders outputs and says how much was drawn before each one. The
+/// byte count is output requested, not a keystream offset: the
+/// generator consumes its keystream in 32-bit words, so mixing
+/// `next_u32`, `next_u64` and `fill_bytes` can skip keystream bytes
+/// that are never counted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StreamPosition {
+    /// Reseeds performed so far; identifies the current key.
+    pub reseed_count: u64,
+    /// Bytes of output drawn under the current key.
+    pub bytes_since_reseed: u64,
 }
 
 /// A reseedable CSPRNG backed by ChaCha20.
 ///
-/// This wraps the standard ChaCha20Rng with an interface designed
+/// This wraps the standard ChaCha20Rng (or a reduced-round variant,
+/// see [`with_rounds`](Self::with_rounds)) with an interface designed
 /// for periodic reseeding from optical entropy. The CSPRNG is
 /// initialized from OS entropy and can be reseeded with conditioned
 /// optical entropy to supplement (not replace) the initial seed.
@@ -44,9 +279,11 @@ pub enum ReseedingError {
 /// - Optical entropy is mixed in via BLAKE3 (non-linear, analyzed)
 /// - Previous seed material is retained and mixed with new entropy
 /// - Compromising only the optical source cannot predict outputs
+/// - Retained seed material is zeroized on drop (the ChaCha state
+///   inside `rand_chacha` is not)
 pub struct ReseedableRng {
-    /// The underlying ChaCha20 CSPRNG.
-    inner: ChaCha20Rng,
+    /// The underlying ChaCha CSPRNG.
+    inner: ChaChaGenerator,
     /// Retained seed material for mixing during reseed.
     /// This is NOT the ChaCha internal state.
     seed_material: [u8; 32],
@@ -56,6 +293,12 @@ pub struct ReseedableRng {
     reseed_count: u64,
     /// Bytes generated since last reseed.
     bytes_since_reseed: u64,
+    /// Domain separator mixed into every reseed.
+    domain: Vec<u8>,
+    /// How reseeds derive new seed material.
+    mode: ReseedMode,
+    /// Encoding of the reseed counter in the mixing input.
+    counter_order: ByteOrder,
 }
 
 impl ReseedableRng {
@@ -70,40 +313,106 @@ impl ReseedableRng {
         rand_core::OsRng.fill_bytes(&mut seed_material);
 
         Self {
-            inner: ChaCha20Rng::from_seed(seed_material),
+            inner: ChaChaGenerator::new(ChaChaRounds::Twenty, seed_material),
             seed_material,
             min_entropy_bits: 128,
             reseed_count: 0,
             bytes_since_reseed: 0,
+            domain: RESEED_DOMAIN.to_vec(),
+            mode: ReseedMode::Standard,
+            counter_order: ByteOrder::LittleEndian,
         }
     }
 
     /// Creates a CSPRNG with a specific minimum entropy requirement.
     pub fn with_min_entropy(min_entropy_bits: usize) -> Self {
-        Self {
-            min_entropy_bits,
-            ..Self::from_os_entropy()
-        }
+        let mut rng = Self::from_os_entropy();
+        rng.min_entropy_bits = min_entropy_bits;
+        rng
+    }
+
+    /// Creates a CSPRNG with a custom reseed domain separator.
+    ///
+    /// Independent instances that must never derive related seeds
+    /// (e.g. several daemons fed by the same camera) should each use
+    /// a distinct domain. Defaults to [`RESEED_DOMAIN`].
+    pub fn with_domain(domain: &[u8]) -> Self {
+        let mut rng = Self::from_os_entropy();
+        rng.domain = domain.to_vec();
+        rng
+    }
+
+    /// Sets how reseeds derive new seed material.
+    pub fn with_reseed_mode(mut self, mode: ReseedMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the byte order of the reseed counter in the mixing input.
+    ///
+    /// Only needed to match another implementation that encodes the
+    /// counter big-endian; see [`ByteOrder`].
+    pub fn with_counter_byte_order(mut self, order: ByteOrder) -> Self {
+        self.counter_order = order;
+        self
+    }
+
+    /// Selects the ChaCha variant, restarting it from the current seed material.
+    ///
+    /// Meant for construction time: output already drawn is not
+    /// continued, the new generator starts afresh.
+    pub fn with_rounds(mut self, rounds: ChaChaRounds) -> Self {
+        self.inner = ChaChaGenerator::new(rounds, self.seed_material);
+        self
+    }
+
+    /// Returns the ChaCha variant in use.
+    pub fn rounds(&self) -> ChaChaRounds {
+        self.inner.rounds()
     }
 
     /// Creates a CSPRNG from a known seed (for testing only).
     #[cfg(test)]
     pub(crate) fn from_seed_for_testing(seed: [u8; 32]) -> Self {
         Self {
-            inner: ChaCha20Rng::from_seed(seed),
+            inner: ChaChaGenerator::new(ChaChaRounds::Twenty, seed),
             seed_material: seed,
             min_entropy_bits: 128,
             reseed_count: 0,
             bytes_since_reseed: 0,
+            domain: RESEED_DOMAIN.to_vec(),
+            mode: ReseedMode::Standard,
+            counter_order: ByteOrder::LittleEndian,
         }
     }
 
+    /// Checks every ChaCha variant against its known-answer vector.
+    ///
+    /// See [`ChaChaRounds::self_test`]. A mismatch means the build is
+    /// mis-seeding or miscomputing ChaCha, and no output from it should
+    /// be trusted. This checks the generator itself, not the quality of
+    /// the optical input.
+    pub fn self_test() -> bool {
+        let mut passed = true;
+        for rounds in ChaChaRounds::ALL {
+            if !rounds.self_test() {
+                tracing::error!(
+                    rounds = rounds.rounds(),
+                    "CSPRNG known-answer self-test failed"
+                );
+                passed = false;
+            }
+        }
+        passed
+    }
+
     /// Reseeds the CSPRNG with conditioned optical entropy.
     ///
     /// The new seed is derived by hashing together:
     /// - The previous seed material
     /// - The new conditioned entropy
     /// - A domain separator and reseed counter
+    /// - In [`ReseedMode::Reinforce`], 32 bytes of current generator output
     ///
     /// This ensures:
     /// - Non-linear mixing (hash, not XOR)
@@ -111,38 +420,65 @@ impl ReseedableRng {
     /// - Forward secrecy properties are maintained
     /// - Compromising optical source alone cannot predict outputs
     pub fn reseed(&mut self, seed: &ConditionedSeed) -> Result<(), ReseedingError> {
-        if seed.entropy_estimate() < self.min_entropy_bits {
+        if seed.seed_entropy_estimate() < self.min_entropy_bits {
             return Err(ReseedingError::InsufficientEntropy {
-                got: seed.entropy_estimate(),
+                got: seed.seed_entropy_estimate(),
                 need: self.min_entropy_bits,
             });
         }
 
-        // Mix using BLAKE3:
-        // new_seed = BLAKE3(domain || counter || old_seed_material || new_entropy)
-        let mut hasher = Hasher::new();
-        hasher.update(RESEED_DOMAIN);
-        hasher.update(&self.reseed_count.to_le_bytes());
-        hasher.update(&self.seed_material);
-        hasher.update(seed.as_bytes());
-
-        let new_seed_material: [u8; 32] = *hasher.finalize().as_bytes();
-
-        // Update state
-        self.seed_material = new_seed_material;
-        self.inner = ChaCha20Rng::from_seed(new_seed_material);
-        self.reseed_count += 1;
-        self.bytes_since_reseed = 0;
+        let reinforcement = match self.mode {
+            ReseedMode::Standard => None,
+            ReseedMode::Reinforce => {
+                let mut own = Zeroizing::new([0u8; 32]);
+                self.inner.as_rng().fill_bytes(own.as_mut());
+                Some(own)
+            }
+        };
+        self.mix(seed.as_bytes(), reinforcement.as_deref());
 
         tracing::info!(
             reseed_count = self.reseed_count,
-            entropy_estimate = seed.entropy_estimate(),
+            entropy_estimate = seed.seed_entropy_estimate(),
             "CSPRNG reseeded via BLAKE3 mixing"
         );
 
         Ok(())
     }
 
+    /// Reseeds the CSPRNG with fresh OS entropy.
+    ///
+    /// Uses the same BLAKE3 mixing as [`reseed`](Self::reseed), so the
+    /// generator keeps moving forward while the optical source is
+    /// unavailable (e.g. quarantined). Like any reseed, it resets
+    /// [`bytes_since_reseed`](Self::bytes_since_reseed).
+    pub fn reseed_from_os(&mut self) {
+        let mut fresh = Zeroizing::new([0u8; 32]);
+        rand_core::OsRng.fill_bytes(fresh.as_mut());
+        self.mix(&fresh, None);
+
+        tracing::info!(
+            reseed_count = self.reseed_count,
+            "CSPRNG reseeded from OS entropy"
+        );
+    }
+
+    /// Mixes new entropy, and any reinforcement, into the seed material
+    /// and rekeys the generator.
+    fn mix(&mut self, entropy: &[u8; 32], reinforcement: Option<&[u8; 32]>) {
+        self.seed_material = mix_seed_material(
+            &self.domain,
+            self.counter_order,
+            self.reseed_count,
+            &self.seed_material,
+            entropy,
+            reinforcement,
+        );
+        self.inner = ChaChaGenerator::new(self.inner.rounds(), self.seed_material);
+        self.reseed_count += 1;
+        self.bytes_since_reseed = 0;
+    }
+
     /// Returns the number of reseeds performed.
     pub fn reseed_count(&self) -> u64 {
         self.reseed_count
@@ -152,27 +488,74 @@ impl ReseedableRng {
     pub fn bytes_since_reseed(&self) -> u64 {
         self.bytes_since_reseed
     }
+
+    /// Returns the current position in the output stream.
+    ///
+    /// Record it before drawing output to note which key produced that
+    /// output and how much came before it. The byte count follows
+    /// [`bytes_since_reseed`](Self::bytes_since_reseed), including any
+    /// [`reset_byte_counter`](Self::reset_byte_counter) call; see
+    /// [`StreamPosition`].
+    pub fn stream_position(&self) -> StreamPosition {
+        StreamPosition {
+            reseed_count: self.reseed_count,
+            bytes_since_reseed: self.bytes_since_reseed,
+        }
+    }
+
+    /// Restarts the [`bytes_since_reseed`](Self::bytes_since_reseed)
+    /// count from zero without touching the generator.
+    ///
+    /// For keeping staleness metrics accurate after an intervention
+    /// this type does not see, such as reseeding the process from the
+    /// OS by other means. The output stream continues unchanged.
+    pub fn reset_byte_counter(&mut self) {
+        self.bytes_since_reseed = 0;
+    }
+
+    /// Returns the domain separator used for reseeding.
+    pub fn domain(&self) -> &[u8] {
+        &self.domain
+    }
+}
+
+impl Zeroize for ReseedableRng {
+    /// Wipes the retained seed material.
+    ///
+    /// The generator keeps producing output from its current state,
+    /// but the next reseed mixes from all-zero material.
+    fn zeroize(&mut self) {
+        self.seed_material.zeroize();
+    }
+}
+
+impl Drop for ReseedableRng {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
 }
 
+impl ZeroizeOnDrop for ReseedableRng {}
+
 impl RngCore for ReseedableRng {
     fn next_u32(&mut self) -> u32 {
         self.bytes_since_reseed += 4;
-        self.inner.next_u32()
+        self.inner.as_rng().next_u32()
     }
 
     fn next_u64(&mut self) -> u64 {
         self.bytes_since_reseed += 8;
-        self.inner.next_u64()
+        self.inner.as_rng().next_u64()
     }
 
     fn fill_bytes(&mut self, dest: &mut [u8]) {
         self.bytes_since_reseed += dest.len() as u64;
-        self.inner.fill_bytes(dest);
+        self.inner.as_rng().fill_bytes(dest);
     }
 
     fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
         self.bytes_since_reseed += dest.len() as u64;
-        self.inner.try_fill_bytes(dest)
+        self.inner.as_rng().try_fill_bytes(dest)
     }
 }
 
@@ -184,6 +567,24 @@ mod tests {
         ConditionedSeed::new_for_testing(data, entropy)
     }
 
+    #[test]
+    fn test_self_test_passes() {
+        assert!(ReseedableRng::self_test());
+    }
+
+    #[test]
+    fn test_known_answers_match_instance_rounds() {
+        for rounds in ChaChaRounds::ALL {
+            assert!(rounds.self_test());
+
+            // An instance of each variant draws the reference keystream
+            let mut rng = ReseedableRng::from_seed_for_testing([0u8; 32]).with_rounds(rounds);
+            let mut output = [0u8; 32];
+            rng.fill_bytes(&mut output);
+            assert_eq!(output, rng.rounds().known_answer());
+        }
+    }
+
     #[test]
     fn test_reseed_increments_count() {
         let mut rng = ReseedableRng::with_min_entropy(64);
@@ -208,6 +609,21 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_xof_entropy_capped_at_seed_length() {
+        let mut rng = ReseedableRng::with_min_entropy(384);
+
+        // 512 bits estimated across 64 bytes, but only 32 are consumed
+        let seed = ConditionedSeed::from_parts(vec![0x42; 64], 512);
+        assert!(matches!(
+            rng.reseed(&seed),
+            Err(ReseedingError::InsufficientEntropy {
+                got: 256,
+                need: 384
+            })
+        ));
+    }
+
     #[test]
     fn test_bytes_since_reseed_tracking() {
         let mut rng = ReseedableRng::from_os_entropy();
@@ -218,6 +634,59 @@ mod tests {
         assert_eq!(rng.bytes_since_reseed(), 100);
     }
 
+    #[test]
+    fn test_reset_byte_counter_keeps_output_continuous() {
+        let mut rng = ReseedableRng::from_seed_for_testing([0x01; 32]);
+        let mut reference = ReseedableRng::from_seed_for_testing([0x01; 32]);
+
+        let mut buf = [0u8; 100];
+        rng.fill_bytes(&mut buf);
+        reference.fill_bytes(&mut buf);
+        assert_eq!(rng.bytes_since_reseed(), 100);
+
+        rng.reset_byte_counter();
+        assert_eq!(rng.bytes_since_reseed(), 0);
+        assert_eq!(rng.reseed_count(), 0);
+
+        let mut out = [0u8; 32];
+        let mut expected = [0u8; 32];
+        rng.fill_bytes(&mut out);
+        reference.fill_bytes(&mut expected);
+        assert_eq!(out, expected);
+        assert_eq!(rng.bytes_since_reseed(), 32);
+
+        rng.reseed_from_os();
+        assert_eq!(rng.bytes_since_reseed(), 0);
+    }
+
+    #[test]
+    fn test_stream_position_tracks_draws_and_reseeds() {
+        let mut rng = ReseedableRng::from_seed_for_testing([0x02; 32]);
+        assert_eq!(rng.stream_position(), StreamPosition::default());
+
+        let mut buf = [0u8; 48];
+        rng.fill_bytes(&mut buf);
+        rng.next_u64();
+        assert_eq!(
+            rng.stream_position(),
+            StreamPosition {
+                reseed_count: 0,
+                bytes_since_reseed: 56,
+            }
+        );
+
+        rng.reseed(&make_test_seed([0x03; 32], 256)).unwrap();
+        assert_eq!(
+            rng.stream_position(),
+            StreamPosition {
+                reseed_count: 1,
+                bytes_since_reseed: 0,
+            }
+        );
+        rng.next_u32();
+        assert_eq!(rng.stream_position().bytes_since_reseed, 4);
+    }
+
     #[test]
     fn test_reseed_changes_output() {
         let initial_seed = [0x01u8; 32];
@@ -286,4 +755,148 @@ mod tests {
 
         assert_ne!(out1, out2);
     }
+
+    #[test]
+    fn test_counter_byte_order_changes_derived_seed() {
+        let initial_seed = [0x01u8; 32];
+        let mut le = ReseedableRng::from_seed_for_testing(initial_seed);
+        let mut be = ReseedableRng::from_seed_for_testing(initial_seed)
+            .with_counter_byte_order(ByteOrder::BigEndian);
+        let entropy = make_test_seed([0xAA; 32], 256);
+
+        // Counter 0 encodes the same either way
+        le.reseed(&entropy).unwrap();
+        be.reseed(&entropy).unwrap();
+        assert_eq!(le.seed_material, be.seed_material);
+
+        le.reseed(&entropy).unwrap();
+        be.reseed(&entropy).unwrap();
+        assert_ne!(le.seed_material, be.seed_material);
+    }
+
+    #[test]
+    fn test_chacha12_reseeds_and_differs_from_chacha20() {
+        let initial_seed = [0x01u8; 32];
+        let mut chacha12 =
+            ReseedableRng::from_seed_for_testing(initial_seed).with_rounds(ChaChaRounds::Twelve);
+        let mut chacha20 = ReseedableRng::from_seed_for_testing(initial_seed);
+        assert_eq!(chacha12.rounds(), ChaChaRounds::Twelve);
+        assert_eq!(chacha20.rounds(), ChaChaRounds::Twenty);
+
+        let entropy = make_test_seed([0xAA; 32], 256);
+        chacha12.reseed(&entropy).unwrap();
+        chacha20.reseed(&entropy).unwrap();
+        assert_eq!(chacha12.rounds(), ChaChaRounds::Twelve);
+        assert_eq!(chacha12.seed_material, chacha20.seed_material);
+
+        let mut out12 = [0u8; 32];
+        let mut out20 = [0u8; 32];
+        chacha12.fill_bytes(&mut out12);
+        chacha20.fill_bytes(&mut out20);
+        assert_ne!(out12, [0u8; 32]);
+        assert_ne!(out12, out20);
+
+        let mut expected = [0u8; 32];
+        ChaCha12Rng::from_seed(chacha12.seed_material).fill_bytes(&mut expected);
+        assert_eq!(out12, expected);
+    }
+
+    #[test]
+    fn test_domain_separates_reseed_output() {
+        let initial_seed = [0x01u8; 32];
+        let mut rng1 = ReseedableRng::from_seed_for_testing(initial_seed);
+        let mut rng2 = ReseedableRng::from_seed_for_testing(initial_seed);
+        rng2.domain = b"optical-entropy-reseed-instance-b".to_vec();
+        assert_eq!(rng1.domain(), RESEED_DOMAIN);
+
+        let entropy = make_test_seed([0xAA; 32], 256);
+        rng1.reseed(&entropy).unwrap();
+        rng2.reseed(&entropy).unwrap();
+
+        // Same initial seed and entropy, different domain
+        let mut out1 = [0u8; 32];
+        let mut out2 = [0u8; 32];
+        rng1.fill_bytes(&mut out1);
+        rng2.fill_bytes(&mut out2);
+
+        assert_ne!(out1, out2);
+    }
+
+    #[test]
+    fn test_reseed_rng_on_bare_chacha() {
+        let initial_seed = [0x01u8; 32];
+        let mut wrapped = ReseedableRng::from_seed_for_testing(initial_seed);
+        let mut bare = ChaCha20Rng::from_seed(initial_seed);
+        let mut untouched = ChaCha20Rng::from_seed(initial_seed);
+        let mut prior = initial_seed;
+
+        let entropy = make_test_seed([0xAA; 32], 256);
+        reseed_rng(&mut bare, &mut prior, &entropy, 0);
+        wrapped.reseed(&entropy).unwrap();
+        assert_ne!(prior, initial_seed);
+
+        // Same algorithm as ReseedableRng
+        let mut out1 = [0u8; 32];
+        let mut out2 = [0u8; 32];
+        let mut out3 = [0u8; 32];
+        bare.fill_bytes(&mut out1);
+        wrapped.fill_bytes(&mut out2);
+        untouched.fill_bytes(&mut out3);
+        assert_eq!(out1, out2);
+        assert_ne!(out1, out3);
+    }
+
+    #[test]
+    fn test_reinforce_depends_on_output_history() {
+        let initial_seed = [0x01u8; 32];
+        let entropy = make_test_seed([0xAA; 32], 256);
+
+        for (mode, diverges) in [(ReseedMode::Standard, false), (ReseedMode::Reinforce, true)] {
+            let mut rng1 =
+                ReseedableRng::from_seed_for_testing(initial_seed).with_reseed_mode(mode);
+            let mut rng2 =
+                ReseedableRng::from_seed_for_testing(initial_seed).with_reseed_mode(mode);
+
+            // Advance only one generator before the identical reseed
+            let mut discard = [0u8; 64];
+            rng1.fill_bytes(&mut discard);
+            rng1.reseed(&entropy).unwrap();
+            rng2.reseed(&entropy).unwrap();
+
+            let mut out1 = [0u8; 32];
+            let mut out2 = [0u8; 32];
+            rng1.fill_bytes(&mut out1);
+            rng2.fill_bytes(&mut out2);
+            assert_eq!(out1 != out2, diverges, "{:?}", mode);
+        }
+    }
+
+    #[test]
+    fn test_reseed_from_os() {
+        let initial_seed = [0x01u8; 32];
+        let mut rng1 = ReseedableRng::from_seed_for_testing(initial_seed);
+        let mut rng2 = ReseedableRng::from_seed_for_testing(initial_seed);
+
+        rng1.reseed_from_os();
+        assert_eq!(rng1.reseed_count(), 1);
+
+        let mut out1 = [0u8; 32];
+        let mut out2 = [0u8; 32];
+        rng1.fill_bytes(&mut out1);
+        rng2.fill_bytes(&mut out2);
+
+        assert_ne!(out1, out2);
+    }
+
+    #[test]
+    fn test_seed_material_zeroizes_on_drop() {
+        fn assert_zeroize_on_drop<T: ZeroizeOnDrop>() {}
+        assert_zeroize_on_drop::<ReseedableRng>();
+
+        // Drop runs the same wipe
+        let mut rng = ReseedableRng::from_os_entropy();
+        assert_ne!(rng.seed_material, [0u8; 32]);
+        rng.zeroize();
+        assert_eq!(rng.seed_material, [0u8; 32]);
+    }
 }
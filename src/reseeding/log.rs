@@ -0,0 +1,54 @@
+//! Durable provenance records for reseeds.
+//!
+//! Tracing output is for operators and is usually rotated away. A
+//! [`ReseedLog`] appends one JSON line per reseed to a file, so an
+//! auditor can later establish which captures fed which generator
+//! state and how healthy the source was at the time.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::Path;
+
+/// Provenance of a single reseed.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReseedRecord {
+    /// When the reseed happened.
+    pub timestamp: DateTime<Utc>,
+    /// Reseed counter after the reseed (1 for the first).
+    pub counter: u64,
+    /// Entropy estimate of the seed, in bits.
+    pub entropy_estimate: usize,
+    /// Frames that contributed to the seed.
+    pub source_frames: u64,
+    /// Fraction of health samples that passed, 0.0 to 1.0.
+    pub health_score: f64,
+}
+
+/// Append-only JSON-lines log of [`ReseedRecord`]s.
+pub struct ReseedLog {
+    writer: Box<dyn Write + Send>,
+}
+
+impl ReseedLog {
+    /// Opens `path` for appending, creating it if needed.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self::new(file))
+    }
+
+    /// Logs to an arbitrary writer.
+    pub fn new<W: Write + Send + 'static>(writer: W) -> Self {
+        Self {
+            writer: Box::new(writer),
+        }
+    }
+
+    /// Appends one record and flushes it.
+    pub fn record(&mut self, record: &ReseedRecord) -> io::Result<()> {
+        let line = serde_json::to_string(record)?;
+        writeln!(self.writer, "{}", line)?;
+        self.writer.flush()
+    }
+}
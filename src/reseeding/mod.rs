@@ -4,5 +4,12 @@
 //! with support for reseeding from conditioned entropy.
 
 mod csprng;
+mod log;
+mod tee;
 
-pub use csprng::{ReseedableRng, ReseedingError};
+pub use csprng::{
+    reseed_rng, reseed_rng_with_domain, ByteOrder, ChaChaRounds, ReseedMode, ReseedableRng,
+    ReseedingError, StreamPosition, RESEED_DOMAIN,
+};
+pub use log::{ReseedLog, ReseedRecord};
+pub use tee::{EntropyTee, TEE_LABEL_A, TEE_LABEL_B};
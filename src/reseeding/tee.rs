@@ -0,0 +1,106 @@
+//! Splitting one conditioned seed between two generators.
+//!
+//! Deployments running two services from one capture pipeline can
+//! tee each seed instead of capturing twice. Each branch receives a
+//! BLAKE3 key derivation of the seed under its own label, so outputs
+//! of one generator reveal nothing about the other's seed.
+//!
+//! The branches share the source entropy rather than dividing it: an
+//! attacker who learns the conditioned seed can derive both. Each
+//! sub-seed therefore keeps the full entropy estimate of its parent.
+
+use crate::conditioning::{ConditionedSeed, SEED_LEN};
+
+/// BLAKE3 key-derivation context; the branch label is appended.
+const TEE_CONTEXT: &str = "optical-entropy 2024 tee sub-seed";
+
+/// Label of the first branch.
+pub const TEE_LABEL_A: &str = "a";
+
+/// Label of the second branch.
+pub const TEE_LABEL_B: &str = "b";
+
+/// Derives two domain-separated sub-seeds from one conditioned seed.
+///
+/// # Example
+///
+/// ```no_run
+/// use optical_entropy::reseeding::{EntropyTee, ReseedableRng};
+/// use optical_entropy::{quick_seed, Camera, CaptureConfig, MockCamera};
+///
+/// let mut camera = MockCamera::new();
+/// camera.open(&CaptureConfig::default()).unwrap();
+/// let seed = quick_seed(&mut camera, 64).unwrap();
+///
+/// let (seed_a, seed_b) = EntropyTee::split(&seed);
+/// let mut rng_a = ReseedableRng::from_os_entropy();
+/// let mut rng_b = ReseedableRng::from_os_entropy();
+/// rng_a.reseed(&seed_a).unwrap();
+/// rng_b.reseed(&seed_b).unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EntropyTee;
+
+impl EntropyTee {
+    /// Splits `seed` into the sub-seeds for branches "a" and "b".
+    pub fn split(seed: &ConditionedSeed) -> (ConditionedSeed, ConditionedSeed) {
+        (
+            Self::derive(seed, TEE_LABEL_A),
+            Self::derive(seed, TEE_LABEL_B),
+        )
+    }
+
+    /// Derives the sub-seed for an arbitrary branch label.
+    pub fn derive(seed: &ConditionedSeed, label: &str) -> ConditionedSeed {
+        let context = format!("{TEE_CONTEXT} {label}");
+        let mut hasher = blake3::Hasher::new_derive_key(&context);
+        hasher.update(seed.as_slice());
+
+        let mut data = vec![0u8; SEED_LEN];
+        hasher.finalize_xof().fill(&mut data);
+        ConditionedSeed::from_parts(data, seed.seed_entropy_estimate())
+            .with_source_frames(seed.source_frames())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reseeding::ReseedableRng;
+    use rand_core::RngCore;
+
+    #[test]
+    fn test_split_seeds_differ() {
+        let seed = ConditionedSeed::new_for_testing([0x42; 32], 256);
+        let (a, b) = EntropyTee::split(&seed);
+
+        assert_ne!(a.as_bytes(), b.as_bytes());
+        assert_ne!(a.as_bytes(), seed.as_bytes());
+        assert_eq!(a.entropy_estimate(), 256);
+
+        // Deterministic per label
+        assert_eq!(
+            EntropyTee::derive(&seed, TEE_LABEL_A).as_bytes(),
+            a.as_bytes()
+        );
+    }
+
+    #[test]
+    fn test_split_rngs_diverge() {
+        let initial_seed = [0x01u8; 32];
+        let mut rng_a = ReseedableRng::from_seed_for_testing(initial_seed);
+        let mut rng_b = ReseedableRng::from_seed_for_testing(initial_seed);
+
+        let seed = ConditionedSeed::new_for_testing([0x42; 32], 256);
+        let (a, b) = EntropyTee::split(&seed);
+        rng_a.reseed(&a).unwrap();
+        rng_b.reseed(&b).unwrap();
+
+        let mut out_a = [0u8; 32];
+        let mut out_b = [0u8; 32];
+        rng_a.fill_bytes(&mut out_a);
+        rng_b.fill_bytes(&mut out_b);
+
+        assert_ne!(out_a, out_b);
+    }
+}
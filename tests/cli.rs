@@ -0,0 +1,277 @@
+//! End-to-end tests of the command-line binary.
+
+use std::process::Command;
+
+fn binary() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_optical-entropy"))
+}
+
+#[test]
+fn test_mock_with_sha256() {
+    let output = binary()
+        .args(["--hash", "sha256", "mock", "-n", "5"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    // Ends with the sample random output: 32 bytes as hex
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let sample = stdout.lines().last().unwrap();
+    assert_eq!(sample.len(), 64);
+    assert!(sample.chars().all(|c| c.is_ascii_hexdigit()));
+}
+
+#[test]
+fn test_invalid_hash_rejected() {
+    let output = binary()
+        .args(["--hash", "md5", "mock", "-n", "5"])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_self_test_passes() {
+    let output = binary().arg("self-test").output().unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("known-answer test: ok"));
+}
+
+#[test]
+fn test_effective_config_applies_device_override() {
+    let path = std::env::temp_dir().join(format!("optical-entropy-{}.toml", std::process::id()));
+    std::fs::write(
+        &path,
+        r#"
+[capture]
+device_id = 2
+width = 320
+height = 240
+exposure_us = 10000
+gain = 1
+fps = 30
+grayscale = true
+"#,
+    )
+    .unwrap();
+
+    let output = binary()
+        .arg("--config")
+        .arg(&path)
+        .args(["--device", "5", "effective-config"])
+        .output()
+        .unwrap();
+    let json = binary()
+        .arg("--config")
+        .arg(&path)
+        .args(["--device", "5", "effective-config", "--json"])
+        .output()
+        .unwrap();
+    std::fs::remove_file(&path).ok();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("device_id = 5"));
+    assert!(stdout.contains("width = 320"));
+
+    assert!(json.status.success());
+    assert!(String::from_utf8_lossy(&json.stdout).contains("\"device_id\": 5"));
+}
+
+#[test]
+fn test_effective_config_keeps_file_output_without_flags() {
+    let path = std::env::temp_dir().join(format!(
+        "optical-entropy-output-{}.toml",
+        std::process::id()
+    ));
+    std::fs::write(
+        &path,
+        "[output]\ncontinuous = true\nframe_count = 7\nmetrics_port = 0\n",
+    )
+    .unwrap();
+
+    let effective = |args: &[&str]| {
+        let output = binary()
+            .arg("--config")
+            .arg(&path)
+            .args(args)
+            .args(["effective-config", "--json"])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let config: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        config["output"].clone()
+    };
+
+    let from_file = effective(&[]);
+    let overridden = effective(&["-n", "3"]);
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(from_file["continuous"], true);
+    assert_eq!(from_file["frame_count"], 7);
+    assert_eq!(overridden["frame_count"], 3);
+}
+
+#[test]
+fn test_config_read_from_stdin() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let mut child = binary()
+        .args(["--config", "-", "effective-config"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"[reseed]\nmin_entropy_bits = 192\n")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("min_entropy_bits = 192"));
+}
+
+#[test]
+fn test_bench_reports_throughput() {
+    let output = binary()
+        .args(["bench", "--mock", "--seconds", "0.5", "--json"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let report: serde_json::Value = serde_json::from_str(stdout.lines().last().unwrap()).unwrap();
+    assert!(report["frames"].as_u64().unwrap() > 0);
+    assert!(report["frames_per_sec"].as_f64().unwrap() > 0.0);
+    assert!(
+        report["latency_p99_us"].as_f64().unwrap() >= report["latency_p50_us"].as_f64().unwrap()
+    );
+}
+
+#[test]
+fn test_bench_rejects_infinite_seconds() {
+    for seconds in ["inf", "NaN", "-1", "1e300"] {
+        let output = binary()
+            .args(["bench", "--mock", "--seconds", seconds])
+            .output()
+            .unwrap();
+
+        assert!(!output.status.success(), "accepted {}", seconds);
+        assert_eq!(output.status.code(), Some(2));
+    }
+}
+
+#[test]
+fn test_dump_bits_grows_with_frames() {
+    let dump_size = |frames: &str| {
+        let path = std::env::temp_dir().join(format!(
+            "optical-entropy-bits-{}-{}.bin",
+            std::process::id(),
+            frames
+        ));
+        let output = binary()
+            .arg("--dump-bits")
+            .arg(&path)
+            .args(["mock", "-n", frames])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let size = std::fs::metadata(&path).unwrap().len();
+        std::fs::remove_file(&path).ok();
+        size
+    };
+
+    let short = dump_size("5");
+    let long = dump_size("20");
+    assert!(short > 0);
+    assert!(long > short);
+}
+
+#[test]
+fn test_dump_output_holds_one_seed_per_extraction() {
+    let path =
+        std::env::temp_dir().join(format!("optical-entropy-output-{}.bin", std::process::id()));
+    let output = binary()
+        .arg("--dump-output")
+        .arg(&path)
+        .args(["mock", "-n", "40", "--seed", "1"])
+        .output()
+        .unwrap();
+    let size = std::fs::metadata(&path).unwrap().len();
+    std::fs::remove_file(&path).ok();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let reseeds: u64 = stdout
+        .lines()
+        .find_map(|line| line.split("Total reseeds: ").nth(1))
+        .unwrap()
+        .trim()
+        .parse()
+        .unwrap();
+    assert!(reseeds > 1);
+    assert_eq!(size, 32 * reseeds);
+}
+
+#[test]
+fn test_generate_in_blocks_writes_exact_length() {
+    // Not a multiple of the block size, so the last block is partial
+    let bytes = 3 * 1024 * 1024 + 123;
+    let count = bytes.to_string();
+    let output = binary()
+        .args(["generate", "-n", &count, "--block-size", "65536"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(output.stdout.len(), bytes);
+
+    let output = binary()
+        .args(["generate", "-n", "100", "--hex", "--block-size", "7"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.trim_end().len(), 200);
+    assert!(stdout.trim_end().chars().all(|c| c.is_ascii_hexdigit()));
+}
+
+#[test]
+fn test_summary_written_on_exit() {
+    let path = std::env::temp_dir().join(format!(
+        "optical-entropy-summary-{}.json",
+        std::process::id()
+    ));
+    let output = binary()
+        .arg("--summary")
+        .arg(&path)
+        .args(["mock", "-n", "40", "--seed", "1"])
+        .output()
+        .unwrap();
+    let text = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    assert!(output.status.success());
+    let summary: serde_json::Value = serde_json::from_str(&text).unwrap();
+    let healthy = summary["healthy_frames"].as_u64().unwrap();
+    let unhealthy = summary["unhealthy_frames"].as_u64().unwrap();
+    assert!(healthy > 0);
+    assert_eq!(
+        summary["frames_processed"].as_u64(),
+        Some(healthy + unhealthy)
+    );
+    assert!(summary["reseeds"].as_u64().unwrap() > 0);
+    assert!(summary["healthy_fraction"].as_f64().is_some());
+    assert!(summary["quality_score"].as_f64().is_some());
+    assert!(summary["frames_per_sec"].as_f64().unwrap() > 0.0);
+    assert_eq!(summary["config"]["capture"]["width"].as_u64(), Some(640));
+}